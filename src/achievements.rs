@@ -0,0 +1,125 @@
+//! Contains the [`Achievements`] type, which tracks feats the [`Player`][crate::player::Player] has accomplished
+
+use crate::menu::{Color, Menu, Screen};
+
+/// A feat which the [`Player`][crate::player::Player] can accomplish
+#[derive(Debug, Clone, Copy)]
+pub enum Achievement {
+    /// Win a battle without losing any health
+    FlawlessVictory,
+    /// Get into the [`EscapePod`][crate::rooms::Room::EscapePod]
+    ReachedEscapePod,
+    /// Carry the maps, the escape pod key card and the captain's diary at the same time
+    Collector,
+    /// Get off the ship
+    Escaped,
+}
+
+impl Achievement {
+    /// Every [`Achievement`], in the order they should be listed
+    const ALL: [Self; 4] = [
+        Self::FlawlessVictory,
+        Self::ReachedEscapePod,
+        Self::Collector,
+        Self::Escaped,
+    ];
+
+    /// Gets the name of the [`Achievement`]
+    const fn get_name(self) -> &'static str {
+        match self {
+            Self::FlawlessVictory => "Flawless Victory",
+            Self::ReachedEscapePod => "Light at the End of the Tunnel",
+            Self::Collector => "Collector",
+            Self::Escaped => "Home Free",
+        }
+    }
+
+    /// Gets a description of how the [`Achievement`] is unlocked
+    const fn get_description(self) -> &'static str {
+        match self {
+            Self::FlawlessVictory => "Win a battle without taking any damage",
+            Self::ReachedEscapePod => "Reach the escape pod",
+            Self::Collector => "Carry the maps, the escape pod key card and the captain's diary at once",
+            Self::Escaped => "Escape from the ship",
+        }
+    }
+}
+
+/// The [`Achievement`]s the [`Player`][crate::player::Player] has unlocked.
+/// These are kept when the time loop resets.
+#[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Each achievement is unlocked independently, so it needs its own flag
+pub struct Achievements {
+    /// Whether [`Achievement::FlawlessVictory`] is unlocked
+    pub flawless_victory: bool,
+    /// Whether [`Achievement::ReachedEscapePod`] is unlocked
+    pub reached_escape_pod: bool,
+    /// Whether [`Achievement::Collector`] is unlocked
+    pub collector: bool,
+    /// Whether [`Achievement::Escaped`] is unlocked
+    pub escaped: bool,
+}
+
+impl Achievements {
+    /// Gets a mutable reference to the flag for the given [`Achievement`]
+    fn get_flag_mut(&mut self, achievement: Achievement) -> &mut bool {
+        match achievement {
+            Achievement::FlawlessVictory => &mut self.flawless_victory,
+            Achievement::ReachedEscapePod => &mut self.reached_escape_pod,
+            Achievement::Collector => &mut self.collector,
+            Achievement::Escaped => &mut self.escaped,
+        }
+    }
+
+    /// Gets whether the given [`Achievement`] is unlocked
+    pub const fn is_unlocked(&self, achievement: Achievement) -> bool {
+        match achievement {
+            Achievement::FlawlessVictory => self.flawless_victory,
+            Achievement::ReachedEscapePod => self.reached_escape_pod,
+            Achievement::Collector => self.collector,
+            Achievement::Escaped => self.escaped,
+        }
+    }
+
+    /// Unlocks the given [`Achievement`]. If it wasn't already unlocked, a [`Screen`] is shown to tell the player.
+    pub fn unlock(&mut self, menu: &mut impl Menu, achievement: Achievement) {
+        let flag = self.get_flag_mut(achievement);
+        if *flag {
+            return;
+        }
+        *flag = true;
+
+        let screen = Screen {
+            title: &format!("Achievement unlocked: {}", achievement.get_name()),
+            content: achievement.get_description(),
+            title_color: Some(Color::Yellow),
+            art: None,
+        };
+        menu.show_screen(screen);
+    }
+
+    /// Shows a [`Screen`] listing every [`Achievement`] and whether it is unlocked
+    pub fn show(&self, menu: &mut impl Menu) {
+        let unlocked = Achievement::ALL.iter().filter(|a| self.is_unlocked(**a)).count();
+
+        let list = Achievement::ALL
+            .iter()
+            .map(|a| {
+                if self.is_unlocked(*a) {
+                    format!("✓ {} - {}", a.get_name(), a.get_description())
+                } else {
+                    "✗ ???".to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let screen = Screen {
+            title: &format!("Achievements - {unlocked}/{} unlocked", Achievement::ALL.len()),
+            content: &list,
+            title_color: None,
+            art: None,
+        };
+        menu.show_screen(screen);
+    }
+}
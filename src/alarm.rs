@@ -0,0 +1,44 @@
+//! Contains the [`Alarm`] type, which tracks how hard the crew are looking for the escaped prisoner
+
+use crate::config;
+
+/// The ship-wide alarm. It is raised by noisy actions, such as [long battles][config::ALARM_LOUD_BATTLE_TURNS],
+/// and makes [random encounters][crate::player::Player::roll_random_encounter] more likely and more dangerous until it is silenced from the [bridge][crate::map::RoomAction::BridgeSilenceAlarm].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Alarm {
+    /// How loud the alarm is, from 0 (silent) up to [`ALARM_MAX_LEVEL`][config::ALARM_MAX_LEVEL]
+    level: usize,
+}
+
+impl Alarm {
+    /// Gets how loud the alarm is, from 0 (silent) up to [`ALARM_MAX_LEVEL`][config::ALARM_MAX_LEVEL]
+    pub const fn get_level(self) -> usize {
+        self.level
+    }
+
+    /// Checks whether the alarm is sounding
+    pub const fn is_active(self) -> bool {
+        self.level > 0
+    }
+
+    /// Raises the alarm by one level, up to [`ALARM_MAX_LEVEL`][config::ALARM_MAX_LEVEL].
+    /// Returns `true` if it got louder, or `false` if it was already at the max.
+    pub fn raise(&mut self) -> bool {
+        if self.level == config::ALARM_MAX_LEVEL {
+            return false;
+        }
+
+        self.level += 1;
+        true
+    }
+
+    /// Turns the alarm off completely
+    pub fn silence(&mut self) {
+        self.level = 0;
+    }
+
+    /// Gets how many percentage points the alarm adds to the [chance of a random encounter][crate::difficulty::Difficulty::get_encounter_chance_percent]
+    pub const fn get_extra_encounter_chance_percent(self) -> usize {
+        self.level * config::ALARM_ENCOUNTER_CHANCE_PERCENT
+    }
+}
@@ -0,0 +1,55 @@
+//! Contains the [`DynamicBalance`] type, which nudges how tough enemies are based on how the [`Player`][crate::player::Player] is doing.
+//! This is turned on in the [settings][crate::settings::Settings::dynamic_balance], and works on top of the fixed [difficulty][config::DIFFICULTY] rather than replacing it.
+
+mod tests;
+
+use crate::config;
+use crate::settings;
+
+/// Keeps track of how well the [`Player`][crate::player::Player] is doing, as a percentage applied to enemies' health when they are [scaled][crate::combat::Enemy::scaled].
+/// Dying lowers it by [`DYNAMIC_BALANCE_DEATH_STEP_PERCENT`][config::DYNAMIC_BALANCE_DEATH_STEP_PERCENT],
+/// and winning a fight while barely getting hurt raises it by [`DYNAMIC_BALANCE_STEAMROLL_STEP_PERCENT`][config::DYNAMIC_BALANCE_STEAMROLL_STEP_PERCENT].
+/// It never goes outside [`DYNAMIC_BALANCE_MIN_PERCENT`][config::DYNAMIC_BALANCE_MIN_PERCENT] and [`DYNAMIC_BALANCE_MAX_PERCENT`][config::DYNAMIC_BALANCE_MAX_PERCENT].
+///
+/// Like [`Achievements`][crate::achievements::Achievements], this is kept when the time loop resets. Changes only affect enemies scaled afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicBalance {
+    /// The percentage of their [scaled][crate::combat::Enemy::scaled] health enemies have
+    health_percent: usize,
+}
+
+impl Default for DynamicBalance {
+    fn default() -> Self {
+        Self { health_percent: 100 }
+    }
+}
+
+impl DynamicBalance {
+    /// Gets the percentage of their health enemies should have, which is always 100 if [dynamic balance][crate::settings::Settings::dynamic_balance] is turned off
+    pub fn get_health_percent(self) -> usize {
+        if settings::get().dynamic_balance {
+            self.health_percent
+        } else {
+            100
+        }
+    }
+
+    /// Makes enemies weaker after the [`Player`][crate::player::Player] dies, down to [`DYNAMIC_BALANCE_MIN_PERCENT`][config::DYNAMIC_BALANCE_MIN_PERCENT]
+    pub fn record_death(&mut self) {
+        self.health_percent = self
+            .health_percent
+            .saturating_sub(config::DYNAMIC_BALANCE_DEATH_STEP_PERCENT)
+            .max(config::DYNAMIC_BALANCE_MIN_PERCENT);
+    }
+
+    /// Makes enemies tougher if the [`Player`][crate::player::Player] won a fight while losing less than
+    /// [`DYNAMIC_BALANCE_STEAMROLL_HEALTH_PERCENT`][config::DYNAMIC_BALANCE_STEAMROLL_HEALTH_PERCENT] of their max health, up to [`DYNAMIC_BALANCE_MAX_PERCENT`][config::DYNAMIC_BALANCE_MAX_PERCENT].
+    /// The damage taken is the difference in their [run stats][crate::stats::RunStats::damage_taken] from before and after the fight.
+    pub fn record_win(&mut self, damage_taken: usize, max_health: usize) {
+        if damage_taken * 100 >= max_health * config::DYNAMIC_BALANCE_STEAMROLL_HEALTH_PERCENT {
+            return;
+        }
+
+        self.health_percent = (self.health_percent + config::DYNAMIC_BALANCE_STEAMROLL_STEP_PERCENT).min(config::DYNAMIC_BALANCE_MAX_PERCENT);
+    }
+}
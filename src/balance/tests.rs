@@ -0,0 +1,30 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that dying and steamrolling move the balance, and that it stays within its bounds however far it is pushed
+#[test]
+fn test_dynamic_balance() {
+    let mut balance = DynamicBalance::default();
+
+    // A fight that hurt doesn't count as steamrolling
+    balance.record_win(5, 10);
+    assert_eq!(balance, DynamicBalance::default());
+
+    balance.record_win(0, 10);
+    assert_eq!(balance.health_percent, 100 + config::DYNAMIC_BALANCE_STEAMROLL_STEP_PERCENT);
+
+    for _ in 0..100 {
+        balance.record_win(0, 10);
+    }
+    assert_eq!(balance.health_percent, config::DYNAMIC_BALANCE_MAX_PERCENT);
+
+    for _ in 0..100 {
+        balance.record_death();
+    }
+    assert_eq!(balance.health_percent, config::DYNAMIC_BALANCE_MIN_PERCENT);
+
+    if !settings::get().dynamic_balance {
+        assert_eq!(balance.get_health_percent(), 100);
+    }
+}
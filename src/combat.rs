@@ -1,20 +1,25 @@
 //! Implements combat-related functionality, such as enemies and health
 
 mod health;
+mod tests;
 
-use std::{
-    cmp::Ordering,
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
+use std::cmp::Ordering;
+use std::fmt::Write;
 
 use crate::{
-    items::Item,
-    menu::{Menu, Screen},
-    player::Player, config, 
+    achievements::Achievement,
+    balance::DynamicBalance,
+    config,
+    difficulty::Difficulty,
+    items::{list_items, AttackDirection, DamageType, Item, Rarity, Weapon},
+    lang,
+    menu::{Color, Menu, Screen},
+    player::Player,
+    rng::Rng,
+    tutorial::TutorialPrompt,
 };
 
-pub use health::{Damage, Health};
+pub use health::{Damage, Health, HealthDisplay};
 
 /// An enemy which can be battled
 #[derive(Debug, Hash)]
@@ -27,44 +32,513 @@ pub struct Enemy {
     /// The items the enemy can use in battle.
     /// Any items left over at the end of a battle will be given to the player.
     pub inventory: Vec<Item>,
+    /// Items the enemy drops on the floor of the room when they are defeated.
+    /// Unlike the [inventory][Enemy::inventory], these can't be used in battle.
+    pub drops: Vec<Item>,
     /// The enemy's current health
     pub health: Health,
     /// The maximum health the enemy can reach
     pub max_health: Health,
+    /// How much XP the player gets for defeating the enemy
+    pub xp_reward: usize,
+    /// How much extra damage the enemy deals with each attack, on top of their weapon's damage
+    pub bonus_damage: Damage,
+    /// How well the enemy aims, as a percentage of their weapon's [accuracy][Weapon::accuracy]
+    pub accuracy: usize,
+    /// How good the enemy is at getting out of the way, as a percentage taken off the [player's][Player] chance to hit them
+    pub evasion: usize,
+    /// The kinds of damage the enemy resists or is weak to. They take normal damage from any kind which isn't listed.
+    pub resistances: &'static [(DamageType, Resistance)],
+    /// How the enemy decides what to do each turn
+    pub attack_pattern: AttackPattern,
+    /// The phases a boss goes through as they are worn down, in order. Most enemies don't have any.
+    pub phases: &'static [BossPhase],
+    /// How many of the enemy's [phases][Enemy::phases] they have entered so far
+    pub phase: usize,
+    /// Whether the enemy knows the player is on board. Alert enemies may [pursue][Player::check_pursuit] the player into neighbouring rooms,
+    /// and fight them as soon as they're in the same room. The player can try to [sneak][Player::sneak] past enemies who aren't alert.
+    pub alert: bool,
+    /// How likely the enemy is to notice the player [sneaking][Player::sneak] past, as a percentage taken off the player's chance
+    pub awareness: usize,
+}
+
+/// How an [`Enemy`] is affected by a [`DamageType`] which they aren't hurt by normally
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Resistance {
+    /// The enemy takes half damage, rounded up
+    Resistant,
+    /// The enemy takes one and a half times the damage, rounded up
+    Weak,
+}
+
+/// A phase of a boss fight, which an [`Enemy`] enters once their health drops to a threshold.
+/// A [`Screen`] is shown when the phase begins, and then the boss changes how they fight.
+#[derive(Debug, Clone, Copy, Hash)]
+pub struct BossPhase {
+    /// The health, as a percentage of their max health, at or below which the [`Enemy`] enters this phase
+    pub health_percent: usize,
+    /// The title of the [`Screen`] shown when the phase begins
+    pub title: &'static str,
+    /// The text of the [`Screen`] shown when the phase begins
+    pub content: &'static str,
+    /// The [`AttackPattern`] the [`Enemy`] switches to, or [`None`] to keep their current one
+    pub attack_pattern: Option<AttackPattern>,
+    /// How much the [`Enemy`] heals by when the phase begins, as a percentage of their max health
+    pub heal_percent: usize,
+    /// How much [bonus damage][Enemy::bonus_damage] the [`Enemy`] gains for the rest of the battle
+    pub bonus_damage: Damage,
+}
+
+impl Resistance {
+    /// Gets the percentage of the normal damage the [`Enemy`] takes
+    pub const fn get_damage_percent(self) -> usize {
+        match self {
+            Self::Resistant => 50,
+            Self::Weak => 150,
+        }
+    }
+
+    /// Gets a hint to show the [player][Player] after they hit an [`Enemy`] with damage they resist or are weak to
+    pub const fn get_hint(self) -> &'static str {
+        match self {
+            Self::Resistant => "It's not very effective...",
+            Self::Weak => "It's very effective!",
+        }
+    }
+}
+
+/// How an [`Enemy`] decides what to do on each turn of a battle.
+/// Whatever the pattern, an [`Enemy`] at half health or less will always eat food if it has some,
+/// and an [`Enemy`] without a weapon will behave [randomly][AttackPattern::Random].
+#[derive(Debug, Clone, Copy, Hash)]
+#[allow(dead_code)] // Not every pattern is used by the current map
+pub enum AttackPattern {
+    /// Attacks half of the time, usually straight ahead, and otherwise dodges or does nothing.
+    Random,
+    /// Always attacks, more often straight ahead than to either side. Never dodges.
+    Aggressive,
+    /// Dodges half of the time, and otherwise either attacks straight ahead or does nothing.
+    Defensive,
+    /// Goes through the given steps in order, one per turn, starting again from the beginning once it reaches the end.
+    /// This is predictable, so a player who pays attention can learn it.
+    ///
+    /// ### Panics
+    /// * When the [`Enemy`] chooses an action, if the list of steps is empty
+    Cyclic(&'static [PatternStep]),
+}
+
+/// One step of an [`AttackPattern::Cyclic`] pattern or a [`Combo`]. Attacks use the [`Enemy`]'s first weapon.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PatternStep {
+    /// Attack to the left
+    AttackLeft,
+    /// Attack straight ahead
+    AttackStraight,
+    /// Attack to the right
+    AttackRight,
+    /// Dodge to the left
+    DodgeLeft,
+    /// Dodge to the right
+    DodgeRight,
+    /// Do nothing
+    Nothing,
+}
+
+impl PatternStep {
+    /// Gets the step the [`Player`] took with an [`Action`], or [`None`] if the action isn't one a [`Combo`] can be made from
+    pub const fn from_action(action: Action) -> Option<Self> {
+        match action {
+            Action::AttackLeft(_) => Some(Self::AttackLeft),
+            Action::AttackStraight(_) => Some(Self::AttackStraight),
+            Action::AttackRight(_) => Some(Self::AttackRight),
+            Action::DodgeLeft => Some(Self::DodgeLeft),
+            Action::DodgeRight => Some(Self::DodgeRight),
+            Action::Nothing => Some(Self::Nothing),
+            _ => None,
+        }
+    }
+
+    /// Gets a short description of the step, to show in the combat help
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::AttackLeft => "attack left",
+            Self::AttackStraight => "attack straight",
+            Self::AttackRight => "attack right",
+            Self::DodgeLeft => "dodge left",
+            Self::DodgeRight => "dodge right",
+            Self::Nothing => "do nothing",
+        }
+    }
+}
+
+/// A sequence of [steps][PatternStep] which the [`Player`] can take on consecutive turns of a battle to pull off a finisher.
+/// The last step should be an attack, and the finisher only lands if that attack hits. The combos are listed in [`config::COMBOS`].
+#[derive(Debug, Clone, Copy, Hash)]
+pub struct Combo {
+    /// The name of the combo, shown when it lands
+    pub name: &'static str,
+    /// The steps to take, in order, one per turn
+    pub steps: &'static [PatternStep],
+    /// The extra damage the finisher deals, of the same [`DamageType`] as the final attack
+    pub bonus_damage: Damage,
+}
+
+impl Combo {
+    /// Gets a description of the steps of the combo, to show in the combat help
+    pub fn describe_steps(&self) -> String {
+        self.steps.iter().map(|step| step.describe()).collect::<Vec<_>>().join(", then ")
+    }
+}
+
+/// Keeps track of the [`Player`]'s recent [steps][PatternStep] in a battle, to spot when they complete a [`Combo`]
+#[derive(Debug, Default, Clone)]
+pub struct ComboTracker {
+    /// The steps the player has taken which could still become a combo, oldest first
+    steps: Vec<PatternStep>,
+}
+
+impl ComboTracker {
+    /// Records the [`Action`] the [`Player`] took this turn, and returns the [`Combo`] it completes, if there is one.
+    /// Any action which breaks the sequence resets the tracker, although it may start a new combo itself.
+    /// The tracker is also reset once a combo is completed.
+    pub fn record(&mut self, combos: &'static [Combo], action: Action) -> Option<&'static Combo> {
+        let Some(step) = PatternStep::from_action(action) else {
+            self.steps.clear();
+            return None;
+        };
+        self.steps.push(step);
+
+        // Forget the oldest steps until what's left is the start of a combo
+        while !self.steps.is_empty() && !combos.iter().any(|combo| combo.steps.starts_with(&self.steps)) {
+            self.steps.remove(0);
+        }
+
+        let combo = combos.iter().find(|combo| combo.steps == self.steps.as_slice())?;
+        self.steps.clear();
+        Some(combo)
+    }
+}
+
+/// Lands the finisher of a [`Combo`] which the [player][Player] has just completed with the given [`Action`], dealing its bonus damage to the [`Enemy`].
+/// The final attack has to have hit this turn for the finisher to land.
+///
+/// ### Returns:
+/// A short description of the finisher, or [`None`] if it didn't land
+fn land_combo(player: &Player, enemy: &mut Enemy, combo: &Combo, player_action: Action, hit: bool) -> Option<String> {
+    let (Action::AttackLeft(p) | Action::AttackStraight(p) | Action::AttackRight(p)) = player_action else {
+        return None;
+    };
+    if !hit || enemy.health.is_dead() {
+        return None;
+    }
+    let Item::Weapon(weapon) = &player.inventory[p].0 else {unreachable!()};
+
+    let damage = enemy.take_hit(combo.bonus_damage, weapon.damage_type);
+
+    Some(format!(
+        "{}! You follow through with your {} for another {} damage.{}",
+        combo.name, weapon.name, damage, enemy.describe_effectiveness(weapon.damage_type)
+    ))
 }
 
 impl Enemy {
+    /// Scales a base [`Enemy`] from [`map`][crate::map] to suit the [`Difficulty`] and the [`Player`]'s level:
+    /// * Health is the base health times the [difficulty's percentage][Difficulty::get_enemy_health_percent] (but at least 1),
+    ///   plus [`ENEMY_HEALTH_PER_PLAYER_LEVEL`][config::ENEMY_HEALTH_PER_PLAYER_LEVEL] for each level the player has gained.
+    /// * Bonus damage is the [difficulty's bonus][Difficulty::get_enemy_bonus_damage],
+    ///   plus 1 for every [`PLAYER_LEVELS_PER_ENEMY_BONUS_DAMAGE`][config::PLAYER_LEVELS_PER_ENEMY_BONUS_DAMAGE] levels the player has gained.
+    /// * The XP reward is the base reward times the [difficulty's percentage][Difficulty::get_enemy_xp_percent],
+    ///   plus [`ENEMY_XP_PER_PLAYER_LEVEL`][config::ENEMY_XP_PER_PLAYER_LEVEL] for each level the player has gained.
+    ///
+    /// * All of that health is then multiplied by the [dynamic balance's percentage][DynamicBalance::get_health_percent] (but is still at least 1).
+    ///
+    /// On [`Normal`][Difficulty::Normal] difficulty at level 1 with an unchanged [`DynamicBalance`], the [`Enemy`] is left as it is.
+    pub fn scaled(base: Self, difficulty: Difficulty, player_level: usize, balance: DynamicBalance) -> Self {
+        let levels_gained = player_level.saturating_sub(1);
+
+        let health = (base.max_health.as_usize() * difficulty.get_enemy_health_percent() / 100).max(1)
+            + levels_gained * config::ENEMY_HEALTH_PER_PLAYER_LEVEL;
+        let health = Health::new((health * balance.get_health_percent() / 100).max(1));
+        let bonus_damage = Damage::new(
+            base.bonus_damage.as_usize()
+                + difficulty.get_enemy_bonus_damage()
+                + levels_gained / config::PLAYER_LEVELS_PER_ENEMY_BONUS_DAMAGE,
+        );
+        let xp_reward = base.xp_reward * difficulty.get_enemy_xp_percent() / 100 + levels_gained * config::ENEMY_XP_PER_PLAYER_LEVEL;
+
+        Self {
+            health,
+            max_health: health,
+            bonus_damage,
+            xp_reward,
+            ..base
+        }
+    }
+
+    /// Gets how the [`Enemy`] is affected by the given [`DamageType`], or [`None`] if they take normal damage from it
+    pub fn get_resistance(&self, damage_type: DamageType) -> Option<Resistance> {
+        self.resistances.iter().find(|(t, _)| *t == damage_type).map(|(_, resistance)| *resistance)
+    }
+
+    /// Scales damage of the given [`DamageType`] by the [`Enemy`]'s [resistance][Resistance::get_damage_percent] to it, rounding up
+    pub fn get_resisted_damage(&self, damage: Damage, damage_type: DamageType) -> Damage {
+        match self.get_resistance(damage_type) {
+            Some(resistance) => Damage::new((damage.as_usize() * resistance.get_damage_percent()).div_ceil(100)),
+            None => damage,
+        }
+    }
+
+    /// Deals damage of the given [`DamageType`] to the [`Enemy`], scaled by their [resistance][Resistance::get_damage_percent] to it.
+    /// Returns how much damage was actually dealt.
+    pub fn take_hit(&mut self, damage: Damage, damage_type: DamageType) -> Damage {
+        self.health.damage(self.get_resisted_damage(damage, damage_type))
+    }
+
+    /// Gets a [hint][Resistance::get_hint] about how well damage of the given [`DamageType`] works on the [`Enemy`], starting with a space,
+    /// or an empty string if they take normal damage from it
+    pub fn describe_effectiveness(&self, damage_type: DamageType) -> String {
+        self.get_resistance(damage_type).map_or_else(String::new, |resistance| format!(" {}", resistance.get_hint()))
+    }
+
+    /// Moves the [`Enemy`] into any [phases][Enemy::phases] whose health threshold they have dropped to, in order,
+    /// showing a [`Screen`] for each one. Dead enemies don't change phase.
+    pub fn advance_phase(&mut self, menu: &mut impl Menu) {
+        while let Some(phase) = self.phases.get(self.phase) {
+            if self.health.is_dead() || self.health.as_usize() * 100 > self.max_health.as_usize() * phase.health_percent {
+                return;
+            }
+            self.phase += 1;
+
+            let healed = self.health.heal_to_max(Damage::new(self.max_health.as_usize() * phase.heal_percent / 100), self.max_health);
+            if let Some(attack_pattern) = phase.attack_pattern {
+                self.attack_pattern = attack_pattern;
+            }
+            self.bonus_damage = self.bonus_damage + phase.bonus_damage;
+
+            let mut content = phase.content.to_string();
+            // Writing to a String can't fail
+            if healed.as_usize() > 0 {
+                let _ = write!(content, "\n\nThe {} heals by {healed} HP.", self.name);
+            }
+            let _ = write!(content, "\nThe {} is now at {}", self.name, self.describe_health());
+
+            menu.show_screen(Screen {
+                title: phase.title,
+                content: &content,
+                title_color: Some(Color::Red),
+                art: None,
+            });
+        }
+    }
+
+    /// Shows a [`Screen`] with everything the [`Player`] can tell about the [`Enemy`] by looking at them:
+    /// their health, the [`DamageType`]s they resist or are weak to, and which of their [phases][Enemy::phases] they are in
+    pub fn examine(&self, menu: &mut impl Menu) {
+        let mut content = format!("The {} is {}\nThey are at {}", self.name, self.description, self.describe_health());
+
+        let describe_resistances = |resistance: Resistance| {
+            self.resistances.iter().filter(|(_, r)| *r == resistance).map(|(t, _)| t.get_name()).collect::<Vec<_>>().join(", ")
+        };
+        let resistant = describe_resistances(Resistance::Resistant);
+        let weak = describe_resistances(Resistance::Weak);
+
+        // Writing to a String can't fail
+        if !resistant.is_empty() {
+            let _ = write!(content, "\nThey resist {resistant} damage.");
+        }
+        if !weak.is_empty() {
+            let _ = write!(content, "\nThey are weak to {weak} damage.");
+        }
+        if resistant.is_empty() && weak.is_empty() {
+            content += "\nThey take normal damage from everything.";
+        }
+
+        if let Some(phase) = self.phase.checked_sub(1).and_then(|i| self.phases.get(i)) {
+            let _ = write!(content, "\n\n{}", phase.title);
+        }
+        if self.bonus_damage.as_usize() > 0 {
+            let _ = write!(content, "\nTheir attacks deal {} extra damage.", self.bonus_damage);
+        }
+
+        menu.show_screen(Screen {
+            title: &format!("You size up the {}", self.name),
+            content: &content,
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Gets short labels for everything currently affecting the [`Enemy`] in battle, for the [status line][describe_statuses]
+    fn get_status_effects(&self) -> Vec<String> {
+        let mut effects = Vec::new();
+        if self.phase > 0 {
+            effects.push(format!("phase {}/{}", self.phase, self.phases.len()));
+        }
+        effects
+    }
+
+    /// Gets the damage the [`Enemy`] deals with an attack, given the base damage of the attack
+    pub fn get_attack_damage(&self, base_damage: Damage) -> Damage {
+        base_damage + self.bonus_damage
+    }
+
+    /// Makes the [`Enemy`] tougher for the given [New Game Plus][crate::new_game_plus::NewGamePlus] cycle.
+    /// Each cycle adds [`NEW_GAME_PLUS_ENEMY_HEALTH`][config::NEW_GAME_PLUS_ENEMY_HEALTH] health and [`NEW_GAME_PLUS_ENEMY_XP`][config::NEW_GAME_PLUS_ENEMY_XP] XP.
+    pub fn scale_for_cycle(&mut self, cycle: usize) {
+        for _ in 0..cycle {
+            self.health += config::NEW_GAME_PLUS_ENEMY_HEALTH;
+            self.max_health += config::NEW_GAME_PLUS_ENEMY_HEALTH;
+            self.xp_reward += config::NEW_GAME_PLUS_ENEMY_XP;
+        }
+    }
+
+    /// Gets a string describing the [`Enemy`]'s health, both as a number and as a [bar][Health::get_bar]
+    fn describe_health(&self) -> String {
+        format!(
+            "{} {}",
+            self.health.display_against(self.max_health),
+            self.health.get_bar(self.max_health, config::HEALTH_BAR_WIDTH)
+        )
+    }
+
+    /// Gets a string describing the enemy attacking in the given direction with the weapon at the given index into their [inventory][Enemy::inventory],
+    /// in the weapon's own [style][Weapon::attack_style]
+    fn describe_attack(&self, w: usize, direction: AttackDirection) -> String {
+        let Item::Weapon(weapon) = &self.inventory[w] else {unreachable!()};
+        lang::fill(weapon.get_attack_key(direction, false), &[&self.name, &weapon.name])
+    }
+
     /// Gets a string describing the enemy carrying out a provided action
     pub fn describe_combat_action(&self, action: Action) -> String {
         use Action::*;
 
         match action {
-            AttackLeft(w) => format!(
-                "The {} attacks to the left with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            AttackRight(w) => format!(
-                "The {} attacks to the right with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            AttackStraight(w) => format!(
-                "The {} attacks in front of them with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            EatFood(f) => format!(
-                "The {} attempts to eat their {}",
-                self.name,
-                self.inventory[f].get_name()
-            ),
-
-            DodgeLeft => format!("The {} dodges to the left", self.name),
-            DodgeRight => format!("The {} dodges to the right", self.name),
-            Nothing => format!("The {} does nothing", self.name),
+            AttackLeft(w) => self.describe_attack(w, AttackDirection::Left),
+            AttackRight(w) => self.describe_attack(w, AttackDirection::Right),
+            AttackStraight(w) => self.describe_attack(w, AttackDirection::Straight),
+            EatFood(f) => lang::fill("combat.enemy.eat", &[&self.name, &self.inventory[f].get_name()]),
+            Throw(w) => lang::fill("combat.enemy.throw", &[&self.name, &self.inventory[w].get_name()]),
+
+            DodgeLeft => lang::fill("combat.enemy.dodge_left", &[&self.name]),
+            DodgeRight => lang::fill("combat.enemy.dodge_right", &[&self.name]),
+            Block => lang::fill("combat.enemy.block", &[&self.name]),
+            Nothing => lang::fill("combat.enemy.nothing", &[&self.name]),
+            // Only the player can flee or examine their opponent
+            Flee | UseBandage(_) | Explode(_) | Examine => unreachable!(),
+        }
+    }
+}
+
+/// Gets the damage dealt by an attack, from the base damage of the weapon used and the strength of the attacker.
+/// Every [`STRENGTH_PER_BONUS_DAMAGE`][config::STRENGTH_PER_BONUS_DAMAGE] points of strength add 1 damage, rounding down.
+pub const fn attack_damage(base_damage: Damage, strength: usize) -> Damage {
+    Damage::new(base_damage.as_usize() + strength / config::STRENGTH_PER_BONUS_DAMAGE)
+}
+
+/// How the [`Action`]s the player and enemy choose are resolved against each other each turn. This is set with [`config::COMBAT_RESOLUTION`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)] // Only one mode is configured at a time
+pub enum Resolution {
+    /// When both combatants attack, the one with the faster weapon lands their hit first and gets away unscathed.
+    /// Dodging away from an attack may leave an opening for a [counter-attack][counter_attack].
+    Speed,
+    /// Both actions land at once, so attacking while being attacked always trades blows,
+    /// and dodging means giving up on dealing any damage that turn.
+    Simultaneous,
+}
+
+impl Resolution {
+    /// Gets which of two clashing attacks lands, from the [speeds][Weapon::speed] of the player's and enemy's weapons.
+    /// [`Ordering::Less`] means only the player's lands, [`Ordering::Greater`] means only the enemy's lands, and [`Ordering::Equal`] means both do.
+    const fn clash(self, player_speed: usize, enemy_speed: usize) -> Ordering {
+        match self {
+            Self::Speed if player_speed < enemy_speed => Ordering::Less,
+            Self::Speed if player_speed > enemy_speed => Ordering::Greater,
+            Self::Speed | Self::Simultaneous => Ordering::Equal,
         }
     }
+
+    /// Gets whether dodging away from an attack can give the player a [counter-attack][counter_attack]
+    const fn allows_counters(self) -> bool {
+        matches!(self, Self::Speed)
+    }
+
+    /// Gets a description of what happens when both combatants attack, and of dodging, for the combat help screen
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::Speed => "If you both attack, the faster weapon hits first and the slower one misses. Dodge the right way and you might get a counter-attack in.",
+            Self::Simultaneous => "If you both attack, you both get hit, however fast your weapons are. Dodging keeps you safe, but you won't get to hit back.",
+        }
+    }
+}
+
+/// How dangerous a fight with an [`Enemy`] looks, compared to how strong the [`Player`] is right now. See [`rate_threat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Threat {
+    /// The player should win without breaking a sweat
+    Trivial,
+    /// The player should win comfortably
+    Easy,
+    /// Either of them could win
+    Even,
+    /// The enemy will probably win
+    Hard,
+    /// The player doesn't stand a chance
+    Deadly,
+}
+
+impl Threat {
+    /// Gets the name of the threat level, to show the player
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Trivial => "Trivial",
+            Self::Easy => "Easy",
+            Self::Even => "Even",
+            Self::Hard => "Hard",
+            Self::Deadly => "Deadly",
+        }
+    }
+}
+
+/// Gets how many hits of the given damage it takes to bring the given health to 0, or [`None`] if the hits don't do any damage
+fn hits_to_defeat(health: Health, damage_per_hit: Damage) -> Option<usize> {
+    (damage_per_hit.as_usize() > 0).then(|| health.as_usize().div_ceil(damage_per_hit.as_usize()))
+}
+
+/// Rates how dangerous a fight with the [`Enemy`] would be for the [`Player`] as they are now, so they can decide whether to fight, sneak or run.
+///
+/// The rating compares how many straight hits each of them needs to defeat the other:
+/// * The player hits with their [main weapon][Player::get_main_weapon], boosted by their strength (which grows as they level up) and scaled by the enemy's resistance to it.
+///   Without a weapon they can't do any damage.
+/// * The enemy hits with their first weapon plus their [bonus damage][Enemy::bonus_damage], less the [defense][Player::get_defense] of the player's equipped armor.
+///
+/// The more hits the player can take for each one they need to land, the lower the threat.
+/// If the enemy can't hurt the player, the fight is [trivial][Threat::Trivial], and if the player can't hurt the enemy, it's [deadly][Threat::Deadly].
+pub fn rate_threat(player: &Player, enemy: &Enemy) -> Threat {
+    let player_damage = player
+        .get_main_weapon()
+        .map_or(Damage::new(0), |w| enemy.get_resisted_damage(player.get_attack_damage(w.straight_damage), w.damage_type));
+    let enemy_damage = enemy.inventory.iter().find_map(|item| match item {
+        Item::Weapon(w) => Some(enemy.get_attack_damage(w.straight_damage)),
+        _ => None,
+    });
+    let enemy_damage = Damage::new(enemy_damage.map_or(0, |d| d.as_usize().saturating_sub(player.get_defense())));
+
+    let hits_to_win = hits_to_defeat(enemy.health, player_damage);
+    let hits_to_lose = hits_to_defeat(player.health, enemy_damage);
+
+    match (hits_to_win, hits_to_lose) {
+        (None, None) => Threat::Even,
+        (_, None) => Threat::Trivial,
+        (None, _) => Threat::Deadly,
+        (Some(win), Some(lose)) => match lose * 100 / win {
+            300.. => Threat::Trivial,
+            150.. => Threat::Easy,
+            80.. => Threat::Even,
+            40.. => Threat::Hard,
+            _ => Threat::Deadly,
+        },
+    }
 }
 
 /// The result of a battle.
@@ -78,16 +552,26 @@ pub enum BattleResult {
     PlayerLoss,
     /// The player ran out of turns
     MaxTurnsReached,
+    /// The ship self-destructed before the player could escape. See [`config::SELF_DESTRUCT_MODE`].
+    SelfDestructed,
+    /// The player [fled][Action::Flee] into another room, leaving the enemy behind
+    Fled,
 }
 
 /// An action which either a player or an enemy can take during a battle
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     /// The combatant does nothing
     Nothing,
     /// The combatant attempts to eat the food item at the given index in their inventory.
     /// This may not happen if they are attacked on the same turn.
     EatFood(usize),
+    /// The combatant puts on the [bandage][Item::Bandage] at the given index in their inventory.
+    /// It takes their turn, but unlike [eating][Action::EatFood] it always works, healing them over the next few turns. Only the player uses bandages.
+    UseBandage(usize),
+    /// The combatant throws the [explosive][Item::Explosive] at the given index in their inventory, which hits the enemy whatever they do.
+    /// Only the player uses explosives - see [`explode`].
+    Explode(usize),
     /// The combatant attacks straight with the weapon at the given index in their inventory.
     /// This attack will connect unless the opponent dodges or attacks with a faster weapon.
     AttackStraight(usize),
@@ -97,26 +581,53 @@ pub enum Action {
     /// The combatant attacks to the right with the weapon at the given index in their inventory.
     /// This attack will only connect if the opponent chooses to [dodge left][Action::DodgeRight].
     AttackRight(usize),
+    /// The combatant throws the weapon at the given index in their inventory, dealing its [`throw_damage`][crate::items::Weapon::throw_damage].
+    /// Like a [straight attack][Action::AttackStraight], this will connect unless the opponent dodges or attacks with a faster weapon.
+    /// The weapon lands in the room, so it can't be used again until after the battle.
+    Throw(usize),
     /// The combatant dodges to the left.
     /// This means they will not be hit by [straight attacks][Action::AttackStraight], but they will be hit by [attacks to the left][Action::AttackLeft]
     DodgeLeft,
     /// The combatant dodges to the right.
     /// This means they will not be hit by [straight attacks][Action::AttackStraight], but they will be hit by [attacks to the left][Action::AttackRight]
     DodgeRight,
+    /// The combatant raises their guard.
+    /// They will still be hit by [straight attacks][Action::AttackStraight], but take [`BLOCK_DAMAGE_PERCENT`][config::BLOCK_DAMAGE_PERCENT] of the damage.
+    Block,
+    /// The combatant runs through an open door into a neighbouring room, ending the battle.
+    /// The enemy is left behind, but may [pursue][Player::check_pursuit] them. Only the player can flee.
+    Flee,
+    /// The combatant [examines][Enemy::examine] their opponent. Unless [`EXAMINE_TAKES_TURN`][config::EXAMINE_TAKES_TURN] is set,
+    /// this doesn't use up their turn. Only the player examines their opponent.
+    Examine,
 }
 
-impl Enemy {
-    /// Gets a hash of the [`Enemy`]'s state including the provided turn number.
-    /// This is useful to implement random-seeming while deterministic enemy AI.
-    fn hash_with_turn(&self, turn_number: usize) -> u64 {
-        let mut s = DefaultHasher::new();
-        self.hash(&mut s);
-        (config::MAX_TURNS - turn_number + 1).hash(&mut s);
-        s.finish()
+impl Action {
+    /// Gets how much stamina the [`Player`] needs to take this action. Defending costs stamina, so that it can't be done every turn.
+    pub const fn get_stamina_cost(self) -> usize {
+        match self {
+            Self::DodgeLeft | Self::DodgeRight => config::DODGE_STAMINA_COST,
+            Self::Block => config::BLOCK_STAMINA_COST,
+            Self::Flee => config::FLEE_STAMINA_COST,
+            _ => 0,
+        }
     }
 
-    /// Determine what action the [`Enemy`] will take this turn
-    fn choose_combat_action(&mut self, turn_number: usize) -> Action {
+    /// Checks whether this is a dodge to either side, which the [`Player`] can't do again until their [cooldown][Player::dodge_cooldown] is over
+    pub const fn is_dodge(self) -> bool {
+        matches!(self, Self::DodgeLeft | Self::DodgeRight)
+    }
+}
+
+/// Gets the damage which gets through a [block][Action::Block], rounding down
+pub const fn blocked_damage(damage: Damage) -> Damage {
+    Damage::new(damage.as_usize() * config::BLOCK_DAMAGE_PERCENT / 100)
+}
+
+impl Enemy {
+    /// Determine what action the [`Enemy`] will take this turn, based on its [`AttackPattern`].
+    /// `turn` is the number of turns which have passed in this battle, starting from 0.
+    fn choose_combat_action(&self, rng: &mut Rng, turn: usize) -> Action {
         // If enemy is at less than half health and has food, then eat it
         if self.health.as_usize() * 2 <= self.max_health.as_usize() {
             if let Some(food_index) = self
@@ -133,12 +644,20 @@ impl Enemy {
             .inventory
             .iter()
             .position(|i| matches!(i, Item::Weapon(_)));
-        // Get a hash of self using the turn number
-        let hash = self.hash_with_turn(turn_number);
 
-        // Pseudorandomly pick an action
-        match weapon_index {
-            Some(weapon_index) => match hash % 8 {
+        // Without a weapon, all the enemy can do is dodge or wait
+        let Some(weapon_index) = weapon_index else {
+            return match rng.below(7) {
+                0..=1 => Action::DodgeLeft,
+                2..=4 => Action::Nothing,
+                5..=6 => Action::DodgeRight,
+                _ => unreachable!(),
+            };
+        };
+
+        // Pseudorandomly pick an action according to the pattern
+        match self.attack_pattern {
+            AttackPattern::Random => match rng.below(8) {
                 0 => Action::AttackLeft(weapon_index),
                 1..=3 => Action::AttackStraight(weapon_index),
                 4 => Action::AttackRight(weapon_index),
@@ -147,12 +666,27 @@ impl Enemy {
                 7 => Action::Nothing,
                 _ => unreachable!(),
             },
-            None => match hash % 7 {
-                0..=1 => Action::DodgeLeft,
-                2..=4 => Action::Nothing,
-                5..=6 => Action::DodgeRight,
+            AttackPattern::Aggressive => match rng.below(4) {
+                0 => Action::AttackLeft(weapon_index),
+                1..=2 => Action::AttackStraight(weapon_index),
+                3 => Action::AttackRight(weapon_index),
                 _ => unreachable!(),
             },
+            AttackPattern::Defensive => match rng.below(4) {
+                0 => Action::DodgeLeft,
+                1 => Action::DodgeRight,
+                2 => Action::AttackStraight(weapon_index),
+                3 => Action::Nothing,
+                _ => unreachable!(),
+            },
+            AttackPattern::Cyclic(steps) => match steps[turn % steps.len()] {
+                PatternStep::AttackLeft => Action::AttackLeft(weapon_index),
+                PatternStep::AttackStraight => Action::AttackStraight(weapon_index),
+                PatternStep::AttackRight => Action::AttackRight(weapon_index),
+                PatternStep::DodgeLeft => Action::DodgeLeft,
+                PatternStep::DodgeRight => Action::DodgeRight,
+                PatternStep::Nothing => Action::Nothing,
+            },
         }
     }
 }
@@ -166,6 +700,7 @@ impl Enemy {
 ///
 /// ### Returns:
 /// A [`BattleResult`] representing the outcome of the battle. If this is a [player loss][BattleResult::PlayerLoss], the player lost the battle and the loop should reset.
+#[allow(clippy::too_many_lines)] // Each step of a turn is kept in order here, so that the whole turn can be followed
 pub fn battle(
     player: &mut Player,
     mut enemy: Enemy,
@@ -174,54 +709,445 @@ pub fn battle(
     let screen = Screen {
         title: &format!("You are spotted by the {}", enemy.name),
         content: &format!(
-            "The {} sees you and blocks your path. They are {}",
-            enemy.name, enemy.description
+            "The {} sees you and blocks your path. They are {}\nThey are at {}\nThe fight looks {}.",
+            enemy.name, enemy.description, enemy.describe_health(), rate_threat(player, &enemy).get_name().to_lowercase()
         ),
+        title_color: Some(Color::Red),
+        art: None,
     };
 
     menu.show_screen(screen);
+    player.tutorial.show(menu, TutorialPrompt::Combat);
+
+    // The player can't retreat from a fight by going back the way they came
+    player.previous_room = None;
+
+    // Every fight starts with the player able to dodge
+    player.dodge_cooldown = 0;
+
+    // Once they've seen the player, the enemy won't forget about them
+    enemy.alert = true;
+
+    // Whether the player has lost any health, for the flawless victory achievement
+    let mut took_damage = false;
+    // How much damage the player had taken before the fight, to tell the dynamic balance how hard it was
+    let damage_taken_before = player.stats.damage_taken;
+
+    // The number of turns which have passed, for enemies with a cyclic attack pattern
+    let mut turn = 0;
+
+    // The player's recent actions, to spot when they pull off a combo
+    let mut combo_tracker = ComboTracker::default();
 
     // Loop until either the player or the enemy reaches 0 health or the player runs out of turns
     loop {
         // Get the player and enemy's actions
         let player_action = player.choose_combat_action(menu);
-        let enemy_action = enemy.choose_combat_action(player.remaining_turns);
 
-        // Carry out the actions
+        // Examining the enemy either leaves the player to choose again, or uses up their turn as if they did nothing
+        let player_action = if let Action::Examine = player_action {
+            enemy.examine(menu);
+            if !config::EXAMINE_TAKES_TURN {
+                continue;
+            }
+            Action::Nothing
+        } else {
+            player_action
+        };
+
+        let enemy_action = enemy.choose_combat_action(&mut player.rng, turn);
+
+        player.stamina -= player_action.get_stamina_cost();
+
+        if let Action::Flee = player_action {
+            let name = enemy.name;
+            flee(player, enemy, menu);
+            raise_alarm_if_loud(player, menu, name, turn);
+            return BattleResult::Fled;
+        }
+
+        let prev_health = player.health;
+
+        // An explosive goes off before anything else, and an enemy caught in it attacking up close gets the player too
+        let enemy_action = if let Action::Explode(i) = player_action {
+            let close_range = matches!(enemy_action, Action::AttackLeft(_) | Action::AttackStraight(_) | Action::AttackRight(_));
+            let prev_enemy_health = enemy.health;
+            explode(player, &mut enemy, i, close_range, menu);
+            player.stats.record_damage_dealt(prev_enemy_health, enemy.health);
+
+            // Enemies killed by the blast can't fight back
+            if enemy.health.is_dead() { Action::Nothing } else { enemy_action }
+        } else {
+            enemy_action
+        };
+
+        // Attacks which miss are carried out as doing nothing, but the player's weapon is still worn down by the attempt
+        let attempted_action = player_action;
+        let (player_action, enemy_action) = roll_to_hit(player, &enemy, player_action, enemy_action, menu);
+
+        // Carry out the actions. The enemy can be hurt or heal in a turn, but never both, so their change in health is the damage dealt.
+        let prev_enemy_health = enemy.health;
         let turn_text = execute_actions(player, &mut enemy, player_action, enemy_action);
+        player.stats.record_damage_dealt(prev_enemy_health, enemy.health);
+        let hit_enemy = enemy.health < prev_enemy_health;
+
+        // A weapon with a longer reach can still catch a dodge, in which case there's no opening to counter-attack
+        let caught_text = catch_dodge_with_reach(player, &enemy, player_action, enemy_action);
+        let turn_text = match &caught_text {
+            Some(caught_text) => format!("{turn_text}\n{caught_text}"),
+            None => turn_text,
+        };
+
+        // Companions join in once the player's attack has landed
+        let turn_text = match companions_turn(player, &mut enemy, enemy_action) {
+            Some(companion_text) => format!("{turn_text}\n{companion_text}"),
+            None => turn_text,
+        };
+
+        player.regen_stamina();
+        player.tick_dodge_cooldown(player_action);
+
+        let turn_text = match player.tick_bandage() {
+            Some(healed) => format!("{turn_text}\nYour bandage heals you by {healed} HP."),
+            None => turn_text,
+        };
 
         // Show the result of the turn
         let turn_text = format!(
-            "{turn_text}\nYou are now at {}/{} HP.\nThe {} is now at {}/{} HP",
-            player.health, player.max_health, enemy.name, enemy.health, enemy.max_health,
+            "{turn_text}\nYou are now at {} and {}/{} stamina.\nThe {} is now at {}",
+            player.health.display_against(player.max_health), player.stamina, player.max_stamina, enemy.name, enemy.describe_health(),
         );
+        let turn_text = match describe_statuses(player, &enemy) {
+            Some(statuses) => format!("{turn_text}\n{statuses}"),
+            None => turn_text,
+        };
+
+        // Show whether the player was hurt or healed this turn
+        let title_color = match player.health.cmp(&prev_health) {
+            Ordering::Less => {
+                took_damage = true;
+                Some(Color::Red)
+            }
+            Ordering::Greater => Some(Color::Green),
+            Ordering::Equal => None,
+        };
 
         let screen = Screen {
             title: "Turn Result",
             content: &turn_text,
+            title_color,
+            art: None,
         };
 
         menu.show_screen(screen);
 
-        if player.health.is_0() {
+        let prev_enemy_health = enemy.health;
+        let counter_text = if caught_text.is_none() {
+            counter_attack(player, &mut enemy, player_action, enemy_action)
+        } else {
+            None
+        };
+        player.stats.record_damage_dealt(prev_enemy_health, enemy.health);
+
+        // The finisher is dealt before the weapon is worn down, in case the final attack breaks it
+        let prev_enemy_health = enemy.health;
+        let combo_text = combo_tracker
+            .record(config::COMBOS, player_action)
+            .and_then(|combo| land_combo(player, &mut enemy, combo, player_action, hit_enemy));
+        player.stats.record_damage_dealt(prev_enemy_health, enemy.health);
+
+        if let Some(combo_text) = combo_text {
+            let screen = Screen {
+                title: "Combo!",
+                content: &format!(
+                    "{combo_text}\nThe {} is now at {}",
+                    enemy.name, enemy.describe_health()
+                ),
+                title_color: Some(Color::Green),
+                art: None,
+            };
+            menu.show_screen(screen);
+        }
+
+        wear_weapon(player, attempted_action, menu);
+
+        if let Some(counter_text) = counter_text {
+            let screen = Screen {
+                title: "Counter-attack!",
+                content: &format!(
+                    "{counter_text}\nThe {} is now at {}",
+                    enemy.name, enemy.describe_health()
+                ),
+                title_color: Some(Color::Green),
+                art: None,
+            };
+            menu.show_screen(screen);
+        }
+
+        if player.health.is_dead() {
             return BattleResult::PlayerLoss;
         }
-        if enemy.health.is_0() {
+
+        // A boss who has been worn down far enough changes how they fight
+        enemy.advance_phase(menu);
+
+        if enemy.health.is_dead() {
+            if !took_damage {
+                player.achievements.unlock(menu, Achievement::FlawlessVictory);
+            }
+            player.balance.record_win(player.stats.damage_taken - damage_taken_before, player.max_health.as_usize());
+            let name = enemy.name;
             win_battle(player, enemy, menu);
+            raise_alarm_if_loud(player, menu, name, turn);
             return BattleResult::PlayerWin;
         }
 
         player.remaining_turns -= 1;
+        player.stats.turns_taken += 1;
+        player.tick_self_destruct();
+
+        if player.has_self_destructed() {
+            return BattleResult::SelfDestructed
+        }
 
         if player.remaining_turns == 0 {
             return BattleResult::MaxTurnsReached
         }
+
+        turn += 1;
+    }
+}
+
+/// Gives each of the [player's][Player] [companions][Player::companions] who can fight a turn against the [`Enemy`].
+/// Whether they join in is decided by the [`COMPANION_AI`][config::COMPANION_AI]. Those who do attack the enemy, and if the enemy attacked this turn,
+/// they have a [chance][config::COMPANION_TARGETED_CHANCE_PERCENT] of being caught by it too. Companions who are killed are [lost][Player::fallen_companions].
+///
+/// ### Returns:
+/// The text describing what the companions did, or [`None`] if the player has no companions who fight
+pub fn companions_turn(player: &mut Player, enemy: &mut Enemy, enemy_action: Action) -> Option<String> {
+    let enemy_damage = match enemy_action {
+        Action::AttackLeft(e) | Action::AttackStraight(e) | Action::AttackRight(e) => match &enemy.inventory[e] {
+            Item::Weapon(weapon) => Some(enemy.get_attack_damage(weapon.straight_damage)),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let mut text = String::new();
+    let mut i = 0;
+    while i < player.companions.len() {
+        let name = player.companions[i].name;
+        let Some(mut fighter) = player.companions[i].fighter else {
+            i += 1;
+            continue;
+        };
+
+        if !config::COMPANION_AI.engages(&fighter) {
+            let _ = writeln!(text, "The {name} keeps well back from the fight.");
+            i += 1;
+            continue;
+        }
+
+        // A dead enemy can't be hit, and can't hit back
+        if enemy.health.is_dead() {
+            i += 1;
+            continue;
+        }
+
+        if roll_chance(&mut player.rng, config::COMPANION_HIT_CHANCE_PERCENT) {
+            let damage = enemy.take_hit(fighter.damage, fighter.damage_type);
+            let _ = writeln!(text, "The {name} lands a blow on the {}, dealing {damage} damage.", enemy.name);
+        } else {
+            let _ = writeln!(text, "The {name} swings at the {}, but misses.", enemy.name);
+        }
+
+        if let Some(damage) = enemy_damage.filter(|_| roll_chance(&mut player.rng, config::COMPANION_TARGETED_CHANCE_PERCENT)) {
+            let damage = fighter.health.damage(damage);
+            let _ = writeln!(text, "The {} catches the {name} as well, dealing {damage} damage.", enemy.name);
+        }
+
+        if fighter.health.is_dead() {
+            let _ = writeln!(text, "The {name} falls, and doesn't get back up.");
+            player.companions.remove(i);
+            player.fallen_companions.push(name);
+            player.log(format!("Lost the {name} in a fight with the {}", enemy.name));
+            continue;
+        }
+
+        let _ = writeln!(text, "The {name} is at {}.", fighter.health.display_against(fighter.max_health));
+        player.companions[i].fighter = Some(fighter);
+        i += 1;
+    }
+
+    if text.is_empty() {
+        None
+    } else {
+        text.pop();
+        Some(text)
+    }
+}
+
+/// Gets a compact line listing everything affecting the [player][Player] and the [`Enemy`] in battle, with how many turns each has left,
+/// or [`None`] if nothing is affecting either of them. It is shown on every turn's result, so it is kept short enough for narrow terminals.
+pub fn describe_statuses(player: &Player, enemy: &Enemy) -> Option<String> {
+    let describe = |effects: Vec<String>| if effects.is_empty() { "-".to_string() } else { effects.join(", ") };
+    let player_effects = player.get_status_effects();
+    let enemy_effects = enemy.get_status_effects();
+
+    if player_effects.is_empty() && enemy_effects.is_empty() {
+        return None;
+    }
+
+    Some(format!("[You: {} | {}: {}]", describe(player_effects), enemy.name, describe(enemy_effects)))
+}
+
+/// Rolls a percentage chance, using the given [`Rng`]. A certain chance isn't rolled for, so it doesn't change what happens next.
+fn roll_chance(rng: &mut Rng, chance_percent: usize) -> bool {
+    chance_percent >= 100 || rng.below(100) < chance_percent
+}
+
+/// Rolls whether the [player's][Player] and the [`Enemy`]'s attacks this turn connect, using the player's [RNG][Player::rng] so that misses can be reproduced.
+/// The player's chance to hit is their weapon's [accuracy][Weapon::accuracy] less the enemy's [evasion][Enemy::evasion],
+/// and the enemy's is their weapon's accuracy scaled by their own [accuracy][Enemy::accuracy]. Only straight and sideways attacks can miss.
+///
+/// A [`Screen`] is shown for each miss, and the attack is carried out as [doing nothing][Action::Nothing] instead.
+/// The turn is still used up, and the player's weapon is still [worn down][wear_weapon].
+///
+/// ### Returns:
+/// The player's and the enemy's actions, with any missed attacks replaced
+pub fn roll_to_hit(player: &mut Player, enemy: &Enemy, player_action: Action, enemy_action: Action, menu: &mut impl Menu) -> (Action, Action) {
+    use Action::*;
+
+    let player_action = match player_action {
+        AttackLeft(p) | AttackStraight(p) | AttackRight(p) => {
+            let Item::Weapon(weapon) = &player.inventory[p].0 else {unreachable!()};
+
+            if roll_chance(&mut player.rng, weapon.accuracy.saturating_sub(enemy.evasion)) {
+                player_action
+            } else {
+                menu.show_screen(Screen {
+                    title: "You miss!",
+                    content: &format!("The {} twists out of the way, and your {} doesn't connect.", enemy.name, weapon.name),
+                    title_color: Some(Color::Yellow),
+                    art: None,
+                });
+                Nothing
+            }
+        }
+        _ => player_action,
+    };
+
+    let enemy_action = match enemy_action {
+        AttackLeft(e) | AttackStraight(e) | AttackRight(e) => {
+            let Item::Weapon(weapon) = &enemy.inventory[e] else {unreachable!()};
+
+            if roll_chance(&mut player.rng, weapon.accuracy * enemy.accuracy / 100) {
+                enemy_action
+            } else {
+                menu.show_screen(Screen {
+                    title: &format!("The {} misses!", enemy.name),
+                    content: &format!("Their {} goes wide.", weapon.name),
+                    title_color: Some(Color::Green),
+                    art: None,
+                });
+                Nothing
+            }
+        }
+        _ => enemy_action,
+    };
+
+    (player_action, enemy_action)
+}
+
+/// Raises the ship's [alarm][crate::alarm::Alarm] if a battle against the [`Enemy`] with the given name went on for long enough to be heard.
+/// `turn` is the 0-based turn the battle ended on.
+fn raise_alarm_if_loud(player: &mut Player, menu: &mut impl Menu, enemy_name: &str, turn: usize) {
+    if turn + 1 >= config::ALARM_LOUD_BATTLE_TURNS {
+        player.raise_alarm(menu, &format!("Your fight with the {enemy_name} was loud enough for the whole ship to hear."));
+    }
+}
+
+/// Wears down the [player's][Player] weapon by 1 [durability][Weapon::durability] if they attacked with it this turn.
+/// If it runs out of durability it breaks, and is taken out of their inventory.
+fn wear_weapon(player: &mut Player, player_action: Action, menu: &mut impl Menu) {
+    let (Action::AttackLeft(p) | Action::AttackStraight(p) | Action::AttackRight(p)) = player_action else {
+        return;
+    };
+    let Item::Weapon(weapon) = &mut player.inventory[p].0 else {unreachable!()};
+
+    weapon.durability = weapon.durability.saturating_sub(1);
+    if weapon.durability > 0 {
+        return;
     }
+
+    let weapon = player.take_item(p);
+    player.log(format!("Broke the {}", weapon.get_name()));
+
+    menu.show_screen(Screen {
+        title: &format!("Your {} breaks", weapon.get_name()),
+        content: "It's taken one hit too many, and falls apart in your hands. A repair kit might have saved it, but it's too late now.",
+        title_color: Some(Color::Red),
+        art: None,
+    });
+}
+
+/// Throws the [explosive][Item::Explosive] at the given index in the [player's][Player] inventory at the [`Enemy`], dealing [`EXPLOSIVE_DAMAGE`][config::EXPLOSIVE_DAMAGE]
+/// to them and showing a [`Screen`] listing the hits. If `close_range` is set, the player is caught in the blast as well.
+/// An enemy killed by the blast is rewarded at the end of the turn, in the same way as one killed by a normal attack.
+fn explode(player: &mut Player, enemy: &mut Enemy, i: usize, close_range: bool, menu: &mut impl Menu) {
+    let explosive = player.take_item(i);
+
+    let damage = enemy.health.damage(config::EXPLOSIVE_DAMAGE);
+    let mut hits = format!("• The {} takes {} damage, and is now at {}\n", enemy.name, damage, enemy.describe_health());
+
+    if close_range {
+        let damage = player.take_hit(config::EXPLOSIVE_SELF_DAMAGE);
+        // Writing to a String can't fail
+        let _ = writeln!(hits, "• You were too close, and take {} damage. You are now at {}", damage, player.health.display_against(player.max_health));
+    }
+
+    menu.show_screen(Screen {
+        title: &format!("Your {} goes off", explosive.get_name()),
+        content: &format!("You pull the pin and throw it at the {}. The blast knocks them off their feet.\n{hits}", enemy.name),
+        title_color: Some(if close_range { Color::Yellow } else { Color::Green }),
+        art: None,
+    });
+}
+
+/// Moves the [player][Player] through a random open door out of the room, leaving the [`Enemy`] behind to [pursue][Player::check_pursuit] them.
+fn flee(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
+    let destinations = player.get_flee_destinations();
+    let to = destinations[player.rng.below(destinations.len())];
+
+    menu.show_screen(Screen {
+        title: &format!("You run for the {}", to.get_name()),
+        content: &format!(
+            "You turn your back on the {} and sprint for the door. You don't stop until you're in the {}, but you can hear them coming after you.\n\nClosing the door behind you might slow them down.",
+            enemy.name,
+            to.get_name()
+        ),
+        title_color: Some(Color::Yellow),
+        art: None,
+    });
+
+    player.log(format!("Fled from the {}", enemy.name));
+    player.get_room_state_mut().enemy = Some(enemy);
+    player.enter_room(to);
 }
 
 /// Shows the player a battle win screen and adds the enemy's items to the player's inventory.
-fn win_battle(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
-    let mut result_text = "You won the battle!\n\n".to_string();
+/// The enemy's [drops][Enemy::drops] are left in the room for the player to pick up. Each item may [roll][Rarity::roll] a higher [`Rarity`].
+fn win_battle(player: &mut Player, mut enemy: Enemy, menu: &mut impl Menu) {
+    player.log(format!("Defeated the {}", enemy.name));
+
+    // The loot may turn out to be better than it looked
+    for item in enemy.inventory.iter_mut().chain(&mut enemy.drops) {
+        *item = item.clone().with_rarity(Rarity::roll(&mut player.rng));
+    }
+    player.defeated_enemies.push(enemy.name);
+    player.stats.enemies_defeated += 1;
+
+    let mut result_text = format!("You won the battle, and gained {} XP!\n\n", enemy.xp_reward);
 
     if !enemy.inventory.is_empty() {
         result_text += &format!(
@@ -230,20 +1156,32 @@ fn win_battle(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
         );
     }
 
-    for item in &enemy.inventory {
-        result_text += &format!("• {} - {}\n", item.get_name(), item.get_description());
-    }
+    result_text += &list_items(&enemy.inventory);
 
     let screen = Screen {
         title: "Battle Result",
         content: &result_text,
+        title_color: None,
+        art: None,
     };
 
     menu.show_screen(screen);
 
     for item in enemy.inventory {
-        player.pick_up_item(item);
+        player.pick_up_item(menu, item);
+    }
+
+    if !enemy.drops.is_empty() {
+        menu.show_screen(Screen {
+            title: &format!("The {} dropped something", enemy.name),
+            content: &format!("As the {} falls, something clatters to the floor of the {}:\n{}", enemy.name, player.room.get_name(), list_items(&enemy.drops)),
+            title_color: None,
+            art: None,
+        });
+        player.get_room_state_mut().items.extend(enemy.drops);
     }
+
+    player.gain_xp(menu, enemy.xp_reward);
 }
 
 /// Carries out the actions performed by the player and enemy on a given turn.
@@ -256,6 +1194,7 @@ fn win_battle(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
 ///
 /// ### Returns:
 /// A string containing a short description of the result of the turn
+#[allow(clippy::too_many_lines)] // Each combination of actions needs its own arm
 fn execute_actions(
     player: &mut Player,
     enemy: &mut Enemy,
@@ -264,24 +1203,57 @@ fn execute_actions(
 ) -> String {
     use Action::*;
 
+    // Describe the actions before they are carried out, as eating or throwing an item removes it from the inventory
+    let player_description = player.describe_combat_action(player_action);
+    let enemy_description = enemy.describe_combat_action(enemy_action);
+
+    // A thrown weapon leaves the player's hands whether or not it hits
+    let thrown = if let Throw(p) = player_action {
+        let Item::Weapon(weapon) = player.take_item(p) else {unreachable!()};
+        Some(weapon)
+    } else {
+        None
+    };
+
+    // A bandage goes on before anything else happens, so the enemy gets a free turn
+    if let UseBandage(p) = player_action {
+        player.take_item(p);
+        player.apply_bandage();
+    }
+
     // Take the turn
     let result_text = match (player_action, enemy_action) {
+        // Enemies never throw their weapons, block, flee or use bandages, and the player fleeing ends the battle before the turn is taken
+        (_, Throw(_) | Block | Flee | UseBandage(_) | Explode(_) | Examine) | (Flee | Examine, _) => unreachable!(),
+        // Player throws their weapon
+        (Throw(_), _) => {
+            let Some(weapon) = &thrown else {unreachable!()};
+            resolve_throw(weapon, enemy, enemy_action, player)
+        }
         // Player hits enemy straight
         (AttackStraight(p), Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_)) => {
-            let Item::Weapon(weapon) = &player.inventory[p] else {unreachable!()};
-            let damage = weapon.straight_damage;
-            enemy.health -= damage;
+            let Item::Weapon(weapon) = &player.inventory[p].0 else {unreachable!()};
+            let damage = enemy.take_hit(player.get_attack_damage(weapon.straight_damage), weapon.damage_type);
 
             format!(
-                "You hit the {} with your {} and dealt {} damage.",
+                "You hit the {} with your {} and dealt {} damage.{}",
+                enemy.name, weapon.name, damage, enemy.describe_effectiveness(weapon.damage_type)
+            )
+        }
+        // Player blocks the enemy's straight attack
+        (Block, AttackStraight(e)) => {
+            let Item::Weapon(weapon) = &enemy.inventory[e] else {unreachable!()};
+            let damage = player.take_hit(blocked_damage(enemy.get_attack_damage(weapon.straight_damage)));
+
+            format!(
+                "You blocked the {}'s {}, and only took {} damage.",
                 enemy.name, weapon.name, damage
             )
         }
         // Enemy hits player straight
-        (Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_), AttackStraight(e)) => {
+        (Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_) | UseBandage(_) | Explode(_), AttackStraight(e)) => {
             let Item::Weapon(weapon) = &enemy.inventory[e] else {unreachable!()};
-            let damage = weapon.straight_damage;
-            player.health -= damage;
+            let damage = player.take_hit(enemy.get_attack_damage(weapon.straight_damage));
 
             format!(
                 "You hit the {} with your {} and dealt {} damage.",
@@ -290,39 +1262,40 @@ fn execute_actions(
         }
         // Both attack straight
         (AttackStraight(p), AttackStraight(e)) => {
-            let Item::Weapon(p_weapon) = &player.inventory[p] else {unreachable!()};
+            let Item::Weapon(p_weapon) = &player.inventory[p].0 else {unreachable!()};
             let Item::Weapon(e_weapon) = &enemy.inventory[e] else {unreachable!()};
 
-            let p_damage = p_weapon.straight_damage;
-            let e_damage = e_weapon.straight_damage;
+            let p_damage = player.get_attack_damage(p_weapon.straight_damage);
+            let p_damage_type = p_weapon.damage_type;
+            let e_damage = enemy.get_attack_damage(e_weapon.straight_damage);
 
-            // What happens when both combatants attack is determined by the speed values of their weapons
-            match p_weapon.speed.cmp(&e_weapon.speed) {
+            // What happens when both combatants attack is determined by the speed values of their weapons, unless they resolve simultaneously
+            match config::COMBAT_RESOLUTION.clash(p_weapon.speed, e_weapon.speed) {
                 // If the player's weapon is faster, only the player hits
                 Ordering::Less => {
-                    enemy.health -= p_damage;
-                    "You both attacked, and you were faster and got away unscathed".to_string()
+                    enemy.take_hit(p_damage, p_weapon.damage_type);
+                    format!("You both attacked, and you were faster and got away unscathed.{}", enemy.describe_effectiveness(p_weapon.damage_type))
                 }
                 // If the enemy's weapon is faster, on the the enemy hits
                 Ordering::Greater => {
-                    player.health -= e_damage;
+                    player.take_hit(e_damage);
                     format!("You both attacked, but the {} was faster and you couldn't get a hit in.", enemy.name)
                 }
                 // If they have the same speed, both get hit.
                 Ordering::Equal => {
-                    enemy.health -= p_damage;
-                    player.health -= e_damage;
-                    "You both attacked with the same speed, and you both got hit.".to_string()
+                    enemy.take_hit(p_damage, p_damage_type);
+                    player.take_hit(e_damage);
+                    format!("You both attacked with the same speed, and you both got hit.{}", enemy.describe_effectiveness(p_damage_type))
                 }
             }
         }
         // Both heal
         (EatFood(p), EatFood(e)) => {
-            let Item::Food(p_food) = player.inventory.remove(p) else {unreachable!()};
+            let Item::Food(p_food) = player.take_item(p) else {unreachable!()};
             let Item::Food(e_food) = enemy.inventory.remove(e) else {unreachable!()};
 
-            let p_inc = player.health.heal_to_max(p_food.heals_for, player.max_health);
-            let e_inc = enemy.health.heal_to_max(e_food.heals_for, enemy.max_health);
+            let p_inc = player.eat(&p_food);
+            let e_inc = enemy.health.heal_to_max(e_food.get_heals_for(), enemy.max_health);
 
             format!(
                 "You both took some time out of the fight to eat some food - how peaceful.\nYou ate your {} and were healed {} HP. The {} ate their {} and was healed {} HP.",
@@ -331,8 +1304,8 @@ fn execute_actions(
         }
         // Player heals
         (EatFood(p), _) => {
-            let Item::Food(p_food) = player.inventory.remove(p) else {unreachable!()};
-            let p_inc = player.health.heal_to_max(p_food.heals_for, player.max_health);
+            let Item::Food(p_food) = player.take_item(p) else {unreachable!()};
+            let p_inc = player.eat(&p_food);
 
             format!(
                 "You ate your {} and were healed by {} HP",
@@ -342,7 +1315,7 @@ fn execute_actions(
         // Enemy heals
         (_, EatFood(e)) => {
             let Item::Food(e_food) = enemy.inventory.remove(e) else {unreachable!()};
-            let e_inc = enemy.health.heal_to_max(e_food.heals_for, enemy.max_health);
+            let e_inc = enemy.health.heal_to_max(e_food.get_heals_for(), enemy.max_health);
 
             format!(
                 "The {} ate their {} and was healed by {} HP",
@@ -351,30 +1324,32 @@ fn execute_actions(
         }
         // Enemy dodges but player hits
         (AttackLeft(p), DodgeLeft) | (AttackRight(p), DodgeRight) => {
-            let Item::Weapon(p_weapon) = &player.inventory[p] else {unreachable!()};
+            let Item::Weapon(p_weapon) = &player.inventory[p].0 else {unreachable!()};
 
-            let prev_enemy_health = enemy.health;
-            enemy.health -= p_weapon.dodge_damage;
+            let damage = enemy.take_hit(player.get_attack_damage(p_weapon.dodge_damage), p_weapon.damage_type);
 
             format!(
-                "The {} dodged, but you caught them and dealt {} damage.",
-                enemy.name, prev_enemy_health - enemy.health
+                "The {} dodged, but you caught them and dealt {} damage.{}",
+                enemy.name, damage, enemy.describe_effectiveness(p_weapon.damage_type)
             )
         }
         // Player dodges but enemy hits
         (DodgeLeft, AttackLeft(e)) | (DodgeRight, AttackRight(e)) => {
             let Item::Weapon(e_weapon) = &enemy.inventory[e] else {unreachable!()};
 
-            let prev_player_health = player.health;
-            player.health -= e_weapon.dodge_damage;
+            let damage = player.take_hit(enemy.get_attack_damage(e_weapon.dodge_damage));
 
             format!(
                 "You dodged, but the {} caught you and dealt {} damage.",
-                enemy.name, prev_player_health - player.health
+                enemy.name, damage
             )
         }
         // Neither the player or the enemy attacks
-        (Nothing | DodgeLeft | DodgeRight, Nothing | DodgeLeft | DodgeRight) => {
+        // The player's explosive has already gone off, and the enemy doesn't hit back
+        (Explode(_), Nothing | DodgeLeft | DodgeRight) => {
+            "The smoke starts to clear.".to_string()
+        }
+        (Nothing | DodgeLeft | DodgeRight | Block | UseBandage(_), Nothing | DodgeLeft | DodgeRight) => {
             "Neither of you attacked. What a waste of time.".to_string()
         }
         // The player attacks but it is dodged
@@ -387,9 +1362,128 @@ fn execute_actions(
         }
     };
 
-    format!(
-        "{}\n{}\n{result_text}",
-        player.describe_combat_action(player_action),
-        enemy.describe_combat_action(enemy_action),
-    )
+    // The thrown weapon can be picked up from the floor after the battle
+    let result_text = if let Some(weapon) = thrown {
+        let text = format!("{result_text}\nYour {} clatters to the floor. You'll have to pick it up after the fight.", weapon.name);
+        player.get_room_state_mut().items.push(Item::Weapon(weapon));
+        text
+    } else {
+        result_text
+    };
+
+    format!("{player_description}\n{enemy_description}\n{result_text}")
+}
+
+/// Gets whether the [player][Player] dodged away from an [`Enemy`]'s attack.
+/// Dodging into an attack, e.g. [dodging left][Action::DodgeLeft] into an [attack to the left][Action::AttackLeft], doesn't count.
+const fn dodged_away(player_action: Action, enemy_action: Action) -> bool {
+    use Action::*;
+
+    match (player_action, enemy_action) {
+        (DodgeLeft, AttackLeft(_)) | (DodgeRight, AttackRight(_)) => false,
+        (DodgeLeft | DodgeRight, AttackLeft(_) | AttackStraight(_) | AttackRight(_)) => true,
+        _ => false,
+    }
+}
+
+/// Gives an [`Enemy`] whose weapon has a longer [reach][Weapon::reach] than the [player's][Player::get_reach] a chance to catch them after they dodge away.
+/// The chance is [`REACH_DODGE_PENALTY_PERCENT`][config::REACH_DODGE_PENALTY_PERCENT] for each point of difference, and a caught player takes the weapon's dodge damage.
+///
+/// ### Returns:
+/// A short description of the player being caught, or [`None`] if they weren't
+fn catch_dodge_with_reach(player: &mut Player, enemy: &Enemy, player_action: Action, enemy_action: Action) -> Option<String> {
+    use Action::*;
+
+    if !dodged_away(player_action, enemy_action) {
+        return None;
+    }
+    let (AttackLeft(e) | AttackStraight(e) | AttackRight(e)) = enemy_action else {unreachable!()};
+    let Item::Weapon(e_weapon) = &enemy.inventory[e] else {unreachable!()};
+
+    let extra_reach = e_weapon.reach.saturating_sub(player.get_reach());
+    if player.rng.below(100) >= extra_reach * config::REACH_DODGE_PENALTY_PERCENT {
+        return None;
+    }
+
+    let damage = player.take_hit(enemy.get_attack_damage(e_weapon.dodge_damage));
+
+    Some(format!(
+        "You didn't get far enough away - the {}'s {} has the reach to catch you anyway, and deals {} damage.",
+        enemy.name, e_weapon.name, damage
+    ))
+}
+
+/// Gives the [player][Player] a chance to counter-attack after they dodge away from an [`Enemy`]'s attack.
+/// The chance is [`COUNTER_CHANCE_PERCENT`][config::COUNTER_CHANCE_PERCENT], and the counter uses the player's [main weapon][Player::get_main_weapon].
+/// Dodging into an attack, e.g. [dodging left][Action::DodgeLeft] into an [attack to the left][Action::AttackLeft], never gives a counter,
+/// and there are no counters at all when [actions resolve simultaneously][Resolution::Simultaneous].
+///
+/// ### Returns:
+/// A short description of the counter-attack, or [`None`] if there wasn't one
+fn counter_attack(player: &mut Player, enemy: &mut Enemy, player_action: Action, enemy_action: Action) -> Option<String> {
+    if !config::COMBAT_RESOLUTION.allows_counters()
+        || !dodged_away(player_action, enemy_action)
+        || player.rng.below(100) >= config::COUNTER_CHANCE_PERCENT
+    {
+        return None;
+    }
+
+    let weapon = player.get_main_weapon()?;
+
+    let damage = enemy.take_hit(player.get_attack_damage(weapon.straight_damage) + config::COUNTER_BONUS_DAMAGE, weapon.damage_type);
+
+    Some(format!(
+        "The {} leaves themself open, and you strike back with your {} for {} damage.{}",
+        enemy.name, weapon.name, damage, enemy.describe_effectiveness(weapon.damage_type)
+    ))
+}
+
+/// Carries out the [player][Player] [throwing][Action::Throw] a weapon at an [`Enemy`].
+/// The throw connects unless the enemy dodges, or attacks straight with a faster weapon.
+///
+/// ### Params:
+/// * `weapon`: the weapon which was thrown. It should already have been removed from the player's inventory.
+/// * `enemy`: the [`Enemy`] which is being battled
+/// * `enemy_action`: the [`Action`] which the enemy chose
+/// * `player`: the [`Player`]'s state
+///
+/// ### Returns:
+/// A string containing a short description of the result of the throw
+fn resolve_throw(weapon: &Weapon, enemy: &mut Enemy, enemy_action: Action, player: &mut Player) -> String {
+    use Action::*;
+
+    let damage = player.get_attack_damage(weapon.throw_damage);
+
+    match enemy_action {
+        Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_) => {
+            let damage = enemy.take_hit(damage, weapon.damage_type);
+            format!(
+                "Your {} hit the {} and dealt {} damage.{}",
+                weapon.name, enemy.name, damage, enemy.describe_effectiveness(weapon.damage_type)
+            )
+        }
+        AttackStraight(e) => {
+            let Item::Weapon(e_weapon) = &enemy.inventory[e] else {unreachable!()};
+            let e_damage = enemy.get_attack_damage(e_weapon.straight_damage);
+
+            // The thrown weapon flies at the speed it would normally be swung at
+            match config::COMBAT_RESOLUTION.clash(weapon.speed, e_weapon.speed) {
+                Ordering::Less => {
+                    enemy.take_hit(damage, weapon.damage_type);
+                    format!("Your {} hit the {} before they could reach you.{}", weapon.name, enemy.name, enemy.describe_effectiveness(weapon.damage_type))
+                }
+                Ordering::Greater => {
+                    player.take_hit(e_damage);
+                    format!("The {} hit you before you could let go of your {}.", enemy.name, weapon.name)
+                }
+                Ordering::Equal => {
+                    enemy.take_hit(damage, weapon.damage_type);
+                    player.take_hit(e_damage);
+                    format!("Your {} hit the {} just as they hit you.{}", weapon.name, enemy.name, enemy.describe_effectiveness(weapon.damage_type))
+                }
+            }
+        }
+        DodgeLeft | DodgeRight => format!("The {} dodged your {}.", enemy.name, weapon.name),
+        Throw(_) | Block | Flee | UseBandage(_) | Explode(_) | Examine => unreachable!(),
+    }
 }
@@ -1,10 +1,44 @@
-//! Contains the [`Health`] and [`Damage`] types for representing HP
+//! Contains the [`Health`] and [`Damage`] types for representing HP.
+//! All of their arithmetic saturates, so a [`Health`] can never wrap round below 0 or past [`usize::MAX`].
+
+mod tests;
 
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Sub},
 };
 
+use serde::{Deserialize, Serialize};
+
+use crate::settings;
+
+/// How [`Health`] is shown to the player. This defaults to [`config::HEALTH_DISPLAY`][crate::config::HEALTH_DISPLAY], and can be changed in the [settings][crate::settings].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthDisplay {
+    /// Show health as HP out of the max, e.g. `7/10 HP`
+    Absolute,
+    /// Show health as a percentage of the max, e.g. `70%`
+    Percent,
+}
+
+impl HealthDisplay {
+    /// Gets the name of the display style, as it is shown in the [settings menu][crate::settings::show]
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Absolute => "HP",
+            Self::Percent => "Percentage",
+        }
+    }
+
+    /// Gets the display style after this one, for cycling through them in the [settings menu][crate::settings::show]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Absolute => Self::Percent,
+            Self::Percent => Self::Absolute,
+        }
+    }
+}
+
 /// The health of the player or an enemy
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Health(usize);
@@ -19,31 +53,88 @@ impl Health {
         Self(health)
     }
 
-    /// Checks whether the health is 0. Used to calculate whether the player or an enemy has lost a battle.
-    pub const fn is_0(self) -> bool {
+    /// Creates a new [`Health`] from a number of HP, but no more than the given max health
+    pub const fn clamped(health: usize, max: Self) -> Self {
+        if health > max.0 { max } else { Self(health) }
+    }
+
+    /// Checks whether the health is 0. Used to calculate whether the player or an enemy has lost a battle, or the player has died outside of one.
+    pub const fn is_dead(self) -> bool {
         self.0 == 0
     }
 
+    /// Decreases the [`Health`] by the given [`Damage`], stopping at 0.
+    /// Returns how much the health decreased by, which is less than `amount` if it was more than enough to kill.
+    pub fn damage(&mut self, amount: Damage) -> Damage {
+        let new_health = self.0.saturating_sub(amount.0);
+        let diff = self.0 - new_health;
+        self.0 = new_health;
+        Damage::new(diff)
+    }
+
     /// Increases the [`Health`] by the given [`Damage`], up to the given max health.
-    /// Returns how much the health increased by.
+    /// Returns how much the health increased by. Health which is already over the max is left as it is.
     pub fn heal_to_max(&mut self, heal_by: Damage, max: Self) -> Damage {
-        let new_health = max.0.min(self.0 + heal_by.0);
+        let new_health = max.0.min(self.0.saturating_add(heal_by.0)).max(self.0);
         let diff = new_health - self.0;
         self.0 = new_health;
         Damage::new(diff)
     }
 
+    /// Renders the health as an ASCII bar of the given width compared to the given max health, e.g. `[######----]`.
+    /// The bar is only shown empty at 0 HP and full at max HP, so that a sliver of health is never hidden by rounding.
+    pub fn get_bar(self, max: Self, width: usize) -> String {
+        // Round to the nearest character, then make sure any health shows and any damage shows
+        let filled = rounded_ratio(self.0, max.0, width).map_or(0, |filled| {
+            let filled = if self.0 > 0 { filled.max(1) } else { filled };
+            let filled = if self.0 < max.0 { filled.min(width.saturating_sub(1)) } else { filled };
+            filled.min(width)
+        });
+
+        format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+    }
+
+    /// Gets the health as a percentage of the given max health, rounded to the nearest percent.
+    /// Like [`get_bar`][Health::get_bar], this is only 0% at 0 HP and 100% at max HP.
+    pub fn get_percentage(self, max: Self) -> usize {
+        rounded_ratio(self.0, max.0, 100).map_or(0, |percent| {
+            let percent = if self.0 > 0 { percent.max(1) } else { percent };
+            let percent = if self.0 < max.0 { percent.min(99) } else { percent };
+            percent.min(100)
+        })
+    }
+
+    /// Formats the health against the given max health, in the style chosen in the [settings][settings::Settings::health_display]
+    pub fn display_against(self, max: Self) -> String {
+        match settings::get().health_display {
+            HealthDisplay::Absolute => format!("{self}/{max} HP"),
+            HealthDisplay::Percent => format!("{}% health", self.get_percentage(max)),
+        }
+    }
+
     /// Gets the value of the health as a [`usize`]. This is needed to do more advanced calculations than just adding and subtracting [`Damage`] values.
     pub const fn as_usize(self) -> usize {
         self.0
     }
 }
 
+/// Gets `value / max * scale`, rounded to the nearest whole number, or [`None`] if `max` is 0.
+/// This is worked out with more bits than a [`usize`] has, so that large health values can't overflow.
+fn rounded_ratio(value: usize, max: usize, scale: usize) -> Option<usize> {
+    let ratio = (value as u128 * scale as u128 + max as u128 / 2).checked_div(max as u128)?;
+    Some(usize::try_from(ratio).unwrap_or(usize::MAX))
+}
+
 impl Damage {
     /// Creates a new [`Damage`] from a change in HP
     pub const fn new(damage: usize) -> Self {
         Self(damage)
     }
+
+    /// Gets the value of the damage as a [`usize`]
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
 }
 
 impl Sub<Damage> for Health {
@@ -58,27 +149,29 @@ impl Add<Damage> for Health {
     type Output = Self;
 
     fn add(self, rhs: Damage) -> Self::Output {
-        Self(self.0 + rhs.0)
+        Self(self.0.saturating_add(rhs.0))
     }
 }
 
-impl Sub<Self> for Health {
-    type Output = Damage;
+impl Add<Self> for Damage {
+    type Output = Self;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Damage(self.0 - rhs.0)
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
     }
 }
 
-impl SubAssign<Damage> for Health {
-    fn sub_assign(&mut self, rhs: Damage) {
-        self.0 = self.0.saturating_sub(rhs.0);
+impl Sub<Self> for Health {
+    type Output = Damage;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Damage(self.0.saturating_sub(rhs.0))
     }
 }
 
 impl AddAssign<Damage> for Health {
     fn add_assign(&mut self, rhs: Damage) {
-        self.0 += rhs.0;
+        self.0 = self.0.saturating_add(rhs.0);
     }
 }
 
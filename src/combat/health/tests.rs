@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that healing stops at the max health, reports how much was actually healed, and never lowers health which is already over the max
+#[test]
+fn test_heal_past_max() {
+    let mut health = Health::new(8);
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(10)), Damage::new(2));
+    assert_eq!(health, Health::new(10));
+
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(10)), Damage::new(0));
+    assert_eq!(health, Health::new(10));
+
+    let mut health = Health::new(12);
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(10)), Damage::new(0));
+    assert_eq!(health, Health::new(12));
+
+    let mut health = Health::new(usize::MAX - 1);
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(usize::MAX)), Damage::new(1));
+    assert_eq!(health, Health::new(usize::MAX));
+}
+
+/// Tests that damage stops at 0 health and reports how much was actually taken, and that subtracting a larger value stops at 0 rather than underflowing
+#[test]
+fn test_damage_below_zero() {
+    let mut health = Health::new(3);
+    assert_eq!(health.damage(Damage::new(10)), Damage::new(3));
+    assert_eq!(health, Health::new(0));
+    assert!(health.is_dead());
+
+    assert_eq!(health.damage(Damage::new(1)), Damage::new(0));
+    assert!(health.is_dead());
+
+    assert_eq!(Health::new(3) - Damage::new(5), Health::new(0));
+    assert_eq!(Health::new(3) - Health::new(5), Damage::new(0));
+    assert_eq!(Health::new(5) - Health::new(3), Damage::new(2));
+}
+
+/// Tests that damage equal to the remaining health kills, and one less doesn't
+#[test]
+fn test_exact_kill() {
+    let mut health = Health::new(5);
+    assert_eq!(health.damage(Damage::new(4)), Damage::new(4));
+    assert!(!health.is_dead());
+
+    let mut health = Health::new(5);
+    assert_eq!(health.damage(Damage::new(5)), Damage::new(5));
+    assert!(health.is_dead());
+}
+
+/// Tests that adding past the largest value stops there rather than overflowing
+#[test]
+fn test_add_overflow() {
+    assert_eq!(Health::new(usize::MAX) + Damage::new(1), Health::new(usize::MAX));
+    assert_eq!(Damage::new(usize::MAX) + Damage::new(1), Damage::new(usize::MAX));
+
+    let mut health = Health::new(usize::MAX);
+    health += Damage::new(1);
+    assert_eq!(health, Health::new(usize::MAX));
+
+    assert_eq!(health.get_percentage(Health::new(usize::MAX)), 100);
+    assert_eq!(Health::new(usize::MAX / 2).get_bar(Health::new(10), 4), "[####]");
+}
+
+/// Tests that a clamped health is never over the max
+#[test]
+fn test_clamped() {
+    assert_eq!(Health::clamped(5, Health::new(10)), Health::new(5));
+    assert_eq!(Health::clamped(15, Health::new(10)), Health::new(10));
+}
@@ -0,0 +1,649 @@
+#![cfg(test)]
+
+use crate::{items::{AttackStyle, DamageType, Rarity, Weapon}, menu::tests::MockMenu, npc::{Fighter, Npc}, player::Player, rooms::{DoorState, Room}};
+
+use super::*;
+
+/// Creates a weapon with the given throw damage and speed for testing
+const fn test_weapon(throw_damage: usize, speed: usize) -> Item {
+    Item::Weapon(Weapon {
+        name: "",
+        description: "",
+        damage_type: DamageType::Blunt,
+        straight_damage: Damage::new(1),
+        dodge_damage: Damage::new(1),
+        speed,
+        throw_damage: Damage::new(throw_damage),
+        two_handed: false,
+        accuracy: 100,
+        reach: 1,
+        durability: 20,
+        max_durability: 20,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        attack_style: None,
+    })
+}
+
+/// Creates an enemy with the given health and inventory for testing
+fn test_enemy(health: usize, inventory: Vec<Item>) -> Enemy {
+    Enemy {
+        name: "",
+        description: "",
+        inventory,
+        drops: Vec::new(),
+        health: Health::new(health),
+        max_health: Health::new(health),
+        xp_reward: 0,
+        bonus_damage: Damage::new(0),
+        accuracy: 100,
+        evasion: 0,
+        resistances: &[],
+        attack_pattern: AttackPattern::Random,
+        phases: &[],
+        phase: 0,
+        alert: false,
+        awareness: 0,
+    }
+}
+
+/// Tests that a thrown weapon deals its throw damage and ends up on the floor of the room
+#[test]
+fn test_throw() {
+    let mut player = Player::init(0);
+    player.strength = 0;
+    player.inventory.push((test_weapon(4, 3), 1));
+    let room_items = player.get_room_state().items.len();
+
+    let mut enemy = test_enemy(10, Vec::new());
+    execute_actions(&mut player, &mut enemy, Action::Throw(0), Action::Nothing);
+
+    assert_eq!(enemy.health, Health::new(6));
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.get_room_state().items.len(), room_items + 1);
+}
+
+/// Tests that a dodged throw deals no damage, but the weapon is still lost until after the battle
+#[test]
+fn test_dodged_throw() {
+    let mut player = Player::init(0);
+    player.inventory.push((test_weapon(4, 3), 1));
+
+    let mut enemy = test_enemy(10, Vec::new());
+    execute_actions(&mut player, &mut enemy, Action::Throw(0), Action::DodgeLeft);
+
+    assert_eq!(enemy.health, Health::new(10));
+    assert!(player.inventory.is_empty());
+    assert!(matches!(player.get_room_state().items.last(), Some(Item::Weapon(_))));
+}
+
+/// Tests that a slower throw loses to a faster straight attack
+#[test]
+fn test_throw_against_faster_attack() {
+    let mut player = Player::init(0);
+    player.inventory.push((test_weapon(4, 3), 1));
+    player.health = Health::new(10);
+
+    let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+    execute_actions(&mut player, &mut enemy, Action::Throw(0), Action::AttackStraight(0));
+
+    assert_eq!(enemy.health, Health::new(10));
+    assert_eq!(player.health, Health::new(9));
+}
+
+/// Tests that strength adds bonus damage, rounding down
+#[test]
+fn test_attack_damage() {
+    let per_bonus = config::STRENGTH_PER_BONUS_DAMAGE;
+
+    assert_eq!(attack_damage(Damage::new(3), 0), Damage::new(3));
+    assert_eq!(attack_damage(Damage::new(3), per_bonus - 1), Damage::new(3));
+    assert_eq!(attack_damage(Damage::new(3), per_bonus), Damage::new(4));
+    assert_eq!(attack_damage(Damage::new(3), per_bonus * 3), Damage::new(6));
+}
+
+/// Tests that the player's strength is added to the damage of a straight attack
+#[test]
+fn test_strength_attack() {
+    let mut player = Player::init(0);
+    player.strength = config::STRENGTH_PER_BONUS_DAMAGE * 2;
+    player.inventory.push((test_weapon(0, 3), 1));
+
+    let mut enemy = test_enemy(10, Vec::new());
+    execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::Nothing);
+
+    // The test weapon has a base straight damage of 1
+    assert_eq!(enemy.health, Health::new(7));
+}
+
+/// Tests that an enemy with a cyclic pattern repeats its steps in order
+#[test]
+fn test_cyclic_pattern() {
+    let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+    enemy.attack_pattern = AttackPattern::Cyclic(&[PatternStep::DodgeLeft, PatternStep::AttackRight]);
+    let mut rng = Rng::new(0);
+
+    for turn in 0..6 {
+        let action = enemy.choose_combat_action(&mut rng, turn);
+        if turn % 2 == 0 {
+            assert!(matches!(action, Action::DodgeLeft));
+        } else {
+            assert!(matches!(action, Action::AttackRight(0)));
+        }
+    }
+}
+
+/// Tests that an aggressive enemy never dodges
+#[test]
+fn test_aggressive_pattern() {
+    let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+    enemy.attack_pattern = AttackPattern::Aggressive;
+    let mut rng = Rng::new(0);
+
+    for turn in 0..100 {
+        let action = enemy.choose_combat_action(&mut rng, turn);
+        assert!(matches!(action, Action::AttackLeft(_) | Action::AttackStraight(_) | Action::AttackRight(_)));
+    }
+}
+
+/// Tests that blocking reduces the damage from a straight attack
+#[test]
+fn test_block() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+
+    // The test weapon has a base straight damage of 1, so use a stronger one
+    let mut weapon = test_weapon(0, 1);
+    let Item::Weapon(w) = &mut weapon else {unreachable!()};
+    w.straight_damage = Damage::new(4);
+
+    let mut enemy = test_enemy(10, vec![weapon]);
+    execute_actions(&mut player, &mut enemy, Action::Block, Action::AttackStraight(0));
+
+    assert_eq!(player.health, Health::new(10) - blocked_damage(Damage::new(4)));
+    assert!(player.health > Health::new(6));
+}
+
+/// Tests that dodging away from an attack can give a counter-attack, but dodging into one never does
+#[test]
+fn test_counter_attack() {
+    let mut player = Player::init(0);
+    player.strength = 0;
+    player.inventory.push((test_weapon(0, 1), 1));
+
+    // Dodging into the attack should never counter, whatever the rng rolls
+    for _ in 0..20 {
+        let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+        assert!(counter_attack(&mut player, &mut enemy, Action::DodgeLeft, Action::AttackLeft(0)).is_none());
+        assert_eq!(enemy.health, Health::new(10));
+    }
+
+    // Dodging away from the attack should counter some of the time, for the bonus damage
+    let mut countered = false;
+    for _ in 0..20 {
+        let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+        if counter_attack(&mut player, &mut enemy, Action::DodgeLeft, Action::AttackStraight(0)).is_some() {
+            countered = true;
+            assert_eq!(enemy.health, Health::new(10) - (Damage::new(1) + config::COUNTER_BONUS_DAMAGE));
+        }
+    }
+    assert!(countered);
+}
+
+/// Tests that a weapon with a longer reach can catch a dodge, and that one with the same reach can't
+#[test]
+fn test_reach_catches_dodge() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.inventory.push((test_weapon(0, 1), 1));
+
+    // With the same reach as the player, the dodge always gets away
+    let enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+    for _ in 0..20 {
+        assert!(catch_dodge_with_reach(&mut player, &enemy, Action::DodgeLeft, Action::AttackStraight(0)).is_none());
+    }
+
+    // With enough extra reach, the dodge is always caught
+    let Item::Weapon(mut long_weapon) = test_weapon(0, 1) else {unreachable!()};
+    long_weapon.reach = 1 + 100 / config::REACH_DODGE_PENALTY_PERCENT;
+    let enemy = test_enemy(10, vec![Item::Weapon(long_weapon)]);
+    assert!(catch_dodge_with_reach(&mut player, &enemy, Action::DodgeLeft, Action::AttackStraight(0)).is_some());
+    assert_eq!(player.health, Health::new(9));
+
+    // Dodging into the attack is handled as a normal hit, so reach doesn't come into it
+    assert!(catch_dodge_with_reach(&mut player, &enemy, Action::DodgeLeft, Action::AttackLeft(0)).is_none());
+}
+
+/// Tests that health bars are the right width, and only show as empty or full at exactly 0 or max health
+#[test]
+fn test_health_bar() {
+    let max = Health::new(100);
+
+    assert_eq!(Health::new(100).get_bar(max, 10), "[##########]");
+    assert_eq!(Health::new(50).get_bar(max, 10), "[#####-----]");
+    assert_eq!(Health::new(0).get_bar(max, 10), "[----------]");
+
+    // Very low or very nearly full health shouldn't be rounded away
+    assert_eq!(Health::new(1).get_bar(max, 10), "[#---------]");
+    assert_eq!(Health::new(99).get_bar(max, 10), "[#########-]");
+}
+
+/// Tests that health percentages round sensibly, without showing 0% or 100% unless health is exactly empty or full
+#[test]
+fn test_health_percentage() {
+    let max = Health::new(100);
+
+    assert_eq!(Health::new(100).get_percentage(max), 100);
+    assert_eq!(Health::new(0).get_percentage(max), 0);
+    assert_eq!(Health::new(1).get_percentage(Health::new(1000)), 1);
+    assert_eq!(Health::new(999).get_percentage(Health::new(1000)), 99);
+    assert_eq!(Health::new(2).get_percentage(Health::new(3)), 67);
+}
+
+/// Tests that a defeated enemy's drops are left in the room, and that picking up dropped escape pod keys unlocks the pod
+#[test]
+fn test_drops() {
+    let mut player = Player::init(0);
+    let room_items = player.get_room_state().items.len();
+
+    let mut enemy = test_enemy(0, Vec::new());
+    enemy.drops = vec![Item::EscapePodKeys];
+    win_battle(&mut player, enemy, &mut MockMenu::default());
+
+    assert!(!player.inventory.iter().any(|(item, _)| matches!(item, Item::EscapePodKeys)));
+    assert_eq!(player.get_room_state().items.len(), room_items + 1);
+
+    let keys = player.get_room_state_mut().items.pop().unwrap();
+    assert!(player.try_pick_up_item(&mut MockMenu::default(), keys).is_ok());
+    let pod_door = player.room_graph.get_state(Room::CrewArea).connections.iter().find(|c| c.to == Room::EscapePod).unwrap().door;
+    assert_eq!(pod_door, DoorState::Closed);
+}
+
+/// Tests that fleeing moves the player through an open door and leaves the enemy behind in the room they fled from
+#[test]
+fn test_flee() {
+    let mut player = Player::init(0);
+    let start = player.room;
+
+    flee(&mut player, test_enemy(5, Vec::new()), &mut MockMenu::default());
+
+    assert_ne!(player.room, start);
+    assert!(player.room_graph.get_state(start).enemy.is_some());
+    assert!(player.get_room_state().enemy.is_none());
+}
+
+/// Tests that an explosive hits the enemy it's thrown at, is used up, and only hurts the player at close range
+#[test]
+fn test_explode() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.inventory.push((Item::Explosive, 3));
+
+    let mut enemy = test_enemy(20, Vec::new());
+    explode(&mut player, &mut enemy, 0, false, &mut MockMenu::default());
+    assert_eq!(enemy.health, Health::new(20 - config::EXPLOSIVE_DAMAGE.as_usize()));
+    assert_eq!(player.health, Health::new(10));
+    assert_eq!(player.inventory[0].1, 2);
+
+    let mut enemy = test_enemy(5, Vec::new());
+    explode(&mut player, &mut enemy, 0, false, &mut MockMenu::default());
+    assert!(enemy.health.is_dead());
+
+    explode(&mut player, &mut enemy, 0, true, &mut MockMenu::default());
+    assert_eq!(player.health, Health::new(10 - config::EXPLOSIVE_SELF_DAMAGE.as_usize()));
+    assert!(player.inventory.is_empty());
+}
+
+/// Tests that attacking wears a weapon down, and that it breaks and is lost when it runs out of durability
+#[test]
+fn test_weapon_breaks() {
+    let mut player = Player::init(0);
+    let mut weapon = test_weapon(1, 1);
+    if let Item::Weapon(w) = &mut weapon {
+        w.durability = 2;
+    }
+    player.inventory.push((weapon, 1));
+    player.equipped_weapon = Some(0);
+
+    wear_weapon(&mut player, Action::AttackStraight(0), &mut MockMenu::default());
+    assert!(matches!(&player.inventory[0].0, Item::Weapon(w) if w.durability == 1));
+
+    // Defending doesn't wear the weapon down
+    wear_weapon(&mut player, Action::Block, &mut MockMenu::default());
+    assert_eq!(player.inventory.len(), 1);
+
+    wear_weapon(&mut player, Action::AttackLeft(0), &mut MockMenu::default());
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.equipped_weapon, None);
+}
+
+/// Tests a whole battle driven through the menu, checking the screens the player is shown along the way
+#[test]
+fn test_battle() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(test_weapon(1, 1), 1)];
+
+    let mut enemy = test_enemy(1, vec![test_weapon(1, 1)]);
+    enemy.name = "guard";
+    enemy.attack_pattern = AttackPattern::Cyclic(&[PatternStep::Nothing]);
+
+    // Attack straight ahead with the weapon, which comes after doing nothing and the three defensive actions
+    let mut menu = MockMenu::with_numbers([Some(4), Some(1)]);
+    assert!(matches!(battle(&mut player, enemy, &mut menu), BattleResult::PlayerWin));
+
+    let titles: Vec<_> = menu.get_screens().iter().map(|(title, _)| title.as_str()).collect();
+    assert_eq!(titles[0], "You are spotted by the guard");
+    assert_eq!(titles[1], "Turn Result");
+    assert!(menu.get_screens()[1].1.contains("guard"));
+    assert_eq!(player.remaining_turns, Player::init(0).remaining_turns);
+
+    assert_eq!(player.stats.enemies_defeated, 1);
+    assert_eq!(player.stats.damage_dealt, 1);
+    assert_eq!(player.stats.damage_taken, 0);
+}
+
+/// Tests that attacks are described in the weapon's own style, or in general terms if it doesn't have one
+#[test]
+fn test_attack_descriptions() {
+    let club = |attack_style| {
+        let Item::Weapon(weapon) = test_weapon(1, 1) else {unreachable!()};
+        Item::Weapon(Weapon { name: "Club", attack_style, ..weapon })
+    };
+
+    let mut player = Player::init(0);
+    player.inventory = vec![(club(None), 1), (club(Some(AttackStyle::Swing)), 1)];
+    assert_eq!(player.describe_combat_action(Action::AttackLeft(0)), "You attack to the left with your Club");
+    assert_eq!(player.describe_combat_action(Action::AttackLeft(1)), "You swing your Club round to the left");
+
+    let mut enemy = test_enemy(5, vec![club(None), club(Some(AttackStyle::Stab))]);
+    enemy.name = "Cook";
+    assert_eq!(enemy.describe_combat_action(Action::AttackStraight(0)), "The Cook attacks in front of them with their Club");
+    assert_eq!(enemy.describe_combat_action(Action::AttackStraight(1)), "The Cook stabs straight at you with their Club");
+}
+
+/// Tests that attacks which miss are carried out as doing nothing, and that misses are decided by the seeded RNG
+#[test]
+fn test_roll_to_hit() {
+    let inaccurate = |accuracy| {
+        let Item::Weapon(weapon) = test_weapon(1, 1) else {unreachable!()};
+        Item::Weapon(Weapon { accuracy, ..weapon })
+    };
+
+    let mut player = Player::init(0);
+    player.inventory = vec![(inaccurate(0), 1)];
+    let mut enemy = test_enemy(10, vec![test_weapon(1, 1)]);
+
+    // Perfectly accurate attacks always hit, but nothing can hit with no accuracy
+    let mut menu = MockMenu::default();
+    let actions = roll_to_hit(&mut player, &enemy, Action::AttackLeft(0), Action::AttackStraight(0), &mut menu);
+    assert_eq!(actions, (Action::Nothing, Action::AttackStraight(0)));
+    assert_eq!(menu.get_last_screen().unwrap().0, "You miss!");
+
+    enemy.accuracy = 0;
+    let actions = roll_to_hit(&mut player, &enemy, Action::DodgeLeft, Action::AttackRight(0), &mut menu);
+    assert_eq!(actions, (Action::DodgeLeft, Action::Nothing));
+
+    // The same seed always misses the same attacks
+    let roll_many = || {
+        let mut player = Player::init(7);
+        player.inventory = vec![(inaccurate(50), 1)];
+        (0..20)
+            .map(|_| roll_to_hit(&mut player, &enemy, Action::AttackStraight(0), Action::Nothing, &mut MockMenu::default()).0)
+            .collect::<Vec<_>>()
+    };
+    let rolls = roll_many();
+    assert_eq!(rolls, roll_many());
+    assert!(rolls.contains(&Action::Nothing) && rolls.contains(&Action::AttackStraight(0)));
+}
+
+/// Tests that enemies take less damage from the kinds they resist and more from the kinds they're weak to, and that the player is told
+#[test]
+fn test_resistances() {
+    let weapon = |damage_type| {
+        let Item::Weapon(weapon) = test_weapon(1, 1) else {unreachable!()};
+        Item::Weapon(Weapon { damage_type, straight_damage: Damage::new(5), ..weapon })
+    };
+
+    let mut player = Player::init(0);
+    player.strength = 0;
+    player.inventory = vec![(weapon(DamageType::Blunt), 1), (weapon(DamageType::Sharp), 1), (weapon(DamageType::Energy), 1)];
+
+    let mut enemy = test_enemy(20, Vec::new());
+    enemy.resistances = &[(DamageType::Blunt, Resistance::Resistant), (DamageType::Sharp, Resistance::Weak)];
+
+    let text = execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::Nothing);
+    assert_eq!(enemy.health, Health::new(17));
+    assert!(text.ends_with("It's not very effective..."));
+
+    let text = execute_actions(&mut player, &mut enemy, Action::AttackStraight(1), Action::Nothing);
+    assert_eq!(enemy.health, Health::new(9));
+    assert!(text.ends_with("It's very effective!"));
+
+    let text = execute_actions(&mut player, &mut enemy, Action::AttackStraight(2), Action::Nothing);
+    assert_eq!(enemy.health, Health::new(4));
+    assert!(text.ends_with("damage."));
+}
+
+/// Tests that a boss enters each phase once their health drops to its threshold, and changes how they fight
+#[test]
+fn test_boss_phases() {
+    const PHASES: &[BossPhase] = &[
+        BossPhase {
+            health_percent: 50,
+            title: "First",
+            content: "",
+            attack_pattern: Some(AttackPattern::Aggressive),
+            heal_percent: 0,
+            bonus_damage: Damage::new(1),
+        },
+        BossPhase {
+            health_percent: 20,
+            title: "Second",
+            content: "",
+            attack_pattern: None,
+            heal_percent: 30,
+            bonus_damage: Damage::new(2),
+        },
+    ];
+
+    let mut menu = MockMenu::default();
+    let mut enemy = test_enemy(10, Vec::new());
+    enemy.phases = PHASES;
+
+    enemy.health = Health::new(6);
+    enemy.advance_phase(&mut menu);
+    assert_eq!(enemy.phase, 0);
+    assert!(menu.get_last_screen().is_none());
+
+    // Dropping past both thresholds at once enters both phases in order
+    enemy.health = Health::new(1);
+    enemy.advance_phase(&mut menu);
+    assert_eq!(enemy.phase, 2);
+    assert_eq!(enemy.health, Health::new(4));
+    assert_eq!(enemy.bonus_damage, Damage::new(3));
+    assert!(matches!(enemy.attack_pattern, AttackPattern::Aggressive));
+    let titles: Vec<_> = menu.get_screens().iter().map(|(title, _)| title.as_str()).collect();
+    assert_eq!(titles, ["First", "Second"]);
+
+    // Phases are only entered once
+    enemy.health = Health::new(1);
+    enemy.advance_phase(&mut menu);
+    assert_eq!(enemy.phase, 2);
+    assert_eq!(enemy.health, Health::new(1));
+}
+
+/// Tests that examining an enemy shows their health, resistances and the phase they are in
+#[test]
+fn test_examine() {
+    const PHASES: &[BossPhase] = &[BossPhase {
+        health_percent: 50,
+        title: "They're furious",
+        content: "",
+        attack_pattern: None,
+        heal_percent: 0,
+        bonus_damage: Damage::new(2),
+    }];
+
+    let mut menu = MockMenu::default();
+    let mut enemy = test_enemy(10, Vec::new());
+    enemy.name = "Guard";
+    enemy.examine(&mut menu);
+    let (title, content) = menu.get_last_screen().unwrap();
+    assert_eq!(title, "You size up the Guard");
+    assert!(content.contains("10/10"));
+    assert!(content.contains("They take normal damage from everything."));
+
+    enemy.resistances = &[(DamageType::Sharp, Resistance::Resistant), (DamageType::Energy, Resistance::Weak)];
+    enemy.phases = PHASES;
+    enemy.health = Health::new(4);
+    enemy.advance_phase(&mut menu);
+    enemy.examine(&mut menu);
+    let (_, content) = menu.get_last_screen().unwrap();
+    assert!(content.contains("4/10"));
+    assert!(content.contains("They resist sharp damage."));
+    assert!(content.contains("They are weak to energy damage."));
+    assert!(content.contains("They're furious"));
+    assert!(content.contains("Their attacks deal 2 extra damage."));
+}
+
+/// Tests that clashing attacks are decided by speed unless actions resolve simultaneously, and that only speed resolution gives counters
+#[test]
+fn test_resolution() {
+    assert_eq!(Resolution::Speed.clash(1, 2), Ordering::Less);
+    assert_eq!(Resolution::Speed.clash(2, 1), Ordering::Greater);
+    assert_eq!(Resolution::Speed.clash(2, 2), Ordering::Equal);
+    assert!(Resolution::Speed.allows_counters());
+
+    assert_eq!(Resolution::Simultaneous.clash(1, 2), Ordering::Equal);
+    assert_eq!(Resolution::Simultaneous.clash(2, 1), Ordering::Equal);
+    assert!(!Resolution::Simultaneous.allows_counters());
+}
+
+/// Tests that the combo tracker spots a completed combo, and that breaking the sequence resets it
+#[test]
+fn test_combo_tracker() {
+    const COMBOS: &[Combo] = &[Combo {
+        name: "Test",
+        steps: &[PatternStep::DodgeLeft, PatternStep::AttackRight],
+        bonus_damage: Damage::new(2),
+    }];
+
+    let mut tracker = ComboTracker::default();
+    assert!(tracker.record(COMBOS, Action::DodgeLeft).is_none());
+    assert_eq!(tracker.record(COMBOS, Action::AttackRight(0)).unwrap().name, "Test");
+
+    // Completing a combo starts the tracker again
+    assert!(tracker.record(COMBOS, Action::AttackRight(0)).is_none());
+
+    // Anything in between breaks the sequence, but the breaking action can start it again
+    assert!(tracker.record(COMBOS, Action::DodgeLeft).is_none());
+    assert!(tracker.record(COMBOS, Action::Block).is_none());
+    assert!(tracker.record(COMBOS, Action::AttackRight(0)).is_none());
+    assert!(tracker.record(COMBOS, Action::DodgeLeft).is_none());
+    assert!(tracker.record(COMBOS, Action::DodgeLeft).is_none());
+    assert!(tracker.record(COMBOS, Action::AttackRight(0)).is_some());
+}
+
+/// Tests that a combo's finisher deals its bonus damage only if the final attack hit
+#[test]
+fn test_land_combo() {
+    let combo = &config::COMBOS[0];
+    let mut player = Player::init(0);
+    player.inventory.clear();
+    player.inventory.push((test_weapon(1, 1), 1));
+
+    let mut enemy = test_enemy(10, Vec::new());
+    assert!(land_combo(&player, &mut enemy, combo, Action::AttackRight(0), false).is_none());
+    assert!(land_combo(&player, &mut enemy, combo, Action::DodgeLeft, true).is_none());
+    assert_eq!(enemy.health, Health::new(10));
+
+    assert!(land_combo(&player, &mut enemy, combo, Action::AttackRight(0), true).unwrap().starts_with(combo.name));
+    assert_eq!(enemy.health, Health::new(10) - combo.bonus_damage);
+}
+
+/// Tests that the threat rating compares how many hits the player and the enemy each need to win
+#[test]
+fn test_rate_threat() {
+    let mut player = Player::init(0);
+    player.strength = 0;
+    player.health = Health::new(10);
+    player.inventory.clear();
+
+    // Neither can hurt the other without a weapon, and an unarmed player can't win against an armed enemy
+    assert_eq!(rate_threat(&player, &test_enemy(10, Vec::new())), Threat::Even);
+    assert_eq!(rate_threat(&player, &test_enemy(10, vec![test_weapon(0, 1)])), Threat::Deadly);
+
+    player.inventory.push((test_weapon(0, 1), 1));
+    assert_eq!(rate_threat(&player, &test_enemy(10, Vec::new())), Threat::Trivial);
+    assert_eq!(rate_threat(&player, &test_enemy(2, vec![test_weapon(0, 1)])), Threat::Trivial);
+    assert_eq!(rate_threat(&player, &test_enemy(10, vec![test_weapon(0, 1)])), Threat::Even);
+    assert_eq!(rate_threat(&player, &test_enemy(40, vec![test_weapon(0, 1)])), Threat::Deadly);
+
+    // Weaknesses make the fight easier
+    let mut enemy = test_enemy(10, vec![test_weapon(0, 1)]);
+    enemy.resistances = &[(DamageType::Blunt, Resistance::Weak)];
+    assert_eq!(rate_threat(&player, &enemy), Threat::Easy);
+}
+
+/// Tests that companions chip in against the enemy, hang back once they're hurt, and are lost if the enemy kills them
+#[test]
+fn test_companions_turn() {
+    let fighter = |health| Fighter { health: Health::new(health), max_health: Health::new(4), damage: Damage::new(1), damage_type: DamageType::Blunt };
+    let mut player = Player::init(0);
+    let mut enemy = test_enemy(100, vec![test_weapon(1, 1)]);
+
+    // Companions who don't fight don't take a turn
+    player.companions = vec![Npc::new("Bystander", &[])];
+    assert_eq!(companions_turn(&mut player, &mut enemy, Action::AttackStraight(0)), None);
+
+    // A cautious companion who's been hurt keeps out of it
+    player.companions = vec![Npc::new("Helper", &[]).with_fighter(fighter(1))];
+    for _ in 0..20 {
+        companions_turn(&mut player, &mut enemy, Action::AttackStraight(0));
+    }
+    assert_eq!(enemy.health, Health::new(100));
+    assert_eq!(player.companions.len(), 1);
+
+    // A healthy one attacks, and can't be hurt unless the enemy attacks
+    player.companions = vec![Npc::new("Helper", &[]).with_fighter(fighter(4))];
+    for _ in 0..20 {
+        companions_turn(&mut player, &mut enemy, Action::Nothing);
+    }
+    assert!(enemy.health < Health::new(100));
+    assert!(matches!(player.companions[0].fighter, Some(f) if f.health == Health::new(4)));
+
+    player.companions[0].fighter = Some(Fighter { max_health: Health::new(1), ..fighter(1) });
+    for _ in 0..50 {
+        companions_turn(&mut player, &mut enemy, Action::AttackStraight(0));
+    }
+    assert!(player.companions.is_empty());
+    assert_eq!(player.fallen_companions, vec!["Helper"]);
+}
+
+/// Tests that the status line lists the timed effects on both sides, and is left out when there aren't any
+#[test]
+fn test_describe_statuses() {
+    const PHASES: &[BossPhase] = &[BossPhase {
+        health_percent: 50,
+        title: "",
+        content: "",
+        attack_pattern: None,
+        heal_percent: 0,
+        bonus_damage: Damage::new(0),
+    }];
+    let mut player = Player::init(0);
+    let mut enemy = test_enemy(10, Vec::new());
+    assert_eq!(describe_statuses(&player, &enemy), None);
+
+    player.bandage_turns = 3;
+    player.dodge_cooldown = 1;
+    assert_eq!(describe_statuses(&player, &enemy).unwrap(), "[You: bandage 3t, no dodge 1t | : -]");
+
+    enemy.name = "Skipper";
+    enemy.phases = PHASES;
+    enemy.phase = 1;
+    player.bandage_turns = 0;
+    player.dodge_cooldown = 0;
+    assert_eq!(describe_statuses(&player, &enemy).unwrap(), "[You: - | Skipper: phase 1/1]");
+}
@@ -1,13 +1,213 @@
 //! Configuration constants for the game
 
-use crate::{combat::Health, rooms::Room};
+use crate::{combat::{Combo, Damage, Health, HealthDisplay, PatternStep, Resolution}, difficulty::Difficulty, items::PickUpOrder, lang::Language, menu::Theme, npc::CompanionAi, rooms::Room};
 
 /// How much health the player should start with
 pub const PLAYER_START_HEALTH: Health = Health::new(10);
 /// What the player's max health should be at the start of the game
 pub const PLAYER_START_MAX_HEALTH: Health = Health::new(10);
+/// How much health the player recovers each turn they spend outside of combat
+pub const REGEN_PER_TURN: Damage = Damage::new(1);
+/// How hungry the player is at the start of each loop
+pub const PLAYER_START_HUNGER: usize = 8;
+/// How hungry the player has to get before they are warned that they need to eat
+pub const HUNGER_WARNING: usize = 15;
+/// How hungry the player has to get before they start starving. Starving players lose health each turn and don't regenerate.
+pub const HUNGER_STARVING: usize = 20;
+/// How much health the player loses each turn while they're starving
+pub const STARVATION_DAMAGE: Damage = Damage::new(1);
+/// How much oxygen the player can have. They start each loop with this much, and can refill it in the engine room.
+pub const PLAYER_MAX_OXYGEN: usize = 20;
+/// How little oxygen the player has to have left before they are warned that it's running out
+pub const OXYGEN_WARNING: usize = 5;
+/// How much health the player loses each turn once they've run out of oxygen
+pub const SUFFOCATION_DAMAGE: Damage = Damage::new(2);
+/// Resting stops once this few turns are left before the loop resets or the ship self-destructs, so the player has time to act
+pub const REST_STOP_TURNS_LEFT: usize = 5;
+/// How much health a [bandage][crate::items::Item::Bandage] heals each turn while it is on
+pub const BANDAGE_HEAL_PER_TURN: Damage = Damage::new(2);
+/// How many turns a [bandage][crate::items::Item::Bandage] keeps healing for after it is put on
+pub const BANDAGE_DURATION_TURNS: usize = 5;
+/// How many turns a shower in the [`WashRoom`][crate::rooms::Room::WashRoom] leaves the [player][crate::player::Player] feeling fresh for
+pub const FRESH_DURATION_TURNS: usize = 10;
+/// How much extra [defense][crate::player::Player::get_defense] the [player][crate::player::Player] has while they feel fresh
+pub const FRESH_DEFENSE_BONUS: usize = 1;
+/// How much damage an [explosive][crate::items::Item::Explosive] deals to the enemy it is thrown at
+pub const EXPLOSIVE_DAMAGE: Damage = Damage::new(8);
+/// How much damage the player takes from their own [explosive][crate::items::Item::Explosive] if they set it off while an enemy is attacking them up close
+pub const EXPLOSIVE_SELF_DAMAGE: Damage = Damage::new(3);
+/// How much durability a [repair kit][crate::items::Item::RepairKit] restores to a weapon, up to its [max][crate::items::Weapon::max_durability]
+pub const REPAIR_KIT_AMOUNT: usize = 10;
+/// How many positions each pin of a lock can be pushed to when [picking it][crate::items::Item::Lockpick]
+pub const LOCK_PIN_POSITIONS: usize = 5;
+/// How many wrong guesses the player can make for each pin of a lock, the last of which sets off its tamper alarm
+pub const LOCKPICK_MISTAKES_PER_PIN: usize = 2;
+/// The percentage chance of the [lockpick][crate::items::Item::Lockpick] snapping with each wrong guess
+pub const LOCKPICK_BREAK_PERCENT: usize = 5;
+/// The percentage of its usual health which [spoiled food][crate::items::Food::is_spoiled] heals for
+pub const SPOILED_FOOD_HEAL_PERCENT: usize = 50;
+/// How much stamina the player can have. They start each loop with this much.
+pub const PLAYER_MAX_STAMINA: usize = 6;
+/// How much stamina the player recovers each turn, in or out of battle
+pub const STAMINA_REGEN_PER_TURN: usize = 1;
+/// How much stamina it costs the player to dodge
+pub const DODGE_STAMINA_COST: usize = 2;
+/// How many turns of battle the player has to wait after dodging before they can dodge again
+pub const DODGE_COOLDOWN_TURNS: usize = 1;
+/// How much stamina it costs the player to block
+pub const BLOCK_STAMINA_COST: usize = 3;
+/// How much stamina it costs the player to flee from a battle
+pub const FLEE_STAMINA_COST: usize = 2;
+/// The percentage chance each turn that an alert enemy follows the player through an open door into the room they are in
+pub const PURSUIT_CHANCE_PERCENT: usize = 50;
+/// The percentage chance that the player sneaks past an enemy at level 1, before the enemy's [awareness][crate::combat::Enemy::awareness] is taken off
+pub const SNEAK_BASE_CHANCE_PERCENT: usize = 60;
+/// How many percentage points each level the player gains adds to their chance of sneaking past an enemy
+pub const SNEAK_CHANCE_PER_LEVEL_PERCENT: usize = 5;
+/// The percentage of an attack's damage which the player still takes when they block it
+pub const BLOCK_DAMAGE_PERCENT: usize = 50;
+/// The percentage chance that the player gets to counter-attack after dodging away from an attack
+pub const COUNTER_CHANCE_PERCENT: usize = 50;
+/// Whether [examining][crate::combat::Action::Examine] an enemy in battle uses up the player's turn
+pub const EXAMINE_TAKES_TURN: bool = false;
+/// How the player's and enemy's actions are resolved against each other each turn in battle
+pub const COMBAT_RESOLUTION: Resolution = Resolution::Speed;
+/// How [companions][crate::player::Player::companions] decide whether to join in each turn of a battle
+pub const COMPANION_AI: CompanionAi = CompanionAi::Cautious;
+/// The percentage of their max health a [cautious][CompanionAi::Cautious] companion has to drop to before they stop fighting
+pub const COMPANION_RETREAT_HEALTH_PERCENT: usize = 50;
+/// The percentage chance that a companion's attack hits the enemy
+pub const COMPANION_HIT_CHANCE_PERCENT: usize = 75;
+/// The percentage chance that an enemy's attack catches a companion who is fighting, as well as going for the player
+pub const COMPANION_TARGETED_CHANCE_PERCENT: usize = 30;
+/// How much extra damage a counter-attack deals on top of a normal straight attack
+pub const COUNTER_BONUS_DAMAGE: Damage = Damage::new(2);
+/// The combos the player can pull off in battle by taking the right actions on consecutive turns
+pub const COMBOS: &[Combo] = &[
+    // Slipping one way opens up the enemy's other side
+    Combo {
+        name: "Sidestep Strike",
+        steps: &[PatternStep::DodgeLeft, PatternStep::AttackRight],
+        bonus_damage: Damage::new(2),
+    },
+    Combo {
+        name: "Sidestep Strike",
+        steps: &[PatternStep::DodgeRight, PatternStep::AttackLeft],
+        bonus_damage: Damage::new(2),
+    },
+    // Three hits in a row don't give the enemy time to recover
+    Combo {
+        name: "Flurry",
+        steps: &[PatternStep::AttackStraight, PatternStep::AttackStraight, PatternStep::AttackStraight],
+        bonus_damage: Damage::new(3),
+    },
+];
+/// The percentage chance that a piece of loot taken from a defeated enemy is [uncommon][crate::items::Rarity::Uncommon]
+pub const UNCOMMON_LOOT_CHANCE_PERCENT: usize = 20;
+/// The percentage chance that a piece of loot taken from a defeated enemy is [rare][crate::items::Rarity::Rare]
+pub const RARE_LOOT_CHANCE_PERCENT: usize = 5;
+/// The reach the player has when they have no weapon they can use
+pub const UNARMED_REACH: usize = 0;
+/// The percentage chance, for each point of reach the enemy's weapon has over the player's, that the player's dodge is caught anyway
+pub const REACH_DODGE_PENALTY_PERCENT: usize = 10;
+/// Whether health is shown as HP or as a percentage of max health, until it is changed in the [settings][crate::settings]
+pub const HEALTH_DISPLAY: HealthDisplay = HealthDisplay::Absolute;
+/// How many characters wide the enemy's health bar is in battle
+pub const HEALTH_BAR_WIDTH: usize = 10;
+/// The maximum weight of items the player can carry, in kg
+pub const PLAYER_MAX_WEIGHT: usize = 12;
+/// The order [taking everything][crate::player::Player::take_all] in a room picks items up in, so the best ones are taken if they don't all fit
+pub const TAKE_ALL_ORDER: PickUpOrder = PickUpOrder::Lightest;
+/// How many people fit in the [escape pod][crate::rooms::Room::EscapePod], including the player.
+/// If the player has more [companions][crate::player::Player::companions] than there are seats left, they have to choose who comes with them.
+pub const ESCAPE_POD_SEATS: usize = 2;
+/// How strong the player is at the start of the game. See [`attack_damage`][crate::combat::attack_damage] for how this affects damage.
+pub const PLAYER_START_STRENGTH: usize = 2;
+/// How many points of strength it takes to deal 1 extra damage with an attack
+pub const STRENGTH_PER_BONUS_DAMAGE: usize = 2;
+/// The total XP the player needs to reach each level, starting from level 2. The player starts at level 1, and can't level up past the end of this list.
+pub const LEVEL_XP_THRESHOLDS: [usize; 4] = [5, 13, 25, 40];
+/// How much the player's max health increases by when they level up
+pub const LEVEL_UP_MAX_HEALTH: Damage = Damage::new(2);
+/// How much the player's strength increases by when they level up
+pub const LEVEL_UP_STRENGTH: usize = 1;
+/// How much extra health enemies have for each New Game Plus cycle
+pub const NEW_GAME_PLUS_ENEMY_HEALTH: Damage = Damage::new(3);
+/// How much extra XP enemies give for each New Game Plus cycle
+pub const NEW_GAME_PLUS_ENEMY_XP: usize = 2;
+/// How much extra health enemies have for each level the player has gained. See [`Enemy::scaled`][crate::combat::Enemy::scaled].
+pub const ENEMY_HEALTH_PER_PLAYER_LEVEL: usize = 1;
+/// How much extra XP enemies give for each level the player has gained
+pub const ENEMY_XP_PER_PLAYER_LEVEL: usize = 1;
+/// How many levels the player has to gain for enemies to deal 1 extra damage with their attacks
+pub const PLAYER_LEVELS_PER_ENEMY_BONUS_DAMAGE: usize = 2;
+/// How much gold the player should start with
+pub const PLAYER_START_GOLD: u32 = 0;
 /// Which room the player should start in
 pub const STARTING_ROOM: Room = Room::Cells;
 
+/// Where the autosave is written to, relative to the directory the game is run from
+pub const AUTOSAVE_PATH: &str = "autosave.txt";
+/// Where to load the ship's layout from, relative to the directory the game is run from. If there is no file here, the built-in layout is used.
+pub const LAYOUT_PATH: &str = "ship.json";
+/// Where the player's [settings][crate::settings::Settings] are kept between runs, relative to the directory the game is run from
+pub const SETTINGS_PATH: &str = "settings.json";
+/// Where the fastest time to escape is kept between runs, relative to the directory the game is run from
+pub const BEST_TIME_PATH: &str = "best_time.txt";
+
 /// The maximum number of turns the player can take before the loop resets
 pub const MAX_TURNS: usize = 30;
+/// How forgiving the game is, until it is changed in the [settings][crate::settings]
+pub const DIFFICULTY: Difficulty = Difficulty::Normal;
+/// Whether enemies get tougher or weaker depending on how the player is doing, on top of the fixed [`DIFFICULTY`], until it is changed in the [settings][crate::settings]. See [`DynamicBalance`][crate::balance::DynamicBalance].
+pub const DYNAMIC_BALANCE: bool = false;
+/// The lowest percentage of their health [dynamic balance][DYNAMIC_BALANCE] can take enemies down to, however often the player dies
+pub const DYNAMIC_BALANCE_MIN_PERCENT: usize = 70;
+/// The highest percentage of their health [dynamic balance][DYNAMIC_BALANCE] can take enemies up to, however easily the player wins
+pub const DYNAMIC_BALANCE_MAX_PERCENT: usize = 130;
+/// How many percentage points of health enemies lose under [dynamic balance][DYNAMIC_BALANCE] each time the player dies
+pub const DYNAMIC_BALANCE_DEATH_STEP_PERCENT: usize = 10;
+/// How many percentage points of health enemies gain under [dynamic balance][DYNAMIC_BALANCE] each time the player steamrolls a fight
+pub const DYNAMIC_BALANCE_STEAMROLL_STEP_PERCENT: usize = 5;
+/// A fight counts as steamrolled if the player won it while losing less than this percentage of their max health
+pub const DYNAMIC_BALANCE_STEAMROLL_HEALTH_PERCENT: usize = 20;
+/// Whether the ship is set to self-destruct, giving the player [fewer turns][Difficulty::get_self_destruct_turns] to escape than [`MAX_TURNS`]
+pub const SELF_DESTRUCT_MODE: bool = false;
+/// How many turns before the self-destruct the player is warned that time is running out
+pub const SELF_DESTRUCT_WARNING_TURNS: usize = 10;
+/// How many turns before the self-destruct the player is warned on every turn
+pub const SELF_DESTRUCT_CRITICAL_TURNS: usize = 3;
+/// The loudest the ship's [alarm][crate::alarm::Alarm] can get
+pub const ALARM_MAX_LEVEL: usize = 3;
+/// How many percentage points each level of the alarm adds to the chance of a random encounter
+pub const ALARM_ENCOUNTER_CHANCE_PERCENT: usize = 10;
+/// How many turns the holo-screen in the [`MessHall`][crate::rooms::Room::MessHall] keeps the crew [distracted][crate::rooms::Distraction] for once it is turned up
+pub const DISTRACTION_DURATION_TURNS: usize = 6;
+/// How many percentage points a [distraction][crate::rooms::Distraction] adds to the player's chance of sneaking past an enemy
+pub const DISTRACTION_SNEAK_BONUS_PERCENT: usize = 25;
+/// How many percentage points the dark adds to the player's chance of sneaking past an enemy while the [ship's power][crate::rooms::RoomGraph::power_on] is off
+pub const DARK_SNEAK_BONUS_PERCENT: usize = 15;
+/// How many turns a battle can last before the noise raises the alarm
+pub const ALARM_LOUD_BATTLE_TURNS: usize = 4;
+
+/// The maximum number of events the journal remembers. Once it's full, the oldest events are forgotten.
+pub const JOURNAL_MAX_ENTRIES: usize = 50;
+
+/// The maximum number of options to show at once in the line-based menu, before splitting the list into pages
+pub const OPTIONS_PER_PAGE: usize = 9;
+/// Whether the terminal menu reveals the text of each screen a character at a time, until it is changed in the [settings][crate::settings]. Pressing a key skips to the end of the text.
+pub const TYPEWRITER: bool = true;
+/// How many characters the typewriter effect reveals per second
+pub const TYPEWRITER_CHARS_PER_SECOND: u64 = 50;
+/// The width to wrap text to in the line-based menu, if the width of the terminal can't be detected
+pub const FALLBACK_TERMINAL_WIDTH: usize = 80;
+/// Whether to show coloured text, until it is changed in the [settings][crate::settings]. This should be disabled for terminals which don't support ANSI colour codes.
+pub const COLORS_ENABLED: bool = true;
+/// Whether to show everything as plain lines of text for screen readers, if it isn't turned on with the `--accessible` command line argument
+pub const ACCESSIBLE_MODE: bool = false;
+/// Whether to offer developer actions like teleporting, if they aren't turned on with the `--dev` command line argument. This should be off for normal play.
+pub const DEV_MODE: bool = false;
+/// The language to show text in, if one isn't chosen with the `--lang <code>` command line argument
+pub const LANGUAGE: Language = Language::English;
+/// The colour theme for the menus, if one isn't chosen with the `--theme <name>` command line argument
+pub const THEME: Theme = Theme::Default;
@@ -0,0 +1,22 @@
+//! Developer mode, which adds [`Player`][crate::player::Player] actions for testing and debugging that would spoil normal play,
+//! such as teleporting to any room. It is turned on at startup with the `--dev` command line argument, and defaults to [`config::DEV_MODE`].
+
+use std::sync::OnceLock;
+
+use crate::config;
+
+/// Whether developer mode was turned on at startup. If it hasn't been set, [`config::DEV_MODE`] is used.
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Turns on developer mode if the `--dev` command line argument is given.
+/// This should be called once, before the game starts.
+pub fn init() {
+    if std::env::args().any(|arg| arg == "--dev") {
+        let _ = ENABLED.set(true);
+    }
+}
+
+/// Gets whether developer mode is on
+pub fn is_enabled() -> bool {
+    *ENABLED.get().unwrap_or(&config::DEV_MODE)
+}
@@ -0,0 +1,83 @@
+//! Contains the [`Difficulty`] type, which decides how forgiving the game is.
+//! This defaults to [`config::DIFFICULTY`][crate::config::DIFFICULTY], and can be changed in the [settings][crate::settings].
+
+use serde::{Deserialize, Serialize};
+
+/// How forgiving the game is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// For players who want to explore at their own pace. There's no self-destruct countdown.
+    Relaxed,
+    /// The game as it was designed to be played
+    Normal,
+    /// For players who know the ship well
+    Hard,
+}
+
+impl Difficulty {
+    /// Gets the name of the difficulty, as it is shown in the [settings menu][crate::settings::show]
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Relaxed => "Relaxed",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    /// Gets the next difficulty up, going back round to the easiest after the hardest, for cycling through them in the [settings menu][crate::settings::show]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Relaxed => Self::Normal,
+            Self::Normal => Self::Hard,
+            Self::Hard => Self::Relaxed,
+        }
+    }
+
+    /// Gets how many turns the ship has left before it self-destructs in [self-destruct mode][crate::config::SELF_DESTRUCT_MODE],
+    /// or [`None`] if there is no countdown at this difficulty
+    pub const fn get_self_destruct_turns(self) -> Option<usize> {
+        match self {
+            Self::Relaxed => None,
+            Self::Normal => Some(27),
+            Self::Hard => Some(20),
+        }
+    }
+
+    /// Checks whether the [`Player`][crate::player::Player] drops everything they're carrying when they die,
+    /// leaving it as [`Remains`][crate::player::Remains] for the next time round the loop to pick back up
+    pub const fn drops_inventory_on_death(self) -> bool {
+        matches!(self, Self::Hard)
+    }
+
+    /// Gets the percentage chance that a [wandering enemy][crate::map::patrolling_guard] ambushes the player each time they move into a room
+    pub const fn get_encounter_chance_percent(self) -> usize {
+        match self {
+            Self::Relaxed => 5,
+            Self::Normal => 10,
+            Self::Hard => 20,
+        }
+    }
+
+    /// Gets the percentage of an [`Enemy`][crate::combat::Enemy]'s base health it has at this difficulty. See [`Enemy::scaled`][crate::combat::Enemy::scaled].
+    pub const fn get_enemy_health_percent(self) -> usize {
+        match self {
+            Self::Relaxed => 75,
+            Self::Normal => 100,
+            Self::Hard => 150,
+        }
+    }
+
+    /// Gets how much extra damage [enemies][crate::combat::Enemy] deal with each attack at this difficulty
+    pub const fn get_enemy_bonus_damage(self) -> usize {
+        match self {
+            Self::Relaxed | Self::Normal => 0,
+            Self::Hard => 1,
+        }
+    }
+
+    /// Gets the percentage of an [`Enemy`][crate::combat::Enemy]'s base XP reward it gives at this difficulty.
+    /// Tougher enemies are worth more, so this rises with their health.
+    pub const fn get_enemy_xp_percent(self) -> usize {
+        self.get_enemy_health_percent()
+    }
+}
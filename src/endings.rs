@@ -0,0 +1,51 @@
+//! Contains the [`Ending`] type, which decides how the game ends when the [`Player`] escapes
+
+use crate::player::Player;
+
+/// One of the ways the game can end, depending on how the [`Player`] escaped.
+/// If more than one applies, the first in this order is chosen:
+/// 1. [`WithCompanion`][Ending::WithCompanion]: the [`Player`] has a [companion][Player::companions] with them
+/// 2. [`CaptainDefeated`][Ending::CaptainDefeated]: the [`Player`] [defeated][Player::defeated_enemies] the Skipper, who is fought as a [boss][crate::combat::BossPhase]
+/// 3. [`Alone`][Ending::Alone]: otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    /// The [`Player`] escaped alone
+    Alone,
+    /// The [`Player`] escaped with a rescued [`Npc`][crate::npc::Npc]
+    WithCompanion,
+    /// The [`Player`] escaped after defeating the ship's captain
+    CaptainDefeated,
+}
+
+impl Ending {
+    /// Gets the [`Ending`] the [`Player`] has earned
+    pub fn from_player(player: &Player) -> Self {
+        if !player.companions.is_empty() {
+            Self::WithCompanion
+        } else if player.defeated_enemies.contains(&"Skipper") {
+            Self::CaptainDefeated
+        } else {
+            Self::Alone
+        }
+    }
+
+    /// Gets the title of the win screen for this [`Ending`]
+    pub const fn get_title(self) -> &'static str {
+        match self {
+            Self::Alone => "Freedom at long last",
+            Self::WithCompanion => "Two seats, two survivors",
+            Self::CaptainDefeated => "The captain goes down with the ship",
+        }
+    }
+
+    /// Gets the text of the win screen for this [`Ending`]
+    pub const fn get_description(self) -> &'static str {
+        match self {
+            Self::Alone => "Or maybe not so long - it's only been a few minutes, after all. You buckle in for the long ride and allow yourself to relax, finally.",
+            Self::WithCompanion => "The technician from the bunks sits beside you, still shaking. They thank you over and over. \
+You'll have to explain them to the Republic when you get home, but for now you're just glad you didn't leave alone.",
+            Self::CaptainDefeated => "Without its skipper, the ship drifts on without a course. By the time anyone notices, you'll be long gone. \
+You buckle in for the long ride and allow yourself to relax, finally.",
+        }
+    }
+}
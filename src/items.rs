@@ -1,9 +1,93 @@
 //! Functionality related to items
 
+use std::cmp::Ordering;
+use std::fmt::Write;
+
 use crate::combat::Damage;
+use crate::config;
+use crate::menu::Color;
+use crate::rng::Rng;
+
+/// How rare a [`Food`], [`Weapon`] or piece of [`Armor`] is. Rarer items are better than the common version, and worth more.
+/// An item is made rarer with [`Item::with_rarity`], which scales its stats from the common version:
+///
+/// | Rarity   | Food healing, weapon damage and armor defense | Value |
+/// |----------|-----------------------------------------------|-------|
+/// | Common   | 100%                                          | 100%  |
+/// | Uncommon | 125%                                          | 150%  |
+/// | Rare     | 150%                                          | 200%  |
+///
+/// Everything in the ship starts out common. The loot the [player][crate::player::Player] takes from defeated [enemies][crate::combat::Enemy]
+/// can [roll][Rarity::roll] a higher rarity, with chances set by [`UNCOMMON_LOOT_CHANCE_PERCENT`][config::UNCOMMON_LOOT_CHANCE_PERCENT]
+/// and [`RARE_LOOT_CHANCE_PERCENT`][config::RARE_LOOT_CHANCE_PERCENT].
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    /// The ordinary version of an item
+    #[default]
+    Common,
+    /// A better-made version of an item
+    Uncommon,
+    /// The best version of an item to be found on the ship
+    Rare,
+}
+
+impl Rarity {
+    /// Gets the name of the rarity
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Common => "Common",
+            Self::Uncommon => "Uncommon",
+            Self::Rare => "Rare",
+        }
+    }
+
+    /// Gets the [`Color`] to show items of this rarity in, or [`None`] for common items
+    pub const fn get_color(self) -> Option<Color> {
+        match self {
+            Self::Common => None,
+            Self::Uncommon => Some(Color::Green),
+            Self::Rare => Some(Color::Yellow),
+        }
+    }
+
+    /// Gets the percentage of the common version's healing, damage or defense that an item of this rarity has
+    pub const fn get_stat_percent(self) -> usize {
+        match self {
+            Self::Common => 100,
+            Self::Uncommon => 125,
+            Self::Rare => 150,
+        }
+    }
+
+    /// Gets the percentage of the common version's value that an item of this rarity is worth
+    pub const fn get_value_percent(self) -> u32 {
+        match self {
+            Self::Common => 100,
+            Self::Uncommon => 150,
+            Self::Rare => 200,
+        }
+    }
+
+    /// Scales a stat of the common version of an item to this rarity, rounding to the nearest whole number
+    const fn scale_stat(self, stat: Damage) -> Damage {
+        Damage::new((stat.as_usize() * self.get_stat_percent() + 50) / 100)
+    }
+
+    /// Picks a rarity for a piece of loot, using the given [`Rng`]
+    pub fn roll(rng: &mut Rng) -> Self {
+        let roll = rng.below(100);
+        if roll < config::RARE_LOOT_CHANCE_PERCENT {
+            Self::Rare
+        } else if roll < config::RARE_LOOT_CHANCE_PERCENT + config::UNCOMMON_LOOT_CHANCE_PERCENT {
+            Self::Uncommon
+        } else {
+            Self::Common
+        }
+    }
+}
 
 /// A food item which heals the player when used
-#[derive(Debug, Hash)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub struct Food {
     /// The name of the food
     pub name: &'static str,
@@ -11,33 +95,213 @@ pub struct Food {
     pub description: &'static str,
     /// How much health the player or an enemy gains by eating the food
     pub heals_for: Damage,
+    /// How much eating the food reduces the [player's hunger][crate::player::Player::hunger] by
+    pub fills_for: usize,
+    /// How heavy the food is, in kg
+    pub weight: usize,
+    /// How much gold the food is worth in a [`Shop`][crate::shop::Shop]
+    pub value: u32,
+    /// How many more turns the food can be left before it spoils. Spoiled food doesn't heal as much.
+    pub turns_until_spoiled: usize,
+    /// How rare the food is. See [`Rarity`] for how this affects its stats.
+    pub rarity: Rarity,
+}
+
+impl Food {
+    /// Gets whether the food has spoiled
+    pub const fn is_spoiled(&self) -> bool {
+        self.turns_until_spoiled == 0
+    }
+
+    /// Gets how much health eating the food heals for, taking into account whether it has spoiled
+    pub const fn get_heals_for(&self) -> Damage {
+        if self.is_spoiled() {
+            Damage::new(self.heals_for.as_usize() * config::SPOILED_FOOD_HEAL_PERCENT / 100)
+        } else {
+            self.heals_for
+        }
+    }
 }
 
 /// A weapon which can be used in a battle
-#[derive(Debug, Hash)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub struct Weapon {
     /// The name of the weapon
     pub name: &'static str,
     /// A description of the weapon
     pub description: &'static str,
 
+    /// The kind of damage the weapon deals, which some [enemies][crate::combat::Enemy] [resist or are weak to][crate::combat::Resistance]
+    pub damage_type: DamageType,
     /// How much damage the weapon deals if it hits an opponent who didn't dodge
     pub straight_damage: Damage,
     /// How much damage the weapon deals if it hits an opponent who dodged
     pub dodge_damage: Damage,
     /// The weapon's speed. A lower speed means the weapon will act faster.
     pub speed: usize,
+    /// How much damage the weapon deals if it is [thrown][crate::combat::Action::Throw] and hits an opponent.
+    /// Thrown weapons land on the floor, so they can't be used again until the battle is over.
+    pub throw_damage: Damage,
+    /// Whether the weapon needs both hands. A two-handed weapon can't be [equipped][crate::player::Player::equipped_weapon] or swung
+    /// while something is [equipped in the off hand][ArmorSlot::OffHand], and nothing can go in the off hand while one is equipped.
+    pub two_handed: bool,
+    /// The percentage chance that an attack with the weapon connects. The [player's][crate::player::Player] chance is lowered by the [enemy's evasion][crate::combat::Enemy::evasion],
+    /// and an [enemy's][crate::combat::Enemy] is scaled by [their own accuracy][crate::combat::Enemy::accuracy]. See [`roll_to_hit`][crate::combat::roll_to_hit].
+    pub accuracy: usize,
+    /// How far the weapon reaches. An opponent whose weapon has a shorter reach may fail to [dodge][crate::combat::Action::DodgeLeft] out of the way of it -
+    /// see [`REACH_DODGE_PENALTY_PERCENT`][crate::config::REACH_DODGE_PENALTY_PERCENT].
+    pub reach: usize,
+    /// How many more attacks the [player][crate::player::Player] can make with the weapon before it breaks and is lost.
+    /// This can be restored with a [repair kit][Item::RepairKit].
+    pub durability: usize,
+    /// The durability the weapon has when it's new, which a [repair kit][Item::RepairKit] can't restore it past
+    pub max_durability: usize,
+    /// How heavy the weapon is, in kg
+    pub weight: usize,
+    /// How much gold the weapon is worth in a [`Shop`][crate::shop::Shop]
+    pub value: u32,
+    /// How the weapon is used to attack, which decides how its attacks are described in battle.
+    /// If this is [`None`], they are described in general terms.
+    pub attack_style: Option<AttackStyle>,
+    /// How rare the weapon is. See [`Rarity`] for how this affects its stats.
+    pub rarity: Rarity,
+}
+
+/// The kind of damage a [`Weapon`] deals. An [`Enemy`][crate::combat::Enemy] may [resist or be weak to][crate::combat::Resistance] some kinds.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    /// Crushing damage, from things like wrenches and clubs
+    Blunt,
+    /// Cutting and piercing damage, from things like knives and darts
+    Sharp,
+    /// Burning and shocking damage, from things like blasters and tasers
+    Energy,
+}
+
+impl DamageType {
+    /// Gets the name of the damage type, to show after an amount of damage
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Blunt => "blunt",
+            Self::Sharp => "sharp",
+            Self::Energy => "energy",
+        }
+    }
+}
+
+/// How a [`Weapon`] is used to attack, which gives its attacks their own descriptions in battle
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum AttackStyle {
+    /// Fired from a distance, like a blaster
+    Shoot,
+    /// Drawn across the opponent, like a blade
+    Slash,
+    /// Thrust into the opponent, like a knife
+    Stab,
+    /// Swung into the opponent, like a club
+    Swing,
+}
+
+/// Which way an attack with a [`Weapon`] is made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackDirection {
+    /// An attack to the left, which hits an opponent who dodges left
+    Left,
+    /// An attack to the right, which hits an opponent who dodges right
+    Right,
+    /// An attack straight ahead, which hits an opponent who doesn't dodge
+    Straight,
+}
+
+impl AttackStyle {
+    /// Gets the [`lang`][crate::lang] keys describing attacks to the left, to the right and straight ahead in the given style,
+    /// by the [player][crate::player::Player] or otherwise by an [enemy][crate::combat::Enemy]. If there is no style, the general descriptions are used.
+    const fn get_keys(style: Option<Self>, by_player: bool) -> [&'static str; 3] {
+        match (style, by_player) {
+            (None, true) => ["combat.player.attack_left", "combat.player.attack_right", "combat.player.attack_straight"],
+            (None, false) => ["combat.enemy.attack_left", "combat.enemy.attack_right", "combat.enemy.attack_straight"],
+            (Some(Self::Shoot), true) => ["combat.player.shoot_left", "combat.player.shoot_right", "combat.player.shoot_straight"],
+            (Some(Self::Shoot), false) => ["combat.enemy.shoot_left", "combat.enemy.shoot_right", "combat.enemy.shoot_straight"],
+            (Some(Self::Slash), true) => ["combat.player.slash_left", "combat.player.slash_right", "combat.player.slash_straight"],
+            (Some(Self::Slash), false) => ["combat.enemy.slash_left", "combat.enemy.slash_right", "combat.enemy.slash_straight"],
+            (Some(Self::Stab), true) => ["combat.player.stab_left", "combat.player.stab_right", "combat.player.stab_straight"],
+            (Some(Self::Stab), false) => ["combat.enemy.stab_left", "combat.enemy.stab_right", "combat.enemy.stab_straight"],
+            (Some(Self::Swing), true) => ["combat.player.swing_left", "combat.player.swing_right", "combat.player.swing_straight"],
+            (Some(Self::Swing), false) => ["combat.enemy.swing_left", "combat.enemy.swing_right", "combat.enemy.swing_straight"],
+        }
+    }
+}
+
+impl Weapon {
+    /// Gets the [`lang`][crate::lang] key describing an attack with the weapon in the given direction, using its [attack style][Weapon::attack_style].
+    /// The [player's][crate::player::Player] text takes the weapon's name, and an [enemy's][crate::combat::Enemy] takes their name and then the weapon's.
+    pub const fn get_attack_key(&self, direction: AttackDirection, by_player: bool) -> &'static str {
+        AttackStyle::get_keys(self.attack_style, by_player)[direction as usize]
+    }
+
+    /// Gets whether the weapon has been worn down, so that a [repair kit][Item::RepairKit] can be used on it
+    pub const fn is_damaged(&self) -> bool {
+        self.durability < self.max_durability
+    }
+}
+
+/// Armor which reduces the damage the [player][crate::player::Player] takes in battle while it is [equipped][crate::player::Player::equipped_armor]
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Armor {
+    /// The name of the armor
+    pub name: &'static str,
+    /// A description of the armor
+    pub description: &'static str,
+    /// How much less damage each hit deals to the wearer
+    pub defense: Damage,
+    /// Which slot the armor is equipped in
+    pub slot: ArmorSlot,
+    /// How heavy the armor is, in kg
+    pub weight: usize,
+    /// How much gold the armor is worth in a [`Shop`][crate::shop::Shop]
+    pub value: u32,
+    /// How rare the armor is. See [`Rarity`] for how this affects its stats.
+    pub rarity: Rarity,
+}
+
+/// Where a piece of [`Armor`] is equipped. The [`Player`][crate::player::Player] can have one piece of armor in each slot.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorSlot {
+    /// Worn on the body, in the [`equipped_armor`][crate::player::Player::equipped_armor] slot
+    Body,
+    /// Held in the off hand, like a shield, in the [`equipped_off_hand`][crate::player::Player::equipped_off_hand] slot.
+    /// The off hand can't be used while a [two-handed][Weapon::two_handed] weapon is equipped.
+    OffHand,
 }
 
 /// An item which can be stored in the [player][crate::player::Player]'s or an [enemy][crate::combat::Enemy]'s inventory
-#[derive(Debug, Hash)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub enum Item {
     /// A food item
     Food(Food),
     /// A weapon
     Weapon(Weapon),
+    /// A piece of armor
+    Armor(Armor),
+    /// A pile of gold, which is added to the [player's gold][crate::player::Player::gold] instead of their inventory when picked up
+    Currency(u32),
     /// The maps which are needed to fly the escape pod
     Maps,
+    /// A bandage, which heals the [player][crate::player::Player] by [`BANDAGE_HEAL_PER_TURN`][config::BANDAGE_HEAL_PER_TURN]
+    /// each turn for [`BANDAGE_DURATION_TURNS`][config::BANDAGE_DURATION_TURNS] turns after it is put on, in or out of battle
+    Bandage,
+    /// An explosive, which can be set off during a battle to deal [`EXPLOSIVE_DAMAGE`][config::EXPLOSIVE_DAMAGE] to the enemy it is thrown at.
+    /// It is used up when it goes off, and can hurt the [player][crate::player::Player] too if an enemy is up close.
+    Explosive,
+    /// A repair kit, which restores [`REPAIR_KIT_AMOUNT`][config::REPAIR_KIT_AMOUNT] of a [damaged][Weapon::is_damaged] weapon's durability
+    RepairKit,
+    /// The ship's deck plans, which mark every room as [known][crate::rooms::RoomState::known] when picked up so that they show up on the [map][crate::rooms::RoomGraph::render_map].
+    /// Not to be confused with the galactic [`Maps`][Item::Maps] needed to escape.
+    ShipMap,
+    /// A fire extinguisher, which is used up putting out the [fire in the kitchen][crate::map::RoomAction::KitchenPutOutFire]
+    FireExtinguisher,
+    /// A set of lock picks, which can be used to [pick the lock][crate::rooms::RoomTransition::lock_pins] of a locked door instead of finding its key
+    Lockpick,
     /// The keys to the escape pod, found by [searching the engine room][crate::map::RoomAction::EngineRoomTakeKeys]
     EscapePodKeys,
 
@@ -56,6 +320,14 @@ impl Item {
         match self {
             Self::Food(f) => f.name,
             Self::Weapon(w) => w.name,
+            Self::Armor(a) => a.name,
+            Self::Currency(_) => "Gold",
+            Self::Bandage => "Bandage",
+            Self::Explosive => "Plasma Grenade",
+            Self::RepairKit => "Repair Kit",
+            Self::ShipMap => "Deck Plans",
+            Self::FireExtinguisher => "Fire Extinguisher",
+            Self::Lockpick => "Lockpick",
             Self::Maps => "Galactic Maps 2168 Edition",
             Self::EscapePodKeys => "Escape Pod Keys",
             Self::Dust => "A thin layer of dust",
@@ -69,6 +341,14 @@ impl Item {
         match self {
             Self::Food(f) => f.description,
             Self::Weapon(w) => w.description,
+            Self::Armor(a) => a.description,
+            Self::Currency(_) => "A handful of gold chips. Nobody uses physical money any more, except for the vending machines on old ships like this one.",
+            Self::Bandage => "A roll of self-adhesive gauze from a first aid kit. It won't patch you up straight away like food, but it'll keep you going for a while.",
+            Self::Explosive => "A fist-sized canister with a pin on top and a warning label in six languages. It's meant for clearing boarding parties out of corridors.",
+            Self::RepairKit => "A pouch of spare parts, glue and a tiny screwdriver. Enough to patch up a weapon that's seen too much use.",
+            Self::ShipMap => "A laminated floor plan of the ship, showing every room on both decks. Someone has circled the escape pod in red pen.",
+            Self::FireExtinguisher => "A squat red canister of foam. The inspection tag is three years out of date, but it feels full.",
+            Self::Lockpick => "A bent hairpin and a thin strip of metal, wrapped in a hair tie. Old ships like this one still have mechanical locks on some doors.",
             Self::Maps => "A map of the galaxy in the format which spacecraft use to plot routes",
             Self::EscapePodKeys => "A key card labelled 'escape pod'. The label is beginning to wear.",
             Self::Dust => "You'd think air vents would be clean like the rest of the ship, but evidently not. If this were an Arnithian ship, you could climb into the vents just fine.",
@@ -76,4 +356,293 @@ impl Item {
             Self::CaptainsDiary(_) => "The diary you found underneath the bunks. It's physical paper and the handwriting is awful."
         }
     }
+
+    /// Gets how heavy the item is, in kg. The [player][crate::player::Player] can only carry up to their [`max_weight`][crate::player::Player::max_weight].
+    pub const fn get_weight(&self) -> usize {
+        match self {
+            Self::Food(f) => f.weight,
+            Self::Weapon(w) => w.weight,
+            Self::Armor(a) => a.weight,
+            Self::CaptainsDiary(_) | Self::Bandage | Self::Explosive | Self::RepairKit | Self::ShipMap => 1,
+            Self::FireExtinguisher => 3,
+            Self::Currency(_) | Self::Maps | Self::EscapePodKeys | Self::Dust | Self::Shame | Self::Lockpick => 0,
+        }
+    }
+
+    /// Gets how much gold the item is worth in a [`Shop`][crate::shop::Shop], or [`None`] if it can't be traded
+    pub const fn get_value(&self) -> Option<u32> {
+        match self {
+            Self::Food(f) => Some(f.value),
+            Self::Weapon(w) => Some(w.value),
+            Self::Armor(a) => Some(a.value),
+            Self::CaptainsDiary(_) => Some(4),
+            Self::Bandage | Self::Lockpick => Some(3),
+            Self::Explosive => Some(8),
+            Self::RepairKit => Some(5),
+            Self::ShipMap => Some(6),
+            Self::FireExtinguisher => Some(2),
+            Self::Currency(_) | Self::Maps | Self::EscapePodKeys | Self::Dust | Self::Shame => None,
+        }
+    }
+
+    /// Gets how rare the item is. Only food, weapons and armor can be anything other than [common][Rarity::Common].
+    pub const fn get_rarity(&self) -> Rarity {
+        match self {
+            Self::Food(f) => f.rarity,
+            Self::Weapon(w) => w.rarity,
+            Self::Armor(a) => a.rarity,
+            _ => Rarity::Common,
+        }
+    }
+
+    /// Makes a [common][Rarity::Common] item the given [`Rarity`], scaling its stats and value as described in [`Rarity`].
+    /// Items which are already rarer, or which can't have a rarity, are returned as they are.
+    pub const fn with_rarity(self, rarity: Rarity) -> Self {
+        if !matches!(self.get_rarity(), Rarity::Common) {
+            return self;
+        }
+        let value_percent = rarity.get_value_percent();
+
+        match self {
+            Self::Food(mut f) => {
+                f.heals_for = rarity.scale_stat(f.heals_for);
+                f.value = f.value * value_percent / 100;
+                f.rarity = rarity;
+                Self::Food(f)
+            }
+            Self::Weapon(mut w) => {
+                w.straight_damage = rarity.scale_stat(w.straight_damage);
+                w.dodge_damage = rarity.scale_stat(w.dodge_damage);
+                w.throw_damage = rarity.scale_stat(w.throw_damage);
+                w.value = w.value * value_percent / 100;
+                w.rarity = rarity;
+                Self::Weapon(w)
+            }
+            Self::Armor(mut a) => {
+                a.defense = rarity.scale_stat(a.defense);
+                a.value = a.value * value_percent / 100;
+                a.rarity = rarity;
+                Self::Armor(a)
+            }
+            item => item,
+        }
+    }
+
+    /// Gets a label to show after the item's name if it is rarer than [common][Rarity::Common], or an empty string otherwise
+    pub const fn get_rarity_label(&self) -> &'static str {
+        match self.get_rarity() {
+            Rarity::Common => "",
+            Rarity::Uncommon => " [Uncommon]",
+            Rarity::Rare => " [Rare]",
+        }
+    }
+
+    /// Gets whether the item can go in the same stack as another one in the [player's inventory][crate::player::Player::inventory].
+    /// Identical items stack, except for weapons and armor, which are always kept separate so that they can be [equipped][crate::player::Player::equipped_weapon] individually.
+    pub fn can_stack_with(&self, other: &Self) -> bool {
+        !matches!(self, Self::Weapon(_) | Self::Armor(_)) && self == other
+    }
+
+    /// Gets a label to show after the item's name if it is [spoiled food][Food::is_spoiled], or an empty string otherwise
+    pub const fn get_spoiled_label(&self) -> &'static str {
+        match self {
+            Self::Food(f) if f.is_spoiled() => " (spoiled)",
+            _ => "",
+        }
+    }
+}
+
+/// Ages every [`Food`] item in the given list by a turn, bringing it closer to spoiling.
+/// This should be used for every list of items in the game each turn, so that all food ages at the same rate.
+pub fn age_food<'a>(items: impl IntoIterator<Item = &'a mut Item>) {
+    for item in items {
+        if let Item::Food(f) = item {
+            f.turns_until_spoiled = f.turns_until_spoiled.saturating_sub(1);
+        }
+    }
+}
+
+
+/// A recipe for [combining][crate::player::Player] two items into a new one
+struct Recipe {
+    /// The names of the two items which are used up. They can be combined in either order.
+    inputs: [&'static str; 2],
+    /// Creates the item which the recipe makes
+    output: fn() -> Item,
+}
+
+/// Every [`Recipe`] in the game
+const RECIPES: &[Recipe] = &[
+    Recipe {
+        inputs: ["Bread roll", "Bar of Chocolate"],
+        output: chocolate_sandwich,
+    },
+    Recipe {
+        inputs: ["Protein Bar", "Can of Synth-Cola"],
+        output: energy_snack,
+    },
+    Recipe {
+        inputs: ["Set of Throwing Darts", "Shaving Razor"],
+        output: razor_darts,
+    },
+];
+
+/// Creates a new 'chocolate sandwich' item, made from a bread roll and a bar of chocolate
+const fn chocolate_sandwich() -> Item {
+    Item::Food(Food {
+        name: "Chocolate Sandwich",
+        description: "A bread roll stuffed with broken chunks of chocolate. Your mother would not approve.",
+        heals_for: Damage::new(16),
+        fills_for: 20,
+        weight: 2,
+        value: 9,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 10,
+    })
+}
+
+/// Creates a new 'energy snack' item, made from a protein bar and a can of synth-cola
+const fn energy_snack() -> Item {
+    Item::Food(Food {
+        name: "Energy Snack",
+        description: "A protein bar soaked in synth-cola until it goes soft. It's disgusting, but it's a lot easier to get down.",
+        heals_for: Damage::new(9),
+        fills_for: 14,
+        weight: 2,
+        value: 5,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 15,
+    })
+}
+
+/// Creates a new 'razor darts' item, made from a set of throwing darts and a shaving razor
+const fn razor_darts() -> Item {
+    Item::Weapon(Weapon {
+        name: "Razor Darts",
+        description: "Throwing darts with slivers of razor blade taped to the tips. They won't fly as straight, but they'll hurt a lot more.",
+        damage_type: DamageType::Sharp,
+        straight_damage: Damage::new(3),
+        dodge_damage: Damage::new(2),
+        speed: 1,
+        throw_damage: Damage::new(9),
+        two_handed: false,
+        accuracy: 85,
+        reach: 1,
+        durability: 10,
+        max_durability: 10,
+        weight: 1,
+        value: 6,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Stab),
+    })
+}
+
+/// Finds the [`Recipe`] which combines the two given items, and creates its output.
+/// Returns [`None`] if the items can't be combined.
+pub fn combine(a: &Item, b: &Item) -> Option<Item> {
+    let names = [a.get_name(), b.get_name()];
+
+    RECIPES
+        .iter()
+        .find(|r| r.inputs == names || r.inputs == [names[1], names[0]])
+        .map(|r| (r.output)())
+}
+
+/// A way of cooking a raw [`Food`] on the stove in the [`Kitchen`][crate::rooms::Room::Kitchen], turning it into a better one
+#[derive(Debug)]
+struct CookingRecipe {
+    /// The name of the food which is cooked
+    input: &'static str,
+    /// Creates the food which cooking it makes
+    output: fn() -> Item,
+}
+
+/// Every [`CookingRecipe`] in the game
+const COOKING_RECIPES: &[CookingRecipe] = &[
+    CookingRecipe {
+        input: "Bread roll",
+        output: toasted_bread_roll,
+    },
+    CookingRecipe {
+        input: "Tin of Peaches",
+        output: stewed_peaches,
+    },
+];
+
+/// Creates a new 'toasted bread roll' item, made by cooking a bread roll
+const fn toasted_bread_roll() -> Item {
+    Item::Food(Food {
+        name: "Toasted Bread Roll",
+        description: "A bread roll toasted golden under the grill. It's crisp on the outside and it'll keep a lot longer now.",
+        heals_for: Damage::new(8),
+        fills_for: 14,
+        weight: 1,
+        value: 4,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 40,
+    })
+}
+
+/// Creates a new 'stewed peaches' item, made by cooking a tin of peaches
+const fn stewed_peaches() -> Item {
+    Item::Food(Food {
+        name: "Stewed Peaches",
+        description: "Peaches simmered in their own syrup until they're soft and sticky. Warm food has never tasted so good.",
+        heals_for: Damage::new(11),
+        fills_for: 10,
+        weight: 1,
+        value: 7,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 45,
+    })
+}
+
+/// Finds the [`CookingRecipe`] for the given item, and creates the food which cooking it makes.
+/// The cooked food keeps the [`Rarity`] of what went into it. Returns [`None`] if the item can't be cooked.
+pub fn cook(item: &Item) -> Option<Item> {
+    COOKING_RECIPES
+        .iter()
+        .find(|r| r.input == item.get_name())
+        .map(|r| (r.output)().with_rarity(item.get_rarity()))
+}
+
+/// The order [taking everything][crate::player::Player::take_all] in a room picks items up in, so the best ones are taken if they don't all fit.
+/// This is set with [`config::TAKE_ALL_ORDER`][crate::config::TAKE_ALL_ORDER].
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // Only one mode is configured at a time
+pub enum PickUpOrder {
+    /// Lightest first, to carry away as many items as possible
+    Lightest,
+    /// Most [valuable][Item::get_value] first. Items which can't be traded come last.
+    MostValuable,
+}
+
+impl PickUpOrder {
+    /// Compares two [`Item`]s to find which one is picked up first in this order.
+    /// Items which compare equal are picked up in the order they are in the room.
+    pub fn compare(self, a: &Item, b: &Item) -> Ordering {
+        match self {
+            Self::Lightest => a.get_weight().cmp(&b.get_weight()),
+            Self::MostValuable => b.get_value().cmp(&a.get_value()),
+        }
+    }
+}
+
+/// Formats a list of items as bullet points, with one item per line
+pub fn list_items(items: &[Item]) -> String {
+    items.iter().fold(String::new(), |mut list, item| {
+        // Writing to a String can't fail
+        let _ = writeln!(list, "• {}{} - {}", item.get_name(), item.get_rarity_label(), item.get_description());
+        list
+    })
+}
+
+/// Formats a list of item stacks as bullet points, with one stack per line. Stacks of more than one item show how many are in them.
+pub fn list_item_stacks(stacks: &[(Item, usize)]) -> String {
+    stacks.iter().fold(String::new(), |mut list, (item, count)| {
+        let count = if *count > 1 { format!(" (x{count})") } else { String::new() };
+        // Writing to a String can't fail
+        let _ = writeln!(list, "• {}{}{}{} - {}", item.get_name(), item.get_rarity_label(), count, item.get_spoiled_label(), item.get_description());
+        list
+    })
 }
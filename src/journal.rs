@@ -0,0 +1,60 @@
+//! Contains the [`Journal`] type, which keeps a log of notable events for the [`Player`][crate::player::Player] to look back on
+
+use std::collections::VecDeque;
+
+use crate::config;
+use crate::menu::{Menu, OptionList, Screen};
+
+/// A chronological log of notable events, such as picking up items, entering new rooms, winning battles and levelling up.
+/// Only the most recent [`JOURNAL_MAX_ENTRIES`][config::JOURNAL_MAX_ENTRIES] events are kept.
+#[derive(Debug, Default, Clone)]
+pub struct Journal {
+    /// The turn of the loop each event happened on, and a description of it, oldest first
+    pub entries: VecDeque<(usize, String)>,
+}
+
+impl Journal {
+    /// Adds an event which happened on the given turn to the end of the journal, forgetting the oldest event if it's full
+    pub fn add(&mut self, turn: usize, event: String) {
+        if self.entries.len() == config::JOURNAL_MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((turn, event));
+    }
+
+    /// Shows the events in the journal as a list, which is split into pages if it's long.
+    /// Picking an event shows it in full, until the player closes the journal.
+    pub fn show(&self, menu: &mut impl Menu) {
+        if self.entries.is_empty() {
+            menu.show_screen(Screen {
+                title: "Your journal is empty",
+                content: "Nothing worth remembering has happened yet.",
+                title_color: None,
+                art: None,
+            });
+            return;
+        }
+
+        let entries = self
+            .entries
+            .iter()
+            .map(|(turn, event)| format!("Turn {turn}: {event}"))
+            .collect::<Vec<_>>();
+
+        loop {
+            let list = OptionList::new(&entries, "Your journal").with_cancel_text("Close");
+
+            let Some(i) = menu.show_option_list_cancellable(list) else {
+                return;
+            };
+
+            let (turn, event) = &self.entries[i];
+            menu.show_screen(Screen {
+                title: &format!("Turn {turn}"),
+                content: event,
+                title_color: None,
+                art: None,
+            });
+        }
+    }
+}
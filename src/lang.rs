@@ -0,0 +1,88 @@
+//! Looks up player-facing text in the active [`Language`].
+//! Text is referred to by a key, such as `"room.bridge.name"`, and each language has a table mapping keys to text.
+//! The language is chosen at startup with the `--lang <code>` command line argument, and defaults to [`config::LANGUAGE`].
+//!
+//! So far, room names and descriptions, combat action descriptions, and the [`Player`][crate::player::Player]'s action menu are translated.
+
+mod en;
+mod fr;
+mod tests;
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::config;
+
+/// A language the game's text can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// English, which every key must have text for
+    English,
+    /// French
+    French,
+}
+
+impl Language {
+    /// Every [`Language`]
+    const ALL: [Self; 2] = [Self::English, Self::French];
+
+    /// Gets the code used to choose the language with `--lang`
+    const fn get_code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::French => "fr",
+        }
+    }
+
+    /// Gets the table mapping keys to text in this language
+    const fn get_table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => en::STRINGS,
+            Self::French => fr::STRINGS,
+        }
+    }
+
+    /// Gets the [`Language`] with the given code, or [`None`] if there isn't one
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.get_code() == code)
+    }
+}
+
+/// The language chosen at startup. If it hasn't been set, [`config::LANGUAGE`] is used.
+static ACTIVE_LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Sets the active [`Language`] from the `--lang <code>` command line argument, if it's given and the code is valid.
+/// This should be called once, before any text is shown.
+pub fn init() {
+    let mut args = std::env::args().skip_while(|arg| arg != "--lang").skip(1);
+
+    if let Some(language) = args.next().and_then(|code| Language::from_code(&code)) {
+        let _ = ACTIVE_LANGUAGE.set(language);
+    }
+}
+
+/// Gets the text for the given key in the given [`Language`].
+/// If the language doesn't have the key, the English text is used instead, and if English doesn't have it either, the key itself is returned.
+pub fn get_in(language: Language, key: &'static str) -> &'static str {
+    let find = |language: Language| language.get_table().iter().find(|(k, _)| *k == key).map(|(_, text)| *text);
+
+    find(language).or_else(|| find(Language::English)).unwrap_or(key)
+}
+
+/// Gets the text for the given key in the active [`Language`]
+pub fn get(key: &'static str) -> &'static str {
+    get_in(*ACTIVE_LANGUAGE.get().unwrap_or(&config::LANGUAGE), key)
+}
+
+/// Gets the text for the given key in the active [`Language`], replacing each `{}` in it with the next of the given arguments
+pub fn fill(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = get(key).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+
+    for (part, arg) in parts.zip(args.iter().map(ToString::to_string).chain(std::iter::repeat(String::new()))) {
+        text += &arg;
+        text += part;
+    }
+
+    text
+}
@@ -0,0 +1,117 @@
+//! The English text for every key. Every other [`Language`][super::Language] falls back to this table.
+
+/// Maps keys to English text
+pub const STRINGS: &[(&str, &str)] = &[
+    ("room.bridge.name", "Bridge"),
+    ("room.bridge.description", "The control centre of the ship. Through the front window you can see into the darkness of space."),
+    ("room.upper_corridor.name", "Upper Corridor"),
+    ("room.upper_corridor.description", "A corridor connecting the bridge to the rest of the ship."),
+    ("room.strategy_room.name", "Strategy Room"),
+    ("room.strategy_room.description", "Where important tactical decisions are made. Before you arrived, the most important decision since since leaving the front lines had been what galactic time zone to use."),
+    ("room.cells.name", "Cells"),
+    ("room.cells.description", "Where they keep prisoners such as yourself. The ship is on a skeleton crew on its way to pick up troops and the security isn't up to scratch, so you managed to force open the door."),
+    ("room.mess_hall.name", "Mess Hall"),
+    ("room.mess_hall.description", "Where the crew eat their meals. A holo-screen in the corner is playing a game of half-G volleyball."),
+    ("room.kitchen.name", "Kitchen"),
+    ("room.kitchen.description", "An immaculately clean kitchen area. All the appliances are electric - no open flames are allowed on the ship."),
+    ("room.stairwell.name", "Stairwell"),
+    ("room.stairwell.description", "A stairwell. There's not much to do, but out the window you can see the ship's engines pushing you forward into your captors' grip."),
+    ("room.crew_area.name", "Crew Area"),
+    ("room.crew_area.description", "Where the soldiers relax after a long cycle. If there were any, that is. There's a dart board on the wall, but no darts anywhere."),
+    ("room.store_room.name", "Store Room"),
+    ("room.store_room.description", "A small room with many shelves containing various things. The light is broken so you can only make out shapes close to the door."),
+    ("room.lower_corridor.name", "Lower Corridor"),
+    ("room.lower_corridor.description", "A corridor connecting the crew area to the engine room."),
+    ("room.wash_room.name", "Wash Room"),
+    ("room.wash_room.description", "A spotless wash room containing a few showers and a few toilets. This is a military vessel, so there's no need for privacy."),
+    ("room.bunks.name", "Bunks"),
+    ("room.bunks.description", "The soldiers will sleep here when they are on board"),
+    ("room.engine_room.name", "Engine Room"),
+    ("room.engine_room.description", "Where the ship's internals are serviced from. The actual engines are at the back of the ship, but this is where the boiler and the electrical breakers are."),
+    ("room.escape_pod.name", "Escape Pod"),
+    ("room.escape_pod.description", "A pod big enough for only two people. It has enough fuel to get you to safety, but only just."),
+    ("room.escape.name", "Open Space"),
+    ("room.escape.description", "The ship shrinks behind you until it's just another star. For the first time since you woke up in that cell, nobody is coming after you."),
+
+    ("direction.north", "north"),
+    ("direction.south", "south"),
+    ("direction.east", "east"),
+    ("direction.west", "west"),
+    ("direction.up", "up"),
+    ("direction.down", "down"),
+
+    ("combat.enemy.attack_left", "The {} attacks to the left with their {}"),
+    ("combat.enemy.attack_right", "The {} attacks to the right with their {}"),
+    ("combat.enemy.attack_straight", "The {} attacks in front of them with their {}"),
+    ("combat.enemy.shoot_left", "The {} fires their {} off to the left"),
+    ("combat.enemy.shoot_right", "The {} fires their {} off to the right"),
+    ("combat.enemy.shoot_straight", "The {} levels their {} and fires straight at you"),
+    ("combat.enemy.slash_left", "The {} slashes to the left with their {}"),
+    ("combat.enemy.slash_right", "The {} slashes to the right with their {}"),
+    ("combat.enemy.slash_straight", "The {} slashes straight at you with their {}"),
+    ("combat.enemy.stab_left", "The {} lunges to the left and stabs with their {}"),
+    ("combat.enemy.stab_right", "The {} lunges to the right and stabs with their {}"),
+    ("combat.enemy.stab_straight", "The {} stabs straight at you with their {}"),
+    ("combat.enemy.swing_left", "The {} swings their {} round to the left"),
+    ("combat.enemy.swing_right", "The {} swings their {} round to the right"),
+    ("combat.enemy.swing_straight", "The {} brings their {} down in front of them"),
+    ("combat.enemy.eat", "The {} attempts to eat their {}"),
+    ("combat.enemy.throw", "The {} throws their {} at you"),
+    ("combat.enemy.dodge_left", "The {} dodges to the left"),
+    ("combat.enemy.dodge_right", "The {} dodges to the right"),
+    ("combat.enemy.block", "The {} raises their guard"),
+    ("combat.enemy.nothing", "The {} does nothing"),
+
+    ("combat.player.attack_left", "You attack to the left with your {}"),
+    ("combat.player.attack_right", "You attack to the right with your {}"),
+    ("combat.player.attack_straight", "You attack in front of you with your {}"),
+    ("combat.player.shoot_left", "You fire your {} off to the left"),
+    ("combat.player.shoot_right", "You fire your {} off to the right"),
+    ("combat.player.shoot_straight", "You level your {} and fire straight ahead"),
+    ("combat.player.slash_left", "You slash to the left with your {}"),
+    ("combat.player.slash_right", "You slash to the right with your {}"),
+    ("combat.player.slash_straight", "You slash straight ahead with your {}"),
+    ("combat.player.stab_left", "You lunge to the left and stab with your {}"),
+    ("combat.player.stab_right", "You lunge to the right and stab with your {}"),
+    ("combat.player.stab_straight", "You stab straight ahead with your {}"),
+    ("combat.player.swing_left", "You swing your {} round to the left"),
+    ("combat.player.swing_right", "You swing your {} round to the right"),
+    ("combat.player.swing_straight", "You bring your {} down in front of you"),
+    ("combat.player.eat", "You attempt to eat your {}"),
+    ("combat.player.throw", "You hurl your {} as hard as you can"),
+    ("combat.player.dodge_left", "You dodge to the left"),
+    ("combat.player.dodge_right", "You dodge to the right"),
+    ("combat.player.block", "You raise your guard"),
+    ("combat.player.nothing", "You do nothing"),
+    ("combat.player.flee", "You turn and run"),
+    ("combat.player.explode", "You reach for your grenade"),
+    ("combat.player.examine", "You size up your opponent"),
+    ("combat.player.bandage", "You hurriedly wrap a bandage around your wounds"),
+
+    ("player.action.check_state", "Check how you're doing"),
+    ("player.action.inventory", "Look through your inventory ({}/{} kg)"),
+    ("player.action.search", "Search the {}"),
+    ("player.action.objectives", "Remind yourself what you need to do"),
+    ("player.action.journal", "Look back over your journal"),
+    ("player.action.stats", "Look at your stats for this loop"),
+    ("player.action.timer", "Check the speedrun timer"),
+    ("player.action.inspect_connections", "Look at where you can go from here"),
+    ("player.action.settings", "Change the settings"),
+    ("player.action.quit", "Quit to the main menu"),
+    ("player.action.skip_tutorial", "Turn off the tutorial"),
+    ("player.action.achievements", "Think back on your achievements"),
+    ("player.action.go", "Go {} to the {}"),
+    ("player.action.open_door", "Open the door {} to the {}"),
+    ("player.action.pick_lock", "Pick the lock on the door to the {}"),
+    ("player.action.close_door", "Close the door to the {}"),
+    ("player.action.go_back", "Go back to the {}"),
+    ("player.action.fast_travel", "Head straight to somewhere you've been before"),
+    ("player.action.rest", "Rest until you're healed"),
+    ("player.action.pick_up", "Pick up the {}{} ({} kg) - {}"),
+    ("player.action.take_all", "Take everything you can carry"),
+    ("player.action.talk", "Talk to the {}"),
+    ("player.action.prompt", "{} - What do you do?"),
+    ("player.fast_travel.destination", "The {} ({} turns away)"),
+    ("player.fast_travel.prompt", "Where do you go?"),
+    ("player.fast_travel.back", "Back"),
+];
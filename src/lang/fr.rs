@@ -0,0 +1,117 @@
+//! The French text for each key. Room names are written without an article, so that they read correctly after a colon.
+
+/// Maps keys to French text
+pub const STRINGS: &[(&str, &str)] = &[
+    ("room.bridge.name", "Passerelle"),
+    ("room.bridge.description", "Le centre de commande du vaisseau. Par la baie avant, vous voyez l'obscurité de l'espace."),
+    ("room.upper_corridor.name", "Couloir supérieur"),
+    ("room.upper_corridor.description", "Un couloir qui relie la passerelle au reste du vaisseau."),
+    ("room.strategy_room.name", "Salle de stratégie"),
+    ("room.strategy_room.description", "C'est ici que se prennent les décisions tactiques importantes. Avant votre arrivée, la décision la plus importante depuis le départ du front avait été le choix du fuseau horaire galactique."),
+    ("room.cells.name", "Cellules"),
+    ("room.cells.description", "C'est ici qu'on garde les prisonniers comme vous. Le vaisseau n'a qu'un équipage réduit et la sécurité laisse à désirer, alors vous avez réussi à forcer la porte."),
+    ("room.mess_hall.name", "Réfectoire"),
+    ("room.mess_hall.description", "C'est ici que l'équipage prend ses repas. Un holo-écran dans le coin diffuse un match de volley en demi-G."),
+    ("room.kitchen.name", "Cuisine"),
+    ("room.kitchen.description", "Une cuisine d'une propreté impeccable. Tous les appareils sont électriques - aucune flamme n'est autorisée à bord."),
+    ("room.stairwell.name", "Cage d'escalier"),
+    ("room.stairwell.description", "Une cage d'escalier. Il n'y a pas grand-chose à faire, mais par la fenêtre vous voyez les moteurs vous pousser vers vos geôliers."),
+    ("room.crew_area.name", "Quartier de l'équipage"),
+    ("room.crew_area.description", "C'est ici que les soldats se détendent après un long cycle. S'il y en avait, du moins. Il y a une cible au mur, mais aucune fléchette."),
+    ("room.store_room.name", "Réserve"),
+    ("room.store_room.description", "Une petite pièce pleine d'étagères et d'objets divers. La lumière est cassée, alors vous ne distinguez que les formes près de la porte."),
+    ("room.lower_corridor.name", "Couloir inférieur"),
+    ("room.lower_corridor.description", "Un couloir qui relie le quartier de l'équipage à la salle des machines."),
+    ("room.wash_room.name", "Salle d'eau"),
+    ("room.wash_room.description", "Une salle d'eau impeccable avec quelques douches et quelques toilettes. C'est un vaisseau militaire, l'intimité n'est pas nécessaire."),
+    ("room.bunks.name", "Dortoir"),
+    ("room.bunks.description", "Les soldats dorment ici quand ils sont à bord"),
+    ("room.engine_room.name", "Salle des machines"),
+    ("room.engine_room.description", "C'est d'ici qu'on entretient les systèmes du vaisseau. Les moteurs sont à l'arrière, mais la chaudière et les disjoncteurs sont ici."),
+    ("room.escape_pod.name", "Capsule de sauvetage"),
+    ("room.escape_pod.description", "Une capsule assez grande pour deux personnes seulement. Elle a juste assez de carburant pour vous mettre en sécurité."),
+    ("room.escape.name", "Espace"),
+    ("room.escape.description", "Le vaisseau rapetisse derrière vous jusqu'à n'être plus qu'une étoile parmi d'autres. Pour la première fois depuis votre réveil dans cette cellule, personne ne vous poursuit."),
+
+    ("direction.north", "au nord"),
+    ("direction.south", "au sud"),
+    ("direction.east", "à l'est"),
+    ("direction.west", "à l'ouest"),
+    ("direction.up", "en haut"),
+    ("direction.down", "en bas"),
+
+    ("combat.enemy.attack_left", "{} attaque à gauche avec son arme : {}"),
+    ("combat.enemy.attack_right", "{} attaque à droite avec son arme : {}"),
+    ("combat.enemy.attack_straight", "{} attaque droit devant avec son arme : {}"),
+    ("combat.enemy.shoot_left", "{} tire à gauche avec son arme : {}"),
+    ("combat.enemy.shoot_right", "{} tire à droite avec son arme : {}"),
+    ("combat.enemy.shoot_straight", "{} vous vise et tire droit devant avec son arme : {}"),
+    ("combat.enemy.slash_left", "{} taille à gauche avec son arme : {}"),
+    ("combat.enemy.slash_right", "{} taille à droite avec son arme : {}"),
+    ("combat.enemy.slash_straight", "{} taille droit devant avec son arme : {}"),
+    ("combat.enemy.stab_left", "{} se fend à gauche et frappe d'estoc avec son arme : {}"),
+    ("combat.enemy.stab_right", "{} se fend à droite et frappe d'estoc avec son arme : {}"),
+    ("combat.enemy.stab_straight", "{} frappe d'estoc droit devant avec son arme : {}"),
+    ("combat.enemy.swing_left", "{} balance son arme vers la gauche : {}"),
+    ("combat.enemy.swing_right", "{} balance son arme vers la droite : {}"),
+    ("combat.enemy.swing_straight", "{} abat son arme devant lui : {}"),
+    ("combat.enemy.eat", "{} essaie de manger : {}"),
+    ("combat.enemy.throw", "{} vous lance son arme : {}"),
+    ("combat.enemy.dodge_left", "{} esquive à gauche"),
+    ("combat.enemy.dodge_right", "{} esquive à droite"),
+    ("combat.enemy.block", "{} lève sa garde"),
+    ("combat.enemy.nothing", "{} ne fait rien"),
+
+    ("combat.player.attack_left", "Vous attaquez à gauche avec votre arme : {}"),
+    ("combat.player.attack_right", "Vous attaquez à droite avec votre arme : {}"),
+    ("combat.player.attack_straight", "Vous attaquez droit devant avec votre arme : {}"),
+    ("combat.player.shoot_left", "Vous tirez à gauche avec votre arme : {}"),
+    ("combat.player.shoot_right", "Vous tirez à droite avec votre arme : {}"),
+    ("combat.player.shoot_straight", "Vous visez et tirez droit devant avec votre arme : {}"),
+    ("combat.player.slash_left", "Vous taillez à gauche avec votre arme : {}"),
+    ("combat.player.slash_right", "Vous taillez à droite avec votre arme : {}"),
+    ("combat.player.slash_straight", "Vous taillez droit devant avec votre arme : {}"),
+    ("combat.player.stab_left", "Vous vous fendez à gauche et frappez d'estoc avec votre arme : {}"),
+    ("combat.player.stab_right", "Vous vous fendez à droite et frappez d'estoc avec votre arme : {}"),
+    ("combat.player.stab_straight", "Vous frappez d'estoc droit devant avec votre arme : {}"),
+    ("combat.player.swing_left", "Vous balancez votre arme vers la gauche : {}"),
+    ("combat.player.swing_right", "Vous balancez votre arme vers la droite : {}"),
+    ("combat.player.swing_straight", "Vous abattez votre arme devant vous : {}"),
+    ("combat.player.eat", "Vous essayez de manger : {}"),
+    ("combat.player.throw", "Vous lancez de toutes vos forces : {}"),
+    ("combat.player.dodge_left", "Vous esquivez à gauche"),
+    ("combat.player.dodge_right", "Vous esquivez à droite"),
+    ("combat.player.block", "Vous levez votre garde"),
+    ("combat.player.nothing", "Vous ne faites rien"),
+    ("combat.player.flee", "Vous prenez la fuite"),
+    ("combat.player.explode", "Vous saisissez votre grenade"),
+    ("combat.player.examine", "Vous jaugez votre adversaire"),
+    ("combat.player.bandage", "Vous enroulez à la hâte un bandage autour de vos blessures"),
+
+    ("player.action.check_state", "Faire le point sur votre état"),
+    ("player.action.inventory", "Fouiller votre inventaire ({}/{} kg)"),
+    ("player.action.search", "Fouiller la pièce : {}"),
+    ("player.action.objectives", "Vous rappeler ce qu'il faut faire"),
+    ("player.action.journal", "Relire votre journal"),
+    ("player.action.stats", "Consulter vos statistiques pour cette boucle"),
+    ("player.action.timer", "Consulter le chronomètre"),
+    ("player.action.inspect_connections", "Regarder où vous pouvez aller d'ici"),
+    ("player.action.settings", "Modifier les paramètres"),
+    ("player.action.quit", "Quitter vers le menu principal"),
+    ("player.action.skip_tutorial", "Désactiver le tutoriel"),
+    ("player.action.achievements", "Repenser à vos exploits"),
+    ("player.action.go", "Aller {} : {}"),
+    ("player.action.open_door", "Ouvrir la porte {} : {}"),
+    ("player.action.pick_lock", "Crocheter la serrure de la porte : {}"),
+    ("player.action.close_door", "Fermer la porte : {}"),
+    ("player.action.go_back", "Revenir : {}"),
+    ("player.action.fast_travel", "Retourner directement dans un endroit déjà visité"),
+    ("player.action.rest", "Vous reposer jusqu'à être guéri"),
+    ("player.action.pick_up", "Ramasser : {}{} ({} kg) - {}"),
+    ("player.action.take_all", "Prendre tout ce que vous pouvez porter"),
+    ("player.action.talk", "Parler : {}"),
+    ("player.action.prompt", "{} - Que faites-vous ?"),
+    ("player.fast_travel.destination", "{} (à {} tours)"),
+    ("player.fast_travel.prompt", "Où allez-vous ?"),
+    ("player.fast_travel.back", "Retour"),
+];
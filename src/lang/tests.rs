@@ -0,0 +1,29 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that every language has text for exactly the same keys as English, with the same number of placeholders
+#[test]
+fn test_tables_match() {
+    for language in Language::ALL {
+        let table = language.get_table();
+        assert_eq!(table.len(), en::STRINGS.len(), "{language:?}");
+
+        for (key, english) in en::STRINGS {
+            let text = table.iter().find(|(k, _)| k == key).map(|(_, text)| *text);
+            assert!(text.is_some(), "{language:?} is missing {key}");
+            assert_eq!(text.unwrap().matches("{}").count(), english.matches("{}").count(), "{language:?} {key}");
+        }
+    }
+}
+
+/// Tests looking up keys in different languages, and falling back when a key is missing
+#[test]
+fn test_get_in() {
+    assert_eq!(get_in(Language::English, "direction.north"), "north");
+    assert_eq!(get_in(Language::French, "direction.north"), "au nord");
+    assert_eq!(get_in(Language::French, "not.a.key"), "not.a.key");
+
+    assert_eq!(Language::from_code("fr"), Some(Language::French));
+    assert_eq!(Language::from_code("xx"), None);
+}
@@ -10,18 +10,53 @@
 
 //! A text-based adventure game
 
+mod achievements;
+mod alarm;
+mod balance;
 mod combat;
 mod config;
+mod dev;
+mod difficulty;
+mod endings;
 mod items;
+mod journal;
+mod lang;
 mod map;
 mod menu;
+mod new_game_plus;
+mod npc;
+mod objectives;
 mod player;
+mod rng;
 mod rooms;
+mod save;
+mod settings;
+mod shop;
+mod speedrun;
+mod stats;
+mod tutorial;
 
+use achievements::Achievements;
+use balance::DynamicBalance;
 use combat::{battle, BattleResult};
-use menu::{Screen, Menu};
-use player::Player;
-use rooms::Room;
+use endings::Ending;
+use menu::{Color, Screen, Menu, OptionList};
+use new_game_plus::{NewGamePlus, NextRun};
+use player::{Player, Quit, Remains};
+use rng::Rng;
+use save::RecordingMenu;
+use speedrun::Timer;
+use tutorial::Tutorial;
+
+/// The banner to show at the top of the [`INTRO_SCREEN`]
+const INTRO_ART: &str = r"  .       *          .            *        .
+        __________________________
+   *   /  ______________________  \=====>    .
+      |  |  T H E   L O O P    |  |
+  .    \  ----------------------  /=====>
+        ------\_________/--------       *
+     .      *        .       .     *
+";
 
 /// The screen to show at the beginning of the game
 const INTRO_SCREEN: Screen = Screen {
@@ -33,63 +68,271 @@ You try the cell door. It's locked, obviously, but the control panel looks insec
 You keep your eyes on the person in the other room, but they don't seem to notice you leaving. Suddenly they look up and see you standing there. They rush out and before you know it you're bleeding out on the floor, and then
 You wake up in a cell, confused and disoriented. You peer as far out of your cell as you can. There's someone in the room opposite you, but they're not looking at you. \
 You hot-wire the door, but then you're more cautious. You duck down below the level of the door and prepare for your final moments... again.",
+    title_color: None,
+    art: Some(INTRO_ART),
 };
 
 /// The screen to show when the time loop resets
 const LOOP_SCREEN: Screen = Screen {
     title: "\"ISPD agents will arrive in: 10 minutes\"",
-    content: "Well, here we go again... You break open the door and hope you don't get shot this time."
+    content: "Well, here we go again... You break open the door and hope you don't get shot this time.",
+    title_color: None,
+    art: None,
 };
 
 /// The screen to show when the player reaches their max turns
 const MAX_TURNS_SCREEN: Screen = Screen {
     title: "\"Now boarding: ISPD agents\"",
-    content: "You groan. There's no way you're getting out of this alive. "
+    content: "You groan. There's no way you're getting out of this alive. ",
+    title_color: None,
+    art: None,
+};
+
+/// The screen to show when the ship self-destructs before the player escapes
+const SELF_DESTRUCT_SCREEN: Screen = Screen {
+    title: "The ship tears itself apart",
+    content: "A deafening roar fills the corridors and the floor buckles under your feet. You were so close.",
+    title_color: Some(Color::Red),
+    art: None,
+};
+
+/// The art to show on the [`DEATH_SCREEN`]
+const DEATH_ART: &str = r"    _______
+   /       \
+  |  X   X  |
+  |    ^    |
+   \ '---' /
+    |_|_|_|
+";
+
+/// The screen to show when the player dies outside of combat
+const DEATH_SCREEN: Screen = Screen {
+    title: "Your vision fades to black",
+    content: "You collapse to the floor. You try to get back up, but your body won't listen any more.",
+    title_color: Some(Color::Red),
+    art: Some(DEATH_ART),
 };
 
+/// Shows the [`DEATH_SCREEN`], with the [stats][Player::stats] of the loop that just ended
+fn show_death_screen(menu: &mut impl Menu, player: &Player) {
+    menu.show_screen(Screen {
+        content: &format!("{}\n\n{}", DEATH_SCREEN.content, player.stats.describe()),
+        ..DEATH_SCREEN
+    });
+}
+
+/// Gets the seed for the run from the `--seed <number>` command line argument, or from the current time if it isn't given
+fn get_seed() -> u64 {
+    let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+
+    args.next()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(Rng::seed_from_time)
+}
+
+/// Asks the player whether they want to go round the time loop again after a game over.
+/// Shows the [`LOOP_SCREEN`] and returns `true` if they do.
+fn try_again(menu: &mut impl Menu) -> bool {
+    if !menu.confirm("Go round the time loop again? If not, this run will be lost.") {
+        return false;
+    }
+
+    menu.show_screen(LOOP_SCREEN);
+    true
+}
+
+/// Takes the [`Remains`] of a [`Player`] who has just died, if they should be left for the next time round the loop at this [difficulty][settings::Settings::difficulty]
+fn drop_remains(player: &mut Player) -> Option<Remains> {
+    if settings::get().difficulty.drops_inventory_on_death() {
+        player.drop_remains()
+    } else {
+        None
+    }
+}
+
+/// Handles the [`Player`] dying: shows the [`DEATH_SCREEN`], makes enemies easier through their [dynamic balance][Player::balance],
+/// and takes their [`Remains`] if they should be left for the next loop
+fn die(menu: &mut impl Menu, player: &mut Player) -> Option<Remains> {
+    player.balance.record_death();
+    show_death_screen(menu, player);
+    drop_remains(player)
+}
+
+/// Shows the main menu after the player has quit a run, and returns `true` if they want to start another one rather than leave the game.
+/// The [settings][settings::show] can be changed from here as well.
+fn show_main_menu(menu: &mut impl Menu) -> bool {
+    let options = ["Start a run".to_string(), "Settings".to_string(), "Exit the game".to_string()];
+
+    loop {
+        match menu.show_option_list(OptionList::new(&options, "Main menu")) {
+            0 => return true,
+            1 => settings::show(menu),
+            _ => return false,
+        }
+    }
+}
+
 fn main() {
+    lang::init();
+    dev::init();
+    settings::init();
+
+    // A broken layout file is reported before the menu takes over the terminal, so the message can be read
+    if let Err(e) = map::load_layout() {
+        eprintln!("The ship layout in {} couldn't be loaded: {e}", config::LAYOUT_PATH);
+        std::process::exit(1);
+    }
+
     let mut menu = menu::init().unwrap();
-    let menu = &mut menu;
 
-    menu.show_screen(INTRO_SCREEN);
+    // Quitting a run goes back to the main menu rather than closing the game
+    loop {
+        let quit;
+        (menu, quit) = play(menu);
+
+        if quit.is_none() || !show_main_menu(&mut menu) {
+            break;
+        }
+    }
+}
+
+/// Plays through a run, from the offer to resume the autosave until the player stops going round the time loop or quits.
+/// Gives back the [`Menu`], along with how the player [quit][Player::quit] if they did.
+fn play<M: Menu>(mut menu: M) -> (M, Option<Quit>) {
+    // Offer to resume the autosave if there is one. This is asked before recording starts, so it isn't part of the save.
+    let (seed, replay, resumed) = match save::load_autosave() {
+        Some((seed, answers)) if menu.confirm("An unfinished run was found. Resume it?") => (seed, answers, true),
+        _ => (get_seed(), Vec::new(), false),
+    };
+
+    let mut recording = RecordingMenu::new(menu, replay);
+    let menu = &mut recording;
+
+    menu.show_screen(Screen {
+        title: INTRO_SCREEN.title,
+        content: &format!("{}\n\nRun seed: {seed}", INTRO_SCREEN.content),
+        title_color: None,
+        art: INTRO_SCREEN.art,
+    });
+
+    // First-time players can have things explained as they come across them
+    let mut tutorial = Tutorial::new(menu.confirm("Is this your first time? Turn on the tutorial?"));
+
+    // The speedrun timer starts once the intro is dismissed. A resumed run has lost the time from before it was closed, so it is marked as resumed.
+    let mut timer = Timer::start(resumed);
+
+    // Achievements are kept between loops, so they are moved into each new player and back out at the end of the loop
+    let mut achievements = Achievements::default();
+    // Like achievements, how far the dynamic balance has shifted is kept between loops
+    let mut balance = DynamicBalance::default();
+    // After a win, New Game Plus progress is kept between loops until the player starts a fresh run
+    let mut new_game_plus: Option<NewGamePlus> = None;
+    // On harder difficulties, whatever the player was carrying when they died is left for the next loop to find
+    let mut remains: Option<Remains> = None;
+    // Set if the player quits part way through, rather than the run coming to an end
+    let mut quit: Option<Quit> = None;
 
     // The outer time loop
-    'time_loop: loop {
-        let mut player = Player::init();
+    loop {
+        // Every loop uses the same seed, so the ship behaves the same way each time round
+        let mut player = match &new_game_plus {
+            Some(new_game_plus) => Player::init_new_game_plus(seed, new_game_plus, balance),
+            None => Player::init_balanced(seed, balance),
+        };
+        player.achievements = achievements;
+        player.tutorial = tutorial;
+        player.timer = timer;
+
+        if let Some(remains) = remains.take() {
+            player.place_remains(menu, remains);
+        }
 
         player.print_room(menu);
 
-        // The inner gameplay loop
-        loop {
+        // The inner gameplay loop, which evaluates to whether to go round the time loop again
+        let keep_playing = loop {
+            if player.has_self_destructed() {
+                menu.show_screen(SELF_DESTRUCT_SCREEN);
+                break try_again(menu);
+            }
+
             if player.remaining_turns == 0 {
                 menu.show_screen(MAX_TURNS_SCREEN);
-                menu.show_screen(LOOP_SCREEN);
-                continue 'time_loop;
+                break try_again(menu);
             }
 
-            if let Some(enemy) = player.get_room_state_mut().enemy.take() {
+            // Enemies who haven't noticed the player yet can be snuck past instead of fought
+            if let Some(enemy) = player.get_room_state_mut().enemy.take_if(|e| e.alert) {
                 let battle_result = battle(&mut player, enemy, menu);
 
                 match battle_result {
-                    BattleResult::PlayerWin => (),
+                    BattleResult::PlayerWin | BattleResult::Fled => (),
                     BattleResult::PlayerLoss => {
-                        menu.show_screen(LOOP_SCREEN);
-                        continue 'time_loop;
-                    },
+                        remains = die(menu, &mut player);
+                        break try_again(menu);
+                    }
                     BattleResult::MaxTurnsReached => {
                         menu.show_screen(MAX_TURNS_SCREEN);
-                        menu.show_screen(LOOP_SCREEN);
-                        continue 'time_loop;
+                        break try_again(menu);
+                    }
+                    BattleResult::SelfDestructed => {
+                        menu.show_screen(SELF_DESTRUCT_SCREEN);
+                        break try_again(menu);
                     }
                 }
             }
 
             player.take_passive_action(menu);
 
-            if matches!(player.room, Room::Escape) {
-                player.show_win_screen(menu);
-                break 'time_loop;
+            // The last autosave was written before the player chose to quit, so resuming it won't quit again
+            if player.quit.is_some() {
+                quit = player.quit;
+                break false;
+            }
+
+            if player.health.is_dead() {
+                remains = die(menu, &mut player);
+                break try_again(menu);
+            }
+
+            if player.has_escaped() {
+                let finish = player.timer.finish();
+                finish.save_best();
+                player.show_win_screen(menu, Ending::from_player(&player), &finish);
+
+                break match NextRun::choose(&player, menu) {
+                    NextRun::NewGamePlus(next) => {
+                        new_game_plus = Some(next);
+                        player.timer = Timer::start(false);
+                        true
+                    }
+                    NextRun::FreshRun => {
+                        new_game_plus = None;
+                        player.timer = Timer::start(false);
+                        true
+                    }
+                    NextRun::Stop => false,
+                };
             }
+
+            // Failing to autosave shouldn't stop the player from carrying on
+            let _ = save::write_autosave(seed, menu.get_answers());
+        };
+
+        achievements = player.achievements;
+        tutorial = player.tutorial;
+        balance = player.balance;
+        timer = player.timer;
+
+        if !keep_playing {
+            break;
         }
     }
+
+    // Unless the player quit and kept it, the run is over, so there's nothing to resume
+    if quit != Some(Quit::Save) {
+        save::delete_autosave();
+    }
+
+    (recording.into_inner(), quit)
 }
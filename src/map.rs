@@ -1,130 +1,167 @@
 //! Functions for initialising the map for each time loop
 
-use std::collections::HashMap;
-
 mod actions;
+mod armor;
 mod enemies;
 mod food;
+mod hazards;
+mod layout;
+mod npcs;
+mod rooms;
+mod tests;
 mod transitions;
+mod traps;
 mod weapons;
 
 pub use actions::RoomAction;
+pub use enemies::patrolling_guard;
+pub use layout::{LayoutError, ITEMS};
+
+use std::sync::OnceLock;
+
+use crate::alarm::Alarm;
+use crate::combat::Enemy;
+use crate::items::Item;
+use crate::npc::Npc;
+use crate::rooms::{Hazard, Room, RoomGraph, RoomState, RoomTransition, Trap};
+use crate::shop::Shop;
+
+/// A description of a [`Room`] as it is at the start of each loop, which [`init`] builds a [`RoomState`] from.
+/// Enemies and the like are given as functions rather than values, so that every loop gets fresh ones.
+/// Definitions are written with struct update syntax on top of [`new`][Self::new]:
+/// ```
+/// RoomDefinition {
+///     items: &[weapons::wrench()],
+///     enemy: Some(enemies::mechanic),
+///     ..RoomDefinition::new(Room::EngineRoom, &[ENGINE_ROOM_TO_LOWER_CORRIDOR])
+/// }
+/// ```
+#[derive(Debug)]
+struct RoomDefinition {
+    /// Which room this defines
+    room: Room,
+    /// Which other rooms the player can go to from this one
+    connections: &'static [RoomTransition],
+    /// The items lying in the room
+    items: &'static [Item],
+    /// The items the player finds by searching the room
+    hidden_items: &'static [Item],
+    /// The actions which can be performed in the room
+    actions: &'static [RoomAction],
+    /// The [`Enemy`] in the room, if there is one
+    enemy: Option<fn() -> Enemy>,
+    /// The [`Shop`] in the room, if there is one
+    shop: Option<ShopDefinition>,
+    /// The [`Trap`] in the room, if there is one
+    trap: Option<fn() -> Trap>,
+    /// The [`Npc`] in the room, if there is one
+    npc: Option<fn() -> Npc>,
+    /// The [`Hazard`] in the room, if there is one
+    hazard: Option<fn() -> Hazard>,
+}
 
-use crate::rooms::{Room, RoomGraph, RoomState};
+/// A description of a [`Shop`] in a [`RoomDefinition`]
+#[derive(Debug)]
+struct ShopDefinition {
+    /// The name of the shop
+    name: &'static str,
+    /// What the shop starts each loop with for sale
+    stock: &'static [Item],
+}
 
-use self::transitions::*;
+impl RoomDefinition {
+    /// Creates a [`RoomDefinition`] for an empty room with the given connections
+    const fn new(room: Room, connections: &'static [RoomTransition]) -> Self {
+        Self {
+            room,
+            connections,
+            items: &[],
+            hidden_items: &[],
+            actions: &[],
+            enemy: None,
+            shop: None,
+            trap: None,
+            npc: None,
+            hazard: None,
+        }
+    }
 
-/// Initialise a new [`RoomGraph`]
-pub fn init() -> RoomGraph {
-    // The bridge
-    let bridge = RoomState::new(Room::Bridge, vec![BRIDGE_TO_UPPER_CORRIDOR])
-        .add_item(weapons::intruders_blaster())
-        .add_action(RoomAction::BridgeHackTheMainframe);
-
-    // The upper corridor
-    let upper_corridor = RoomState::new(
-        Room::UpperCorridor,
-        vec![
-            UPPER_CORRIDOR_TO_BRIDGE,
-            UPPER_CORRIDOR_TO_STRATEGY_ROOM,
-            UPPER_CORRIDOR_TO_CELLS,
-            UPPER_CORRIDOR_TO_MESS_HALL,
-        ],
-    );
-
-    // The strategy room
-    let strategy_room = RoomState::new(Room::StrategyRoom, vec![STRATEGY_ROOM_TO_UPPER_CORRIDOR])
-        .with_enemy(enemies::skipper())
-        .add_action(RoomAction::StrategyRoomTakeMaps);
-
-    // The cells
-    let cells = RoomState::new(Room::Cells, vec![CELLS_TO_UPPER_CORRIDOR])
-        .add_action(RoomAction::CellsClimbIntoVents);
-
-    // The mess hall
-    let mess_hall = RoomState::new(
-        Room::MessHall,
-        vec![
-            MESS_HALL_TO_UPPER_CORRIDOR,
-            MESS_HALL_TO_KITCHEN,
-            MESS_HALL_TO_STAIRWELL,
-        ],
-    )
-    .with_enemy(enemies::cook())
-    .add_action(RoomAction::MessHallWatchTheGame);
-
-    // The kitchen
-    let kitchen = RoomState::new(Room::Kitchen, vec![KITCHEN_TO_MESS_HALL])
-        .add_item(food::bread_roll())
-        .add_item(weapons::eating_knife());
-
-    // The stairwell
-    let stairwell = RoomState::new(
-        Room::Stairwell,
-        vec![STAIRWELL_TO_MESS_HALL, STAIRWELL_TO_CREW_AREA],
-    );
-
-    // The crew area
-    let crew_area = RoomState::new(
-        Room::CrewArea,
-        vec![
-            CREW_AREA_TO_STAIRWELL,
-            CREW_AREA_TO_STORE_ROOM,
-            CREW_AREA_TO_ESCAPE_POD,
-            CREW_AREA_TO_LOWER_CORRIDOR,
-        ],
-    );
-
-    // The store room
-    let store_room = RoomState::new(Room::StoreRoom, vec![STORE_ROOM_TO_CREW_AREA])
-        .add_action(RoomAction::StoreRoomFindChocolate);
-
-    // The lower corridor
-    let lower_corridor = RoomState::new(
-        Room::LowerCorridor,
-        vec![
-            LOWER_CORRIDOR_TO_CREW_AREA,
-            LOWER_CORRIDOR_TO_BUNKS,
-            LOWER_CORRIDOR_TO_WASH_ROOM,
-            LOWER_CORRIDOR_TO_ENGINE_ROOM,
-        ],
-    );
-
-    // The bunks
-    let bunks = RoomState::new(Room::Bunks, vec![BUNKS_TO_LOWER_CORRIDOR])
-        .add_item(weapons::throwing_dart_set())
-        .add_action(RoomAction::BunksGetDiary);
-
-    // The wash room
-    let wash_room = RoomState::new(Room::WashRoom, vec![WASH_ROOM_TO_LOWER_CORRIDOR])
-        .add_item(weapons::shaving_razor());
-
-    // The engine room
-    let engine_room = RoomState::new(Room::EngineRoom, vec![ENGINE_ROOM_TO_LOWER_CORRIDOR])
-        .with_enemy(enemies::mechanic())
-        .add_action(RoomAction::EngineRoomTakeKeys)
-        .add_item(weapons::wrench());
-
-    let escape_pod = RoomState::new(Room::EscapePod, vec![ESCAPE_POD_TO_CREW_AREA])
-        .add_action(RoomAction::EscapePodTakeOff);
-
-    // Construct a room graph from all the rooms
+    /// Builds the [`RoomState`] this definition describes
+    fn build(&self) -> RoomState {
+        let mut state = RoomState::new(self.room, self.connections.to_vec());
+
+        for item in self.items {
+            state = state.add_item(item.clone());
+        }
+        for item in self.hidden_items {
+            state = state.hide_item(item.clone());
+        }
+        for action in self.actions {
+            state = state.add_action(action.clone());
+        }
+        if let Some(enemy) = self.enemy {
+            state = state.with_enemy(enemy());
+        }
+        if let Some(shop) = &self.shop {
+            state = state.with_shop(Shop::new(shop.name, shop.stock.to_vec()));
+        }
+        if let Some(trap) = self.trap {
+            state = state.with_trap(trap());
+        }
+        if let Some(npc) = self.npc {
+            state = state.with_npc(npc());
+        }
+        if let Some(hazard) = self.hazard {
+            state = state.with_hazard(hazard());
+        }
+
+        state
+    }
+}
+
+/// The rooms loaded from the [layout file][crate::config::LAYOUT_PATH] at startup. If there wasn't one, the built-in [`ROOMS`][rooms::ROOMS] are used.
+static LOADED_ROOMS: OnceLock<&'static [RoomDefinition]> = OnceLock::new();
+
+/// Loads the rooms from the [layout file][crate::config::LAYOUT_PATH], if there is one, to be used by [`init`] instead of the built-in rooms.
+/// This should be called once, before the first [`init`].
+pub fn load_layout() -> Result<(), LayoutError> {
+    if let Some(text) = layout::read()? {
+        let _ = LOADED_ROOMS.set(layout::parse(&text)?);
+    }
+    Ok(())
+}
+
+/// Builds a [`RoomGraph`] from the given [`RoomDefinition`]s
+fn build_graph(definitions: &[RoomDefinition]) -> RoomGraph {
     RoomGraph {
-        rooms: HashMap::from([
-            (Room::Bridge, bridge),
-            (Room::UpperCorridor, upper_corridor),
-            (Room::StrategyRoom, strategy_room),
-            (Room::Cells, cells),
-            (Room::MessHall, mess_hall),
-            (Room::Kitchen, kitchen),
-            (Room::Stairwell, stairwell),
-            (Room::CrewArea, crew_area),
-            (Room::StoreRoom, store_room),
-            (Room::LowerCorridor, lower_corridor),
-            (Room::Bunks, bunks),
-            (Room::WashRoom, wash_room),
-            (Room::EngineRoom, engine_room),
-            (Room::EscapePod, escape_pod),
-        ]),
+        rooms: definitions.iter().map(|definition| (definition.room, definition.build())).collect(),
+        alarm: Alarm::default(),
+        distraction: None,
+        power_on: true,
+    }
+}
+
+/// Initialise a new [`RoomGraph`] from the [loaded layout][load_layout], or from the table of built-in [`ROOMS`][rooms::ROOMS] if there isn't one.
+/// Every [`Room`] which isn't defined, such as [`Room::Escape`], is given an empty [`RoomState`] with no connections,
+/// so that [`get_state`][RoomGraph::get_state] never panics.
+pub fn init() -> RoomGraph {
+    let definitions = LOADED_ROOMS.get().copied().unwrap_or(rooms::ROOMS);
+    let mut graph = build_graph(definitions);
+
+    // Catch mistakes in the map while developing
+    if cfg!(debug_assertions) {
+        assert_eq!(graph.rooms.len(), definitions.len(), "A room is defined more than once");
+
+        for problem in graph.validate() {
+            assert!(!problem.is_fatal(), "Invalid room graph: {problem}");
+            eprintln!("Warning: {problem}");
+        }
     }
+
+    // This is done after validating, so that connections to rooms which weren't defined are still caught
+    for room in Room::ALL {
+        graph.rooms.entry(room).or_insert_with(|| RoomState::new(room, Vec::new()));
+    }
+
+    graph
 }
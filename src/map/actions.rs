@@ -1,11 +1,16 @@
 //! Contains the [`RoomAction`] type and related functionality
 
-use crate::{menu::Screen, player::Player, items::Item, rooms::{Room, RoomTransition}};
+use serde::Deserialize;
+
+use crate::{config, menu::{Color, Menu, OptionList, Screen}, player::Player, items::{self, Item}, rooms::{Distraction, DoorState, Room}};
 
 use super::food;
 
-/// An action that can be performed in a room
-#[derive(Debug)]
+/// An action that can be performed in a room.
+/// Actions are added to a room with [`RoomState::add_action`][crate::rooms::RoomState::add_action].
+/// Each turn, the player is offered every action in their room using its [description][RoomAction::get_description],
+/// and the one they pick is [executed][RoomAction::execute]. The [`RoomActionResult`] decides whether it is offered again.
+#[derive(Debug, Clone, Deserialize)]
 pub enum RoomAction {
     /// Take the maps in the [`StrategyRoom`][Room::StrategyRoom]
     StrategyRoomTakeMaps,
@@ -22,8 +27,24 @@ pub enum RoomAction {
     BridgeHackTheMainframe,
     /// Watch the half-G volleyball in the [`MessHall`][Room::MessHall]
     MessHallWatchTheGame,
+    /// Turn up the volume on the holo-screen in the [`MessHall`][Room::MessHall], which [distracts][crate::rooms::Distraction] the crew nearby for a while
+    MessHallTurnUpTheGame,
     /// Find the [captain's diary][Item::CaptainsDiary] in the [`Bunks`][Room::Bunks]
-    BunksGetDiary
+    BunksGetDiary,
+    /// Trade with the vending machine [`Shop`][crate::shop::Shop] in the [`MessHall`][Room::MessHall]
+    MessHallUseVendingMachine,
+    /// Refill the [player's oxygen][Player::oxygen] from the tanks in the [`EngineRoom`][Room::EngineRoom]
+    EngineRoomRefillOxygen,
+    /// Switch the [ship's power][crate::rooms::RoomGraph::power_on] off or back on at the breakers in the [`EngineRoom`][Room::EngineRoom]
+    EngineRoomFlipBreakers,
+    /// Silence the ship's [`Alarm`][crate::alarm::Alarm] from the control panel on the [`Bridge`][Room::Bridge]
+    BridgeSilenceAlarm,
+    /// Put out the [fire][super::hazards::kitchen_fire] in the [`Kitchen`][Room::Kitchen] with a [fire extinguisher][Item::FireExtinguisher]
+    KitchenPutOutFire,
+    /// Cook a raw [`Food`][crate::items::Food] into a better one on the stove in the [`Kitchen`][Room::Kitchen]
+    KitchenCook,
+    /// Take a shower in the [`WashRoom`][Room::WashRoom], which makes the [player][Player::fresh_turns] feel fresh for a while
+    WashRoomShower,
 
 }
 
@@ -34,12 +55,20 @@ pub struct RoomActionResult<'a> {
     pub message: Option<Screen<'a>>,
     /// Whether this action should be listed on future turns in this room
     pub show_again: bool,
+    /// An item to give the player once the message has been shown
+    pub item: Option<Item>,
 }
 
 impl<'a> RoomActionResult<'a> {
     /// Creates a new [`RoomActionResult`] from the given optional message and whether to show the action again 
     const fn new(message: Option<Screen<'a>>, show_again: bool) -> Self {
-        Self{ message, show_again }
+        Self{ message, show_again, item: None }
+    }
+
+    /// Takes a [`RoomActionResult`] by value and returns a new one which gives the player the provided [`Item`]
+    fn with_item(mut self, item: Item) -> Self {
+        self.item = Some(item);
+        self
     }
 }
 
@@ -54,62 +83,86 @@ impl RoomAction {
             Self::CellsClimbIntoVents => "Climb into the air vent",
             Self::BridgeHackTheMainframe => "Hack the mainframe",
             Self::MessHallWatchTheGame => "Watch the game",
-            Self::BunksGetDiary => "Search underneath the beds"
+            Self::MessHallTurnUpTheGame => "Turn up the volume on the holo-screen",
+            Self::BunksGetDiary => "Search underneath the beds",
+            Self::MessHallUseVendingMachine => "Use the vending machine",
+            Self::EngineRoomRefillOxygen => "Breathe from the oxygen tanks",
+            Self::EngineRoomFlipBreakers => "Flip the main breakers",
+            Self::BridgeSilenceAlarm => "Check the alarm panel",
+            Self::KitchenPutOutFire => "Put out the fire",
+            Self::KitchenCook => "Cook something on the stove",
+            Self::WashRoomShower => "Take a shower",
+        }
+    }
+
+    /// Gets whether this action deals with the [`Hazard`][crate::rooms::Hazard] in its room.
+    /// While a room has a hazard, these are the only actions offered there.
+    pub const fn resolves_hazard(&self) -> bool {
+        matches!(self, Self::KitchenPutOutFire)
+    }
+    /// Gets the connection between two [`Room`]s which this action can open up, if there is one.
+    /// This is used by [`RoomGraph::validate`][crate::rooms::RoomGraph::validate] to check that every room can be reached.
+    pub const fn get_unlocked_connection(&self) -> Option<(Room, Room)> {
+        match self {
+            // Taking the keys unlocks the door to the escape pod
+            Self::EngineRoomTakeKeys => Some((Room::CrewArea, Room::EscapePod)),
+            Self::EscapePodTakeOff => Some((Room::EscapePod, Room::Escape)),
+            _ => None,
         }
     }
+
     /// Runs the action
     /// 
     /// ### Params:
-    /// * `player`: the [`Player`]'s state. This is used to e.g. add items to their inventory
-    pub fn execute(&self, player: &mut Player) -> RoomActionResult {
+    /// * `player`: the [`Player`]'s state. This is used to e.g. check their inventory or change the state of other rooms
+    /// * `menu`: the [`Menu`] to display to, for actions which need more input from the player
+    #[allow(clippy::too_many_lines)] // Each action needs its own arm
+    pub fn execute(&self, player: &mut Player, menu: &mut impl Menu) -> RoomActionResult<'_> {
         match self {
             Self::StrategyRoomTakeMaps => {
-                player.pick_up_item(Item::Maps);
-
                 let screen = Screen {
                     title: "You take the drive",
                     content: "You take the drive, and read its description - 'Galactic Maps 2168 Edition'",
+                    title_color: None,
+                    art: None,
                 };
-                RoomActionResult::new(Some(screen), false)
+                RoomActionResult::new(Some(screen), false).with_item(Item::Maps)
             }
             Self::EngineRoomTakeKeys => {
-                let crew_area_state = player.room_graph.get_state_mut(Room::CrewArea);
-
-                let escape_pod_index = crew_area_state
-                    .connections
-                    .iter()
-                    .position(|t|t.prompt_text == Some("Escape Pod")) 
-                    .unwrap();
-
-                crew_area_state.connections[escape_pod_index] = RoomTransition {
-                    message: "You walk up to the door, the same as any other. This time, it detects the key card in your pocket and slides open. \
-It clearly hasn't opened in scores and makes a grating sound. You would worry if there were anyone left alive.",
-                    prompt_text: None,
-                    to: Room::EscapePod
-                };
-                
-                player.pick_up_item(Item::EscapePodKeys);
+                // The escape pod door recognises the key card, so it can now be opened like any other
+                player.room_graph.set_door(Room::CrewArea, Room::EscapePod, DoorState::Closed);
 
                 let screen = Screen {
                     title: "You look through the drawers",
                     content: "You search every drawer. You don't find anything interesting until you get to the second-last one, which has a key card in it. You flip it over and it is labelled 'escape pod'.",
+                    title_color: None,
+                    art: None,
                 };
-                RoomActionResult::new(Some(screen), false)
+                RoomActionResult::new(Some(screen), false).with_item(Item::EscapePodKeys)
             }
             Self::EscapePodTakeOff => {
-                let has_maps = player.inventory.iter().any(|item|matches!(&item, Item::Maps));
+                let has_maps = player.inventory.iter().any(|(item, _)| matches!(item, Item::Maps));
 
                 if !has_maps {
                     let screen = Screen {
                         title: "You try to launch, but there's an error.",
-                        content: "\"Maps out of date: pod cannot launch without in-date maps\". You try to override the message but you can't figure it out."
+                        content: "\"Maps out of date: pod cannot launch without in-date maps\". You try to override the message but you can't figure it out.",
+                        title_color: None,
+                        art: None,
                     };
                     return RoomActionResult::new(Some(screen), true);
                 }
 
+                // Anyone who doesn't fit in the pod has to be left behind
+                if !player.choose_pod_passengers(menu) {
+                    return RoomActionResult::new(None, true);
+                }
+
                 let screen = Screen {
                     title: "You plug in the maps and blast off",
-                    content: "It's a bit anticlimactic at first but then the thrusters kick in and you feel yourself shuddering home."
+                    content: "It's a bit anticlimactic at first but then the thrusters kick in and you feel yourself shuddering home.",
+                    title_color: None,
+                    art: None,
                 };
 
                 player.room = Room::Escape;
@@ -117,54 +170,253 @@ It clearly hasn't opened in scores and makes a grating sound. You would worry if
                 RoomActionResult::new(Some(screen), false)
             }
             Self::StoreRoomFindChocolate => {
-                player.pick_up_item(food::bar_of_chocolate());
                 let screen = Screen {
                     title: "You run your hands around the top of each shelf in turn",
-                    content: "You eventually feel something - a thin, solid rectangle. You bring it into the light and read - 'Real Cacao'. You pocket it."
+                    content: "You eventually feel something - a thin, solid rectangle. You bring it into the light and read - 'Real Cacao'. You pocket it.",
+                    title_color: None,
+                    art: None,
                 };
 
-                RoomActionResult::new(Some(screen), false)
+                RoomActionResult::new(Some(screen), false).with_item(food::bar_of_chocolate())
             }
             Self::CellsClimbIntoVents => {
-                player.pick_up_item(Item::Dust);
                 let screen = Screen {
                     title: "You take out the grate and go to lift yourself up",
-                    content: "You push as hard as you can, but the opening's just not big enough."
+                    content: "You push as hard as you can, but the opening's just not big enough.",
+                    title_color: None,
+                    art: None,
                 };
 
+                RoomActionResult::new(Some(screen), true).with_item(Item::Dust)
+            }
+            Self::EngineRoomFlipBreakers => {
+                player.room_graph.power_on = !player.room_graph.power_on;
+
+                let screen = if player.room_graph.power_on {
+                    player.log("Switched the ship's power back on".to_string());
+
+                    Screen {
+                        title: "The power comes back on",
+                        content: "You push the breakers back up one by one. The lights flicker and buzz into life all over the ship, and the doors start humming again.",
+                        title_color: None,
+                        art: None,
+                    }
+                } else {
+                    player.log("Cut the ship's power at the breakers".to_string());
+
+                    Screen {
+                        title: "You cut the power",
+                        content: "You haul the main breakers down. With a dying whine the ship goes dark, apart from the red emergency strips along the floor. \
+It'll be easier to stay out of sight, but the doors won't open or close without power - they're stuck the way they are.",
+                        title_color: Some(Color::Yellow),
+                        art: None,
+                    }
+                };
+
+                // The breakers can be flipped back as many times as the player likes
+                RoomActionResult::new(Some(screen), true)
+            }
+            Self::BridgeSilenceAlarm => {
+                let screen = if player.room_graph.alarm.is_active() {
+                    player.room_graph.alarm.silence();
+                    player.log("Silenced the ship's alarm".to_string());
+
+                    Screen {
+                        title: "You silence the alarm",
+                        content: "You find the switch for the alarm and flick it off. The ship goes quiet, and the crew will soon go back to what they were doing. Try not to set it off again.",
+                        title_color: Some(Color::Green),
+                        art: None,
+                    }
+                } else {
+                    Screen {
+                        title: "The alarm panel is quiet",
+                        content: "Every light on the panel is green. As far as the ship knows, nothing is wrong.",
+                        title_color: None,
+                        art: None,
+                    }
+                };
+
+                // The alarm can be set off again, so the panel is always there
+                RoomActionResult::new(Some(screen), true)
+            }
+            Self::KitchenPutOutFire => {
+                let Some(i) = player.inventory.iter().position(|(item, _)| matches!(item, Item::FireExtinguisher)) else {
+                    let screen = Screen {
+                        title: "You look around for something to put the fire out with",
+                        content: "You try smothering the flames with a dish towel, but it catches light and you have to throw it into the sink. You'll need something better than that.",
+                        title_color: None,
+                        art: None,
+                    };
+                    return RoomActionResult::new(Some(screen), true);
+                };
+
+                player.take_item(i);
+                player.get_room_state_mut().resolve_hazard();
+                player.log("Put out the fire in the kitchen".to_string());
+
+                let screen = Screen {
+                    title: "You put out the fire",
+                    content: "You pull the pin and sweep the extinguisher across the stove until the canister splutters empty. The flames die under a blanket of foam. \
+Once the smoke clears, you spot a few gold chips glinting behind the stove.",
+                    title_color: Some(Color::Green),
+                    art: None,
+                };
+
+                RoomActionResult::new(Some(screen), false)
+            }
+            Self::KitchenCook => {
+                let cookable: Vec<_> = player
+                    .inventory
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, (item, _))| items::cook(item).map(|_| i))
+                    .collect();
+
+                if cookable.is_empty() {
+                    let screen = Screen {
+                        title: "You have nothing to cook",
+                        content: "The stove is still warm, but you haven't got anything that would be improved by cooking. A bread roll or a tin of something would do.",
+                        title_color: None,
+                        art: None,
+                    };
+                    return RoomActionResult::new(Some(screen), true);
+                }
+
+                let options: Vec<_> = cookable
+                    .iter()
+                    .map(|i| {
+                        let item = &player.inventory[*i].0;
+                        format!("{}{}{}", item.get_name(), item.get_rarity_label(), item.get_spoiled_label())
+                    })
+                    .collect();
+                let list = OptionList::new(&options, "What do you cook?").with_cancel_text("Back");
+                let Some(choice) = menu.show_option_list_cancellable(list) else {
+                    return RoomActionResult::new(None, true);
+                };
+
+                let ingredient = player.take_item(cookable[choice]);
+                // The ingredient was only offered because it can be cooked
+                let cooked = items::cook(&ingredient).unwrap();
+                player.log(format!("Cooked a {} into a {}", ingredient.get_name(), cooked.get_name()));
+
+                let screen = Screen {
+                    title: &format!("You made a {}", cooked.get_name()),
+                    content: &format!("You cook your {} on the stove. {}", ingredient.get_name(), cooked.get_description()),
+                    title_color: Some(Color::Green),
+                    art: None,
+                };
+                menu.show_screen(screen);
+
+                // There's always more to cook, so the stove is always there
+                RoomActionResult::new(None, true).with_item(cooked)
+            }
+            Self::WashRoomShower => {
+                // The shower can't be used again until the last one has worn off
+                if player.fresh_turns > 0 {
+                    let screen = Screen {
+                        title: "You're still clean",
+                        content: "You've only just got out of the shower. Any longer in there and you'll start to prune.",
+                        title_color: None,
+                        art: None,
+                    };
+                    return RoomActionResult::new(Some(screen), true);
+                }
+
+                player.fresh_turns = config::FRESH_DURATION_TURNS;
+                player.log("Took a shower in the wash room".to_string());
+
+                let screen = Screen {
+                    title: "You take a shower",
+                    content: &format!(
+                        "Six cycles of grime swirl down the drain. You step out feeling like a new person, and standing up a little straighter.\nYou have {} extra defense for the next {} turns.",
+                        config::FRESH_DEFENSE_BONUS,
+                        config::FRESH_DURATION_TURNS
+                    ),
+                    title_color: Some(Color::Green),
+                    art: None,
+                };
+                menu.show_screen(screen);
+
+                RoomActionResult::new(None, true)
+            }
+            Self::MessHallTurnUpTheGame => {
+                if player.room_graph.distraction.is_some() {
+                    let screen = Screen {
+                        title: "It won't go any louder",
+                        content: "The volume's already maxed out. The commentator's voice is rattling the cutlery on the tables.",
+                        title_color: None,
+                        art: None,
+                    };
+                    return RoomActionResult::new(Some(screen), true);
+                }
+
+                player.room_graph.distraction = Some(Distraction { room: player.room, turns: config::DISTRACTION_DURATION_TURNS });
+                player.log("Turned up the volleyball game to distract the crew".to_string());
+
+                let screen = Screen {
+                    title: "You turn the game up as loud as it goes",
+                    content: "The Vikings score and the crowd roars through every speaker in the mess hall. Down the corridors you hear doors opening and footsteps heading this way - anyone nearby will be watching the game for a while, not looking for you.",
+                    title_color: Some(Color::Green),
+                    art: None,
+                };
+
+                // The game goes on all day, so it can be turned up again once it's quietened down
                 RoomActionResult::new(Some(screen), true)
             }
             Self::BridgeHackTheMainframe => {
-                player.pick_up_item(Item::Shame);
                 let screen = Screen {
                     title: "You walk over to the computer",
                     content: "You type ' OR 1 = 1'. Nothing happens. 
 You type 'a; DROP TABLE Prisoners'. Nothing happens. 
 You type '<script>alert(\"This is easier in the movies\")</script>'. Nothing happens.
-You leave the computer and pretend nothing ever happened (which it didn't)."
+You leave the computer and pretend nothing ever happened (which it didn't).",
+                    title_color: None,
+                    art: None,
                 };
 
-                RoomActionResult::new(Some(screen), true)
+                RoomActionResult::new(Some(screen), true).with_item(Item::Shame)
             }
             Self::MessHallWatchTheGame => {
                 let screen = Screen {
                     title: "You take a seat and watch the half-G volleyball",
                     content: "That's half-G relative to Earth's g=9.8Nkg-1, of course, not the Arnithian standard of g=11Nkg-1. It's a quirk of history, really. \
 The Martian Moonmen are doing awfully well, but you know you should really be cheering for the Venutian Vikings instead. Even with half gravity it's impressive how high they punt the ball. \
-You look up and realise its been a long while since you sat down. That was a nice break, but you've got more important things to do."
+You look up and realise its been a long while since you sat down. That was a nice break, but you've got more important things to do.",
+                    title_color: None,
+                    art: None,
                 };
 
                 RoomActionResult::new(Some(screen), false)
             }
             Self::BunksGetDiary => {
-                player.pick_up_item(Item::CaptainsDiary(0));
-
                 let screen = Screen {
                     title: "You poke your head under the beds",
-                    content: "You see a small messy paper book. You take it out and read the title - 'Captain's Diary - Private'"
+                    content: "You see a small messy paper book. You take it out and read the title - 'Captain's Diary - Private'",
+                    title_color: None,
+                    art: None,
                 };
             
-                RoomActionResult::new(Some(screen), false)
+                RoomActionResult::new(Some(screen), false).with_item(Item::CaptainsDiary(0))
+            }
+            Self::MessHallUseVendingMachine => {
+                // Take the shop out of the room to avoid multiple mutable references
+                let mut shop = player.get_room_state_mut().shop.take().unwrap();
+                shop.trade(player, menu);
+                player.get_room_state_mut().shop = Some(shop);
+
+                RoomActionResult::new(None, true)
+            }
+            Self::EngineRoomRefillOxygen => {
+                player.oxygen = config::PLAYER_MAX_OXYGEN;
+
+                let screen = Screen {
+                    title: "You find a valve on one of the oxygen tanks",
+                    content: "You crack it open and take a few deep breaths. Your head clears and you feel ready to go on.",
+                    title_color: None,
+                    art: None,
+                };
+
+                RoomActionResult::new(Some(screen), true)
             }
         }
     }
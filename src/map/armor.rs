@@ -0,0 +1,45 @@
+//! Functions to create [`Armor`] items
+
+use crate::{
+    combat::Damage,
+    items::{Armor, ArmorSlot, Item, Rarity},
+};
+
+/// Creates a new 'padded jacket' item
+pub(super) const fn padded_jacket() -> Item {
+    Item::Armor(Armor {
+        name: "Padded Jacket",
+        description: "A thick quilted jacket, meant for working in the cold parts of the ship. It should take the edge off a hit.",
+        defense: Damage::new(1),
+        slot: ArmorSlot::Body,
+        weight: 2,
+        value: 4,
+        rarity: Rarity::Common,
+    })
+}
+
+/// Creates a new 'flak vest' item
+pub(super) const fn flak_vest() -> Item {
+    Item::Armor(Armor {
+        name: "Flak Vest",
+        description: "A heavy armoured vest with ceramic plates sewn into it. It's old, but it'll still stop a blaster bolt or two.",
+        defense: Damage::new(2),
+        slot: ArmorSlot::Body,
+        weight: 5,
+        value: 10,
+        rarity: Rarity::Common,
+    })
+}
+
+/// Creates a new 'serving tray' item
+pub(super) const fn serving_tray() -> Item {
+    Item::Armor(Armor {
+        name: "Serving Tray",
+        description: "A dented metal tray from the kitchen. Held up in your off hand, it makes a passable shield.",
+        defense: Damage::new(1),
+        slot: ArmorSlot::OffHand,
+        weight: 1,
+        value: 2,
+        rarity: Rarity::Common,
+    })
+}
@@ -1,6 +1,7 @@
-//! Functions to create [enemies][Enemy]
+//! Functions to create the base [enemies][Enemy], before they are [scaled][Enemy::scaled] to suit the difficulty and the player's level
 
-use crate::combat::{Enemy, Health};
+use crate::combat::{AttackPattern, BossPhase, Damage, Enemy, Health, PatternStep, Resistance};
+use crate::items::{DamageType, Item};
 
 use super::weapons;
 
@@ -9,10 +10,24 @@ pub(super) fn cook() -> Enemy {
     Enemy {
         name: "Cook",
         description: "The ship's cook. There's not much to do when there aren't any troops, so they're sitting around watching the game.",
-        inventory: vec![weapons::standard_blaster()],
+        inventory: vec![weapons::standard_blaster(), Item::Currency(5)],
+        drops: Vec::new(),
 
         health: Health::new(7),
         max_health: Health::new(7),
+        xp_reward: 5,
+        bonus_damage: Damage::new(0),
+        accuracy: 80,
+        evasion: 0,
+        // Their apron won't stop a blade
+        resistances: &[(DamageType::Sharp, Resistance::Weak)],
+        // They'd rather get back to the game than fight
+        attack_pattern: AttackPattern::Defensive,
+        phases: &[],
+        phase: 0,
+        alert: false,
+        // They only have eyes for the game
+        awareness: 20,
     }
 }
 
@@ -22,21 +37,130 @@ pub(super) fn mechanic() -> Enemy {
         name: "Mechanic",
         description: "The ship's mechanic. They check the ship every cycle and fix anything that's broken. They were previously a high ranking ISPD officer and still carry a taser everywhere with them. \
 At the moment they're checking the ship's comms, while listening to music through a pair of particularly bulky headphones. Bad practice, of course, but you don't mind.",
-        inventory: vec![weapons::ispd_taser()],
+        inventory: vec![weapons::ispd_taser(), Item::Currency(8)],
+        drops: Vec::new(),
 
         health: Health::new(10),
         max_health: Health::new(10),
+        xp_reward: 8,
+        bonus_damage: Damage::new(0),
+        accuracy: 90,
+        evasion: 5,
+        // Their overalls are insulated, but the wrench is what they're used to being on the other end of
+        resistances: &[(DamageType::Energy, Resistance::Resistant), (DamageType::Blunt, Resistance::Weak)],
+        // They're still moving to the beat of their music
+        attack_pattern: AttackPattern::Cyclic(&[
+            PatternStep::AttackLeft,
+            PatternStep::AttackStraight,
+            PatternStep::DodgeLeft,
+            PatternStep::Nothing,
+            PatternStep::AttackRight,
+            PatternStep::DodgeRight,
+        ]),
+        phases: &[],
+        phase: 0,
+        alert: false,
+        // They can't hear a thing over their music
+        awareness: 10,
     }
 }
 
-/// Creates a new 'skipper' enemy
+/// The phases the [skipper] goes through as they're worn down
+const SKIPPER_PHASES: &[BossPhase] = &[
+    BossPhase {
+        health_percent: 60,
+        title: "The Skipper stops holding back",
+        content: "The Skipper wipes a trickle of blood from their lip and laughs. \"I didn't survive nine years of war to be beaten by a stowaway.\" \
+They fall into a fighting stance you recognise from the academy - left, centre, right, centre, over and over. They're hitting harder now.",
+        attack_pattern: Some(AttackPattern::Cyclic(&[
+            PatternStep::AttackStraight,
+            PatternStep::AttackLeft,
+            PatternStep::AttackStraight,
+            PatternStep::AttackRight,
+        ])),
+        heal_percent: 0,
+        bonus_damage: Damage::new(1),
+    },
+    BossPhase {
+        health_percent: 25,
+        title: "The Skipper makes a last stand",
+        content: "The Skipper staggers back against the strategy table and jams a stim pen into their thigh. Their eyes go wide and wild. \
+Whatever discipline they had is gone - now they just want you dead.",
+        attack_pattern: Some(AttackPattern::Aggressive),
+        heal_percent: 30,
+        bonus_damage: Damage::new(0),
+    },
+];
+
+/// Creates a new 'skipper' enemy. They are a boss, and fight harder as they are worn down - see [`SKIPPER_PHASES`].
 pub(super) fn skipper() -> Enemy {
     Enemy {
         name: "Skipper",
         description: "The ship's captain. Having served in the 2143-2152 inter-system war, they have great experience in combat. On the other hand, they're very good at forgetting things.",
-        inventory: vec![weapons::captains_blaster()],
+        inventory: vec![weapons::captains_blaster(), Item::Currency(15)],
+        drops: Vec::new(),
 
         health: Health::new(15),
         max_health: Health::new(15),
+        xp_reward: 15,
+        bonus_damage: Damage::new(0),
+        accuracy: 100,
+        evasion: 10,
+        // Their dress uniform is lined with a cut-proof weave
+        resistances: &[(DamageType::Sharp, Resistance::Resistant)],
+        attack_pattern: AttackPattern::Aggressive,
+        phases: SKIPPER_PHASES,
+        phase: 0,
+        alert: false,
+        awareness: 40,
+    }
+}
+
+/// Creates a new 'frightened crew member' enemy. They only fight if the player [threatens them][super::npcs::frightened_crew_member].
+pub(super) fn frightened_crew_member() -> Enemy {
+    Enemy {
+        name: "Frightened Crew Member",
+        description: "A young technician who was hiding in the bunks. They're shaking, but they're cornered and they know it.",
+        inventory: vec![weapons::eating_knife(), Item::Currency(2)],
+        drops: Vec::new(),
+
+        health: Health::new(4),
+        max_health: Health::new(4),
+        xp_reward: 2,
+        bonus_damage: Damage::new(0),
+        accuracy: 70,
+        evasion: 10,
+        resistances: &[],
+        // They'd rather not be hit than hit you
+        attack_pattern: AttackPattern::Defensive,
+        phases: &[],
+        phase: 0,
+        alert: false,
+        awareness: 30,
+    }
+}
+
+/// Creates a new 'patrolling guard' enemy. Unlike the other enemies, guards aren't placed in a room, and instead [ambush the player at random][crate::player::Player::roll_random_encounter].
+pub fn patrolling_guard() -> Enemy {
+    Enemy {
+        name: "Patrolling Guard",
+        description: "One of the few soldiers left on board. They walk the same route round the ship every cycle, and they look bored of it.",
+        inventory: vec![weapons::standard_blaster(), Item::Currency(3)],
+        // Guards carry a spare key card, so the escape pod can be reached without searching the engine room
+        drops: vec![Item::EscapePodKeys],
+
+        health: Health::new(6),
+        max_health: Health::new(6),
+        xp_reward: 4,
+        bonus_damage: Damage::new(0),
+        accuracy: 85,
+        evasion: 5,
+        // Their helmet and padding soak up knocks, but their armor conducts
+        resistances: &[(DamageType::Blunt, Resistance::Resistant), (DamageType::Energy, Resistance::Weak)],
+        attack_pattern: AttackPattern::Random,
+        phases: &[],
+        phase: 0,
+        alert: false,
+        awareness: 30,
     }
 }
@@ -2,15 +2,20 @@
 
 use crate::{
     combat::Damage,
-    items::{Food, Item},
+    items::{Food, Item, Rarity},
 };
 
-/// Creates a new 'bread roll' item
+/// Creates a new 'bread roll' item. These are baked in the [`Kitchen`][crate::rooms::Room::Kitchen], so they're fresher than food found elsewhere.
 pub(super) const fn bread_roll() -> Item {
     Item::Food(Food {
         name: "Bread roll",
         description: "A soft white bread roll. It's tasty, but not substantial.",
         heals_for: Damage::new(5),
+        fills_for: 12,
+        weight: 1,
+        value: 2,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 25,
     })
 }
 
@@ -20,5 +25,52 @@ pub(super) const fn bar_of_chocolate() -> Item {
         name: "Bar of Chocolate",
         description: "A bar of dark chocolate. It says on the label that it's made from real cacao, bred from plants that trace their lineage all the way back to Earth!",
         heals_for: Damage::new(10),
+        fills_for: 6,
+        weight: 1,
+        value: 6,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 15,
+    })
+}
+
+/// Creates a new 'protein bar' item
+pub(super) const fn protein_bar() -> Item {
+    Item::Food(Food {
+        name: "Protein Bar",
+        description: "A dense brown bar wrapped in silver foil. It tastes like cardboard, but it keeps you going.",
+        heals_for: Damage::new(3),
+        fills_for: 10,
+        weight: 1,
+        value: 3,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 20,
+    })
+}
+
+/// Creates a new 'can of synth-cola' item
+pub(super) const fn synth_cola() -> Item {
+    Item::Food(Food {
+        name: "Can of Synth-Cola",
+        description: "A can of fizzy brown liquid. It's mostly sugar, which is exactly what you need right now.",
+        heals_for: Damage::new(4),
+        fills_for: 2,
+        weight: 1,
+        value: 2,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 20,
+    })
+}
+
+/// Creates a new 'tin of peaches' item
+pub(super) const fn tin_of_peaches() -> Item {
+    Item::Food(Food {
+        name: "Tin of Peaches",
+        description: "A dented tin with a faded label. It's years out of date, but tinned food lasts forever.",
+        heals_for: Damage::new(6),
+        fills_for: 8,
+        weight: 1,
+        value: 4,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 30,
     })
 }
\ No newline at end of file
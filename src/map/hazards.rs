@@ -0,0 +1,16 @@
+//! Functions to create [hazards][Hazard]
+
+use crate::combat::Damage;
+use crate::items::Item;
+use crate::rooms::Hazard;
+
+/// Creates a new 'kitchen fire' hazard, which is put out with the [`KitchenPutOutFire`][super::RoomAction::KitchenPutOutFire] action
+pub(super) const fn kitchen_fire() -> Hazard {
+    Hazard {
+        name: "The heat from the stove scorches you",
+        description: "A pan of synthetic oil has caught light on the stove, and the flames are licking at the ceiling. Everything on the counters is out of reach, and the heat is unbearable.",
+        damage: Damage::new(1),
+        // The cook dropped their tips behind the stove
+        reveals: &[Item::Currency(4)],
+    }
+}
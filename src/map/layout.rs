@@ -0,0 +1,296 @@
+//! Loads the ship's layout from a JSON file at [`LAYOUT_PATH`][config::LAYOUT_PATH], so the ship can be changed without recompiling.
+//! If there is no file, the built-in [`ROOMS`][super::rooms::ROOMS] are used.
+//!
+//! The file holds a list of rooms, each of which is written like this:
+//! ```json
+//! {
+//!     "room": "EngineRoom",
+//!     "connections": [
+//!         { "to": "LowerCorridor", "direction": "North", "message": "You leave the engine room.", "door": "Closed" }
+//!     ],
+//!     "items": ["wrench", { "gold": 6 }],
+//!     "hidden_items": ["repair_kit"],
+//!     "actions": ["EngineRoomTakeKeys"],
+//!     "enemy": "mechanic",
+//!     "shop": { "name": "Vending Machine", "stock": ["protein_bar"] },
+//!     "trap": "tripwire",
+//!     "npc": "frightened_crew_member",
+//!     "hazard": "kitchen_fire"
+//! }
+//! ```
+//! * `room` and `to` are [`Room`] names, `direction` is a [`Direction`], `door` is a [`DoorState`] and each action is a [`RoomAction`]. They are written exactly as the variants are named.
+//! * Only `room` and `connections` are needed. A connection's `door` is open if it isn't given, and it can also have a `prompt_text` to show instead of the name of the room.
+//!   A locked door can be given `lock_pins` to let the player pick its lock - the more pins, the harder it is.
+//! * Items, enemies, traps, NPCs and hazards are the ones already in the game, and are written as the names in [`ITEMS`], [`ENEMIES`], [`TRAPS`], [`NPCS`] and [`HAZARDS`].
+//!   A hazard can only be dealt with by an action which [resolves it][RoomAction::resolves_hazard], so that action should be in the same room. Gold is written as `{ "gold": <amount> }`.
+//!
+//! `ship.example.json` has the built-in layout written out in full, as a starting point for changes.
+
+use std::fmt::Display;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::combat::Enemy;
+use crate::config;
+use crate::items::Item;
+use crate::npc::Npc;
+use crate::rooms::{Direction, DoorState, Hazard, Room, RoomTransition, Trap, ValidationProblem};
+
+use super::{armor, enemies, food, hazards, npcs, traps, weapons, RoomAction, RoomDefinition, ShopDefinition};
+
+/// A table of things which can be put in a layout file, each with the name it is written as
+type NameTable<T> = &'static [(&'static str, T)];
+
+/// The items which can be put in a layout file or spawned in [developer mode][crate::dev], by the name they are written as
+pub const ITEMS: NameTable<Item> = &[
+    ("intruders_blaster", weapons::intruders_blaster()),
+    ("captains_blaster", weapons::captains_blaster()),
+    ("standard_blaster", weapons::standard_blaster()),
+    ("ispd_taser", weapons::ispd_taser()),
+    ("throwing_dart_set", weapons::throwing_dart_set()),
+    ("shaving_razor", weapons::shaving_razor()),
+    ("wrench", weapons::wrench()),
+    ("eating_knife", weapons::eating_knife()),
+    ("padded_jacket", armor::padded_jacket()),
+    ("flak_vest", armor::flak_vest()),
+    ("serving_tray", armor::serving_tray()),
+    ("bread_roll", food::bread_roll()),
+    ("bar_of_chocolate", food::bar_of_chocolate()),
+    ("protein_bar", food::protein_bar()),
+    ("synth_cola", food::synth_cola()),
+    ("tin_of_peaches", food::tin_of_peaches()),
+    ("bandage", Item::Bandage),
+    ("explosive", Item::Explosive),
+    ("repair_kit", Item::RepairKit),
+    ("ship_map", Item::ShipMap),
+    ("lockpick", Item::Lockpick),
+    ("fire_extinguisher", Item::FireExtinguisher),
+];
+
+/// The enemies which can be put in a layout file, by the name they are written as
+const ENEMIES: NameTable<fn() -> Enemy> = &[
+    ("cook", enemies::cook),
+    ("mechanic", enemies::mechanic),
+    ("skipper", enemies::skipper),
+    ("frightened_crew_member", enemies::frightened_crew_member),
+    ("patrolling_guard", enemies::patrolling_guard),
+];
+
+/// The traps which can be put in a layout file, by the name they are written as
+const TRAPS: NameTable<fn() -> Trap> = &[("tripwire", traps::tripwire)];
+
+/// The NPCs which can be put in a layout file, by the name they are written as
+const NPCS: NameTable<fn() -> Npc> = &[("frightened_crew_member", npcs::frightened_crew_member)];
+
+/// The hazards which can be put in a layout file, by the name they are written as
+const HAZARDS: NameTable<fn() -> Hazard> = &[("kitchen_fire", hazards::kitchen_fire)];
+
+/// A layout file, as it is written
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutFile {
+    /// Every room in the ship
+    rooms: Vec<RoomFile>,
+}
+
+/// A room in a layout file, as it is written
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoomFile {
+    /// Which room this is
+    room: Room,
+    /// Which other rooms the player can go to from this one
+    connections: Vec<ConnectionFile>,
+    /// The items lying in the room
+    #[serde(default)]
+    items: Vec<ItemFile>,
+    /// The items the player finds by searching the room
+    #[serde(default)]
+    hidden_items: Vec<ItemFile>,
+    /// The actions which can be performed in the room
+    #[serde(default)]
+    actions: Vec<RoomAction>,
+    /// The name of the enemy in the room, if there is one
+    enemy: Option<String>,
+    /// The shop in the room, if there is one
+    shop: Option<ShopFile>,
+    /// The name of the trap in the room, if there is one
+    trap: Option<String>,
+    /// The name of the NPC in the room, if there is one
+    npc: Option<String>,
+    /// The name of the hazard in the room, if there is one
+    hazard: Option<String>,
+}
+
+/// A connection between rooms in a layout file, as it is written
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConnectionFile {
+    /// Which room the connection goes to
+    to: Room,
+    /// Which way the room is
+    direction: Direction,
+    /// The message to show when the player goes through
+    message: String,
+    /// The option to show the player instead of the name of the room
+    prompt_text: Option<String>,
+    /// Whether the door starts open, closed or locked
+    #[serde(default)]
+    door: DoorState,
+    /// How many pins the door's lock has, or 0 if it can't be picked
+    #[serde(default)]
+    lock_pins: usize,
+}
+
+/// An item in a layout file, as it is written
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ItemFile {
+    /// An item from [`ITEMS`], by name
+    Named(String),
+    /// A pile of gold
+    Gold {
+        /// How much gold there is
+        gold: u32,
+    },
+}
+
+/// A shop in a layout file, as it is written
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShopFile {
+    /// The name of the shop
+    name: String,
+    /// What the shop has for sale
+    stock: Vec<ItemFile>,
+}
+
+/// A reason a layout file couldn't be loaded
+#[derive(Debug)]
+pub enum LayoutError {
+    /// The file couldn't be read
+    Io(std::io::Error),
+    /// The file isn't valid JSON, or doesn't match the layout schema
+    Json(serde_json::Error),
+    /// An item, enemy, trap, NPC or hazard was given which doesn't exist. Holds what kind of thing it was and its name.
+    UnknownName(&'static str, String),
+    /// The same room was given more than once
+    DuplicateRoom(Room),
+    /// The rooms don't fit together into a playable ship
+    Invalid(Vec<ValidationProblem>),
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "it couldn't be read ({e})"),
+            Self::Json(e) => write!(f, "it isn't a valid layout ({e})"),
+            Self::UnknownName(kind, name) => write!(f, "there is no {kind} called \"{name}\""),
+            Self::DuplicateRoom(room) => write!(f, "{room:?} is in it more than once"),
+            Self::Invalid(problems) => {
+                write!(f, "the rooms don't make a playable ship:")?;
+                for problem in problems {
+                    write!(f, "\n* {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Finds the thing with the given name in a table of names, or returns an error saying there isn't one
+fn find<T: Clone>(table: NameTable<T>, kind: &'static str, name: &str) -> Result<T, LayoutError> {
+    table
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, thing)| thing.clone())
+        .ok_or_else(|| LayoutError::UnknownName(kind, name.to_string()))
+}
+
+/// Turns items from a layout file into a list which lives for the rest of the program
+fn leak_items(items: Vec<ItemFile>) -> Result<&'static [Item], LayoutError> {
+    let items = items
+        .into_iter()
+        .map(|item| match item {
+            ItemFile::Named(name) => find(ITEMS, "item", &name),
+            ItemFile::Gold { gold } => Ok(Item::Currency(gold)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items.leak())
+}
+
+/// Turns a string from a layout file into one which lives for the rest of the program
+fn leak_str(text: String) -> &'static str {
+    text.leak()
+}
+
+impl RoomFile {
+    /// Turns the room into a [`RoomDefinition`], looking up everything given by name.
+    /// The layout is only loaded once, so its contents are leaked to give them the same `'static` lifetime as the built-in rooms.
+    fn into_definition(self) -> Result<RoomDefinition, LayoutError> {
+        let connections: Vec<_> = self
+            .connections
+            .into_iter()
+            .map(|connection| RoomTransition {
+                message: leak_str(connection.message),
+                to: connection.to,
+                direction: connection.direction,
+                prompt_text: connection.prompt_text.map(leak_str),
+                door: connection.door,
+                lock_pins: connection.lock_pins,
+            })
+            .collect();
+
+        let shop = match self.shop {
+            Some(shop) => Some(ShopDefinition { name: leak_str(shop.name), stock: leak_items(shop.stock)? }),
+            None => None,
+        };
+
+        Ok(RoomDefinition {
+            items: leak_items(self.items)?,
+            hidden_items: leak_items(self.hidden_items)?,
+            actions: self.actions.leak(),
+            enemy: self.enemy.map(|name| find(ENEMIES, "enemy", &name)).transpose()?,
+            shop,
+            trap: self.trap.map(|name| find(TRAPS, "trap", &name)).transpose()?,
+            npc: self.npc.map(|name| find(NPCS, "NPC", &name)).transpose()?,
+            hazard: self.hazard.map(|name| find(HAZARDS, "hazard", &name)).transpose()?,
+            ..RoomDefinition::new(self.room, connections.leak())
+        })
+    }
+}
+
+/// Reads a layout from the text of a layout file, and checks that it makes a playable ship
+pub(super) fn parse(text: &str) -> Result<&'static [RoomDefinition], LayoutError> {
+    let file: LayoutFile = serde_json::from_str(text).map_err(LayoutError::Json)?;
+
+    let mut definitions: Vec<RoomDefinition> = Vec::new();
+    for room in file.rooms {
+        if definitions.iter().any(|d| d.room == room.room) {
+            return Err(LayoutError::DuplicateRoom(room.room));
+        }
+        definitions.push(room.into_definition()?);
+    }
+
+    let problems: Vec<_> = super::build_graph(&definitions)
+        .validate()
+        .into_iter()
+        .filter(|problem| problem.is_fatal())
+        .collect();
+    if !problems.is_empty() {
+        return Err(LayoutError::Invalid(problems));
+    }
+
+    Ok(definitions.leak())
+}
+
+/// Reads the text of the layout file at [`LAYOUT_PATH`][config::LAYOUT_PATH], or returns [`None`] if there isn't one
+pub(super) fn read() -> Result<Option<String>, LayoutError> {
+    match fs::read_to_string(config::LAYOUT_PATH) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(LayoutError::Io(e)),
+    }
+}
@@ -0,0 +1,69 @@
+//! Functions to create [npcs][Npc]
+
+use crate::combat::{Damage, Health};
+use crate::items::DamageType;
+use crate::npc::{DialogueEffect::*, DialogueNode, Fighter, Npc, Response};
+use crate::rooms::Room;
+
+use super::{enemies, food};
+
+/// Creates a new 'frightened crew member' npc, who is hiding in the [`Bunks`][Room::Bunks].
+/// They can tell the player where the escape pod key is, give them some food and then escape with them, or be threatened into a fight.
+/// Once they've joined the player, they help out in fights with a spanner, but they're no soldier.
+pub(super) const fn frightened_crew_member() -> Npc {
+    Npc::new("Frightened Crew Member", &[
+        // 0
+        DialogueNode {
+            text: "Please don't hurt me! I'm just a technician. I never even wanted to be posted on this ship.",
+            responses: &[
+                Response { text: "I won't hurt you. I just want to get off this ship.", next: Some(1), effects: &[] },
+                Response { text: "Hand over everything you've got.", next: Some(3), effects: &[] },
+                Response { text: "Stay quiet and you'll be fine.", next: None, effects: &[] },
+            ],
+        },
+        // 1
+        DialogueNode {
+            text: "The escape pod's off the crew area, but it's locked. The mechanic keeps the key card somewhere in the engine room, at the end of the lower corridor.",
+            responses: &[
+                Response { text: "Thank you. Is there anything else that could help?", next: Some(2), effects: &[RevealRoom(Room::EngineRoom)] },
+                Response { text: "Thanks. Stay hidden.", next: None, effects: &[RevealRoom(Room::EngineRoom)] },
+            ],
+        },
+        // 2
+        DialogueNode {
+            text: "Here, take this. It's not much, but I won't be needing it in here.",
+            responses: &[
+                Response { text: "Thank you. Good luck.", next: None, effects: &[GiveItem(food::protein_bar), SetStartNode(4)] },
+            ],
+        },
+        // 3
+        DialogueNode {
+            text: "I - I don't have anything! Stay back! I'm warning you!",
+            responses: &[
+                Response { text: "Have it your way.", next: None, effects: &[StartFight(enemies::frightened_crew_member)] },
+                Response { text: "Fine. Forget it.", next: None, effects: &[] },
+            ],
+        },
+        // 4
+        DialogueNode {
+            text: "Good luck out there. I'm staying right here until this is all over.",
+            responses: &[
+                Response { text: "There's room for two in the escape pod. Come with me.", next: Some(5), effects: &[] },
+                Response { text: "Take care.", next: None, effects: &[] },
+            ],
+        },
+        // 5
+        DialogueNode {
+            text: "You'd really take me with you? I... alright. I'll stay close behind you.",
+            responses: &[
+                Response { text: "Let's go.", next: None, effects: &[JoinPlayer] },
+            ],
+        },
+    ])
+    .with_fighter(Fighter {
+        health: Health::new(8),
+        max_health: Health::new(8),
+        damage: Damage::new(1),
+        damage_type: DamageType::Blunt,
+    })
+}
@@ -0,0 +1,125 @@
+//! The table of every room in the ship, which [`init`][super::init] builds the [`RoomGraph`][crate::rooms::RoomGraph] from
+
+use crate::items::Item;
+use crate::rooms::Room;
+
+use super::transitions::*;
+use super::{armor, enemies, food, hazards, npcs, traps, weapons, RoomAction, RoomDefinition, ShopDefinition};
+
+/// Every room in the ship, as it is at the start of each loop
+pub(super) const ROOMS: &[RoomDefinition] = &[
+    // The bridge
+    RoomDefinition {
+        items: &[weapons::intruders_blaster()],
+        actions: &[RoomAction::BridgeHackTheMainframe, RoomAction::BridgeSilenceAlarm],
+        ..RoomDefinition::new(Room::Bridge, &[BRIDGE_TO_UPPER_CORRIDOR])
+    },
+    // The upper corridor
+    RoomDefinition::new(
+        Room::UpperCorridor,
+        &[
+            UPPER_CORRIDOR_TO_BRIDGE,
+            UPPER_CORRIDOR_TO_STRATEGY_ROOM,
+            UPPER_CORRIDOR_TO_CELLS,
+            UPPER_CORRIDOR_TO_MESS_HALL,
+        ],
+    ),
+    // The strategy room
+    RoomDefinition {
+        enemy: Some(enemies::skipper),
+        items: &[armor::flak_vest(), Item::ShipMap],
+        hidden_items: &[Item::Explosive],
+        actions: &[RoomAction::StrategyRoomTakeMaps],
+        ..RoomDefinition::new(Room::StrategyRoom, &[STRATEGY_ROOM_TO_UPPER_CORRIDOR])
+    },
+    // The cells
+    RoomDefinition {
+        actions: &[RoomAction::CellsClimbIntoVents],
+        ..RoomDefinition::new(Room::Cells, &[CELLS_TO_UPPER_CORRIDOR])
+    },
+    // The mess hall
+    RoomDefinition {
+        enemy: Some(enemies::cook),
+        items: &[Item::FireExtinguisher],
+        shop: Some(ShopDefinition {
+            name: "Vending Machine",
+            stock: &[food::protein_bar(), food::protein_bar(), food::synth_cola()],
+        }),
+        actions: &[RoomAction::MessHallWatchTheGame, RoomAction::MessHallTurnUpTheGame, RoomAction::MessHallUseVendingMachine],
+        ..RoomDefinition::new(
+            Room::MessHall,
+            &[
+                MESS_HALL_TO_UPPER_CORRIDOR,
+                MESS_HALL_TO_KITCHEN,
+                MESS_HALL_TO_STAIRWELL,
+            ],
+        )
+    },
+    // The kitchen
+    RoomDefinition {
+        items: &[food::bread_roll(), weapons::eating_knife(), armor::serving_tray()],
+        actions: &[RoomAction::KitchenPutOutFire, RoomAction::KitchenCook],
+        hazard: Some(hazards::kitchen_fire),
+        ..RoomDefinition::new(Room::Kitchen, &[KITCHEN_TO_MESS_HALL])
+    },
+    // The stairwell
+    RoomDefinition::new(
+        Room::Stairwell,
+        &[STAIRWELL_TO_MESS_HALL, STAIRWELL_TO_CREW_AREA],
+    ),
+    // The crew area
+    RoomDefinition::new(
+        Room::CrewArea,
+        &[
+            CREW_AREA_TO_STAIRWELL,
+            CREW_AREA_TO_STORE_ROOM,
+            CREW_AREA_TO_ESCAPE_POD,
+            CREW_AREA_TO_LOWER_CORRIDOR,
+        ],
+    ),
+    // The store room
+    RoomDefinition {
+        hidden_items: &[food::tin_of_peaches(), Item::Bandage],
+        actions: &[RoomAction::StoreRoomFindChocolate],
+        trap: Some(traps::tripwire),
+        ..RoomDefinition::new(Room::StoreRoom, &[STORE_ROOM_TO_CREW_AREA])
+    },
+    // The lower corridor
+    RoomDefinition::new(
+        Room::LowerCorridor,
+        &[
+            LOWER_CORRIDOR_TO_CREW_AREA,
+            LOWER_CORRIDOR_TO_BUNKS,
+            LOWER_CORRIDOR_TO_WASH_ROOM,
+            LOWER_CORRIDOR_TO_ENGINE_ROOM,
+        ],
+    ),
+    // The bunks
+    RoomDefinition {
+        items: &[weapons::throwing_dart_set()],
+        hidden_items: &[armor::padded_jacket(), Item::Currency(6)],
+        actions: &[RoomAction::BunksGetDiary],
+        npc: Some(npcs::frightened_crew_member),
+        ..RoomDefinition::new(Room::Bunks, &[BUNKS_TO_LOWER_CORRIDOR])
+    },
+    // The wash room
+    RoomDefinition {
+        items: &[weapons::shaving_razor(), Item::Bandage],
+        hidden_items: &[Item::Lockpick],
+        actions: &[RoomAction::WashRoomShower],
+        ..RoomDefinition::new(Room::WashRoom, &[WASH_ROOM_TO_LOWER_CORRIDOR])
+    },
+    // The engine room
+    RoomDefinition {
+        enemy: Some(enemies::mechanic),
+        items: &[weapons::wrench()],
+        hidden_items: &[Item::RepairKit],
+        actions: &[RoomAction::EngineRoomTakeKeys, RoomAction::EngineRoomRefillOxygen, RoomAction::EngineRoomFlipBreakers],
+        ..RoomDefinition::new(Room::EngineRoom, &[ENGINE_ROOM_TO_LOWER_CORRIDOR])
+    },
+    // The escape pod
+    RoomDefinition {
+        actions: &[RoomAction::EscapePodTakeOff],
+        ..RoomDefinition::new(Room::EscapePod, &[ESCAPE_POD_TO_CREW_AREA])
+    },
+];
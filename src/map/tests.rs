@@ -0,0 +1,77 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that the example layout file is the same as the built-in layout, so that it stays a good starting point
+#[test]
+fn test_example_layout() {
+    let loaded = build_graph(layout::parse(include_str!("../../ship.example.json")).unwrap());
+    let built_in = build_graph(rooms::ROOMS);
+
+    assert_eq!(loaded.rooms.len(), built_in.rooms.len());
+    for (room, expected) in &built_in.rooms {
+        let state = loaded.get_state(*room);
+
+        assert_eq!(state.items, expected.items);
+        assert_eq!(state.hidden_items, expected.hidden_items);
+        assert_eq!(state.actions.len(), expected.actions.len());
+        assert_eq!(state.enemy.as_ref().map(|e| e.name), expected.enemy.as_ref().map(|e| e.name));
+        assert_eq!(state.shop.is_some(), expected.shop.is_some());
+        assert_eq!(state.trap.is_some(), expected.trap.is_some());
+        assert_eq!(state.npc.is_some(), expected.npc.is_some());
+        assert_eq!(state.hazard.is_some(), expected.hazard.is_some());
+
+        let connections = |state: &RoomState| state.connections.iter().map(|c| (c.to, c.direction, c.message, c.door, c.lock_pins)).collect::<Vec<_>>();
+        assert_eq!(connections(state), connections(expected));
+    }
+}
+
+/// Tests that mistakes in a layout file are reported rather than loaded
+#[test]
+fn test_invalid_layout() {
+    assert!(matches!(layout::parse("{ \"rooms\": [ }"), Err(LayoutError::Json(_))));
+
+    let unknown_item = r#"{ "rooms": [{ "room": "Cells", "connections": [], "items": ["teapot"] }] }"#;
+    assert!(matches!(layout::parse(unknown_item), Err(LayoutError::UnknownName("item", name)) if name == "teapot"));
+
+    let duplicate = r#"{ "rooms": [{ "room": "Cells", "connections": [] }, { "room": "Cells", "connections": [] }] }"#;
+    assert!(matches!(layout::parse(duplicate), Err(LayoutError::DuplicateRoom(Room::Cells))));
+
+    // The player can't get anywhere from the cells, let alone escape
+    let unwinnable = r#"{ "rooms": [{ "room": "Cells", "connections": [] }] }"#;
+    assert!(matches!(layout::parse(unwinnable), Err(LayoutError::Invalid(_))));
+}
+
+/// Tests that every enemy is strictly tougher on hard difficulty than on relaxed, and is left as it is on normal
+#[test]
+fn test_scaled_enemies() {
+    use crate::balance::DynamicBalance;
+    use crate::difficulty::Difficulty;
+
+    let templates: [fn() -> Enemy; 5] = [
+        enemies::cook,
+        enemies::mechanic,
+        enemies::skipper,
+        enemies::frightened_crew_member,
+        enemies::patrolling_guard,
+    ];
+
+    for template in templates {
+        let relaxed = Enemy::scaled(template(), Difficulty::Relaxed, 1, DynamicBalance::default());
+        let hard = Enemy::scaled(template(), Difficulty::Hard, 1, DynamicBalance::default());
+
+        assert!(hard.max_health > relaxed.max_health, "{}", hard.name);
+        assert!(hard.bonus_damage > relaxed.bonus_damage, "{}", hard.name);
+        assert!(hard.xp_reward > relaxed.xp_reward, "{}", hard.name);
+        assert_eq!(hard.health, hard.max_health);
+
+        let normal = Enemy::scaled(template(), Difficulty::Normal, 1, DynamicBalance::default());
+        assert_eq!(normal.max_health, template().max_health);
+        assert_eq!(normal.xp_reward, template().xp_reward);
+
+        // Enemies keep up with the player as they level up
+        let levelled = Enemy::scaled(template(), Difficulty::Normal, 5, DynamicBalance::default());
+        assert!(levelled.max_health > normal.max_health);
+        assert!(levelled.bonus_damage > normal.bonus_damage);
+    }
+}
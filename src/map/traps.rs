@@ -0,0 +1,14 @@
+//! Functions to create [traps][Trap]
+
+use crate::combat::Damage;
+use crate::rooms::Trap;
+
+/// Creates a new 'tripwire' trap, which is set up in the dark of the store room
+pub(super) const fn tripwire() -> Trap {
+    Trap {
+        name: "Your foot catches on something in the dark",
+        description: "Someone has strung a wire across the doorway. You stumble, and a crate of spare parts topples off a shelf onto your back.",
+        damage: Damage::new(2),
+        one_shot: true,
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::{
     combat::Damage,
-    items::{Item, Weapon},
+    items::{AttackStyle, DamageType, Item, Rarity, Weapon},
 };
 
 /// Creates a new 'intruders blaster' item
@@ -11,9 +11,20 @@ pub(super) const fn intruders_blaster() -> Item {
         name: "Intruders Blaster",
         description: "An energy weapon kept on the wall in the bridge to use if an enemy boards the ship.",
 
+        damage_type: DamageType::Energy,
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(3),
         speed: 3,
+        throw_damage: Damage::new(3),
+        two_handed: false,
+        accuracy: 95,
+        reach: 4,
+        durability: 30,
+        max_durability: 30,
+        weight: 3,
+        value: 8,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Shoot),
     })
 }
 
@@ -23,9 +34,20 @@ pub(super) const fn captains_blaster() -> Item {
         name: "Captain's Blaster",
         description: "An energy weapon which the captain keeps by their side through sunshine and rain, through of course they've seen neither in a long time.",
 
+        damage_type: DamageType::Energy,
         straight_damage: Damage::new(7),
         dodge_damage: Damage::new(5),
         speed: 3,
+        throw_damage: Damage::new(3),
+        two_handed: false,
+        accuracy: 95,
+        reach: 4,
+        durability: 30,
+        max_durability: 30,
+        weight: 3,
+        value: 12,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Shoot),
     })
 }
 
@@ -35,9 +57,20 @@ pub(super) const fn standard_blaster() -> Item {
         name: "Standard Issue Blaster",
         description: "The blaster issued to every serving troop. It's slow, but well made.",
 
+        damage_type: DamageType::Energy,
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(2),
         speed: 4,
+        throw_damage: Damage::new(3),
+        two_handed: false,
+        accuracy: 90,
+        reach: 4,
+        durability: 30,
+        max_durability: 30,
+        weight: 3,
+        value: 7,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Shoot),
     })
 }
 
@@ -47,9 +80,20 @@ pub(super) const fn ispd_taser() -> Item {
         name: "ISPD Taser",
         description: "A high-powered taser given to every officer in the Interstellar Police Department. It's fast and lethal if you're not careful (or if you are).",
 
+        damage_type: DamageType::Energy,
         straight_damage: Damage::new(10),
         dodge_damage: Damage::new(5),
-        speed: 2
+        speed: 2,
+        throw_damage: Damage::new(2),
+        two_handed: false,
+        accuracy: 85,
+        reach: 2,
+        durability: 20,
+        max_durability: 20,
+        weight: 2,
+        value: 10,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Shoot),
     })
 }
 
@@ -59,9 +103,20 @@ pub(super) const fn throwing_dart_set() -> Item {
         name: "Set of Throwing Darts",
         description: "A set of sharp darts from the darts set in the bunks. They're not too sharp, but you can throw them fast as anything.",
 
+        damage_type: DamageType::Sharp,
         straight_damage: Damage::new(2),
         dodge_damage: Damage::new(2),
-        speed: 1
+        speed: 1,
+        throw_damage: Damage::new(6),
+        two_handed: false,
+        accuracy: 85,
+        reach: 1,
+        durability: 15,
+        max_durability: 15,
+        weight: 1,
+        value: 3,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Stab),
     })
 }
 
@@ -71,9 +126,20 @@ pub(super) const fn shaving_razor() -> Item {
         name: "Shaving Razor",
         description: "A razor you found in the wash room. It's sharp, but it's not really a weapon.",
 
+        damage_type: DamageType::Sharp,
         straight_damage: Damage::new(3),
         dodge_damage: Damage::new(2),
-        speed: 5
+        speed: 5,
+        throw_damage: Damage::new(2),
+        two_handed: false,
+        accuracy: 95,
+        reach: 1,
+        durability: 10,
+        max_durability: 10,
+        weight: 1,
+        value: 2,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Slash),
     })
 }
 
@@ -82,11 +148,22 @@ pub(super) const fn shaving_razor() -> Item {
 pub(super) const fn wrench() -> Item {
     Item::Weapon(Weapon {
         name: "Wrench",
-        description: "A wrench from the engine room. It's weighty and you could do some good damage with it.",
+        description: "A wrench from the engine room. It's so weighty you need both hands to swing it, but you could do some good damage with it.",
 
+        damage_type: DamageType::Blunt,
         straight_damage: Damage::new(6),
         dodge_damage: Damage::new(4),
-        speed: 3
+        speed: 3,
+        throw_damage: Damage::new(9),
+        two_handed: true,
+        accuracy: 80,
+        reach: 3,
+        durability: 25,
+        max_durability: 25,
+        weight: 4,
+        value: 5,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Swing),
     })
 }
 
@@ -97,8 +174,19 @@ pub(super) const fn eating_knife() -> Item {
         name: "Eating Knife",
         description: "A sharp steel knife. Synthetic protein is tough, so it's sharp and sturdy",
 
+        damage_type: DamageType::Sharp,
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(5),
-        speed: 2    
+        speed: 2,
+        throw_damage: Damage::new(7),
+        two_handed: false,
+        accuracy: 95,
+        reach: 1,
+        durability: 12,
+        max_durability: 12,
+        weight: 1,
+        value: 4,
+        rarity: Rarity::Common,
+        attack_style: Some(AttackStyle::Stab),
     })
 }
\ No newline at end of file
@@ -17,6 +17,8 @@
 //! let screen = Screen {
 //!     title: "The result",
 //!     content: &format!("You picked '{}'", options[user_choice]),
+//!     title_color: Some(Color::Green),
+//!     art: None,
 //! };
 //!
 //! menu.show_screen(screen);
@@ -24,12 +26,18 @@
 
 pub mod tests;
 
+use std::sync::OnceLock;
+
+use crate::config;
+
 /// The list of options for a user to choose from
 pub struct OptionList<'a> {
     /// A list of options for the player to choose from
     pub options: &'a [String],
     /// A command to show the user
     pub prompt: &'a str,
+    /// The text of the extra option shown by [`show_option_list_cancellable`][Menu::show_option_list_cancellable]
+    pub cancel_text: &'a str,
 }
 
 impl<'a> OptionList<'a> {
@@ -40,7 +48,14 @@ impl<'a> OptionList<'a> {
     pub fn new(options: &'a [String], prompt: &'a str) -> Self {
         assert!(!options.is_empty(), "Options should not be empty");
 
-        Self { options, prompt }
+        Self { options, prompt, cancel_text: "Cancel" }
+    }
+
+    /// Takes an [`OptionList`] by value and returns a new one whose cancel option shows the given text instead of 'Cancel'.
+    /// This is useful for submenus, where cancelling goes 'Back' to the previous list.
+    pub const fn with_cancel_text(mut self, cancel_text: &'a str) -> Self {
+        self.cancel_text = cancel_text;
+        self
     }
 }
 
@@ -51,8 +66,120 @@ pub struct Screen<'a> {
     pub title: &'a str,
     /// The text to display
     pub content: &'a str,
+    /// The colour to show the title in, or [`None`] to use the terminal's default colour
+    pub title_color: Option<Color>,
+    /// Multi-line ASCII art to show between the title and the content, or [`None`] for an ordinary screen.
+    /// The art is shown exactly as written, centred as a block if there is room.
+    pub art: Option<&'static str>,
+}
+
+/// A colour which text can be shown in.
+/// Colours are shown using ANSI escape codes picked by the active [`Theme`], unless they are turned off in the [settings][crate::settings::Settings::colors].
+#[derive(Debug, Clone, Copy)]
+pub enum Color {
+    /// Used for damage and health warnings
+    Red,
+    /// Used for healing and completed objectives
+    Green,
+    /// Used for less urgent warnings
+    Yellow,
+    /// Used for room titles
+    Cyan,
+}
+
+/// A set of colours for the terminal menus to show text in.
+/// The theme is chosen at startup with the `--theme <name>` command line argument, and defaults to [`config::THEME`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// The terminal's normal colours, with titles coloured by their [`Color`]
+    Default,
+    /// Bold, bright colours for readability, with every title and option stood out from the background
+    HighContrast,
+}
+
+impl Theme {
+    /// Every [`Theme`]
+    const ALL: [Self; 2] = [Self::Default, Self::HighContrast];
+
+    /// Gets the name used to choose the theme with `--theme`
+    const fn get_name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::HighContrast => "high-contrast",
+        }
+    }
+
+    /// Gets the [`Theme`] with the given name, or [`None`] if there isn't one
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.get_name() == name)
+    }
+
+    /// Gets the ANSI escape code which shows text in the given [`Color`] in this theme
+    const fn get_color_code(self, color: Color) -> &'static str {
+        match (self, color) {
+            (Self::Default, Color::Red) => "\x1b[31m",
+            (Self::Default, Color::Green) => "\x1b[32m",
+            (Self::Default, Color::Yellow) => "\x1b[33m",
+            (Self::Default, Color::Cyan) => "\x1b[36m",
+            (Self::HighContrast, Color::Red) => "\x1b[1;91m",
+            (Self::HighContrast, Color::Green) => "\x1b[1;92m",
+            (Self::HighContrast, Color::Yellow) => "\x1b[1;93m",
+            (Self::HighContrast, Color::Cyan) => "\x1b[1;96m",
+        }
+    }
+
+    /// Gets the ANSI escape code for titles without a [`Color`] in this theme, or [`None`] to use the terminal's default colour
+    const fn get_plain_title_code(self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            Self::HighContrast => Some("\x1b[1;97m"),
+        }
+    }
+
+    /// Gets the ANSI escape code for the options in option lists in this theme, or [`None`] to use the terminal's default colour
+    const fn get_option_code(self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            Self::HighContrast => Some("\x1b[97m"),
+        }
+    }
+}
+
+/// The theme chosen at startup. If it hasn't been set, [`config::THEME`] is used.
+static ACTIVE_THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Sets the active [`Theme`] from the `--theme <name>` command line argument, if it's given and the name is valid
+fn init_theme() {
+    let mut args = std::env::args().skip_while(|arg| arg != "--theme").skip(1);
+
+    if let Some(theme) = args.next().and_then(|name| Theme::from_name(&name)) {
+        let _ = ACTIVE_THEME.set(theme);
+    }
+}
+
+/// Gets the active [`Theme`], or [`None`] if colours are [turned off][crate::settings::Settings::colors] or the menu is in [accessible mode][is_accessible]
+fn get_theme() -> Option<Theme> {
+    (crate::settings::get().colors && !is_accessible()).then(|| *ACTIVE_THEME.get().unwrap_or(&config::THEME))
+}
+
+/// Gets the ANSI escape code to show a screen's title in with the active [`Theme`], or [`None`] if it should be left uncoloured
+fn get_title_code(color: Option<Color>) -> Option<&'static str> {
+    let theme = get_theme()?;
+
+    match color {
+        Some(color) => Some(theme.get_color_code(color)),
+        None => theme.get_plain_title_code(),
+    }
 }
 
+/// Gets the ANSI escape code to show options in with the active [`Theme`], or [`None`] if they should be left uncoloured
+fn get_option_code() -> Option<&'static str> {
+    get_theme()?.get_option_code()
+}
+
+/// The ANSI escape code which resets the foreground colour and boldness to the terminal's default
+const ANSI_RESET_COLOR: &str = "\x1b[22;39m";
+
 /// An error which can occur while displaying a menu. Some variants will only occur on specific platforms.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -61,7 +188,8 @@ pub enum Error {
     Io(std::io::Error),
     /// A character was encountered which is not supported
     IncompatibleCharacter,
-    /// The user asked to quit
+    /// The user asked to quit, and [confirmed][Menu::confirm] it.
+    /// The program will exit without running destructors, so implementations should leave the terminal in a usable state before returning this.
     Quit,
 }
 
@@ -83,6 +211,28 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// The prompt to [confirm][Menu::confirm] with when the user tries to quit
+const QUIT_PROMPT: &str = "Are you sure you want to quit? Your progress will be lost.";
+
+/// Checks a number the user typed in for [`prompt_number`][Menu::prompt_number], which should be from 0 to `max` (inclusive).
+/// Returns the number, or a message explaining what was wrong with it to show the user before asking again.
+pub fn parse_number(input: &str, max: usize) -> Result<usize, String> {
+    match input.trim().parse() {
+        Ok(n) if n <= max => Ok(n),
+        Ok(_) => Err(format!("Enter a number no bigger than {max}")),
+        Err(_) => Err("Not a valid number".to_string()),
+    }
+}
+
+/// Handles an [`Error`] from one of the fallible [`Menu`] methods.
+/// If the user quit, the program exits. Otherwise, it panics.
+fn handle_error(error: Error) -> ! {
+    match error {
+        Error::Quit => std::process::exit(0),
+        e => panic!("{e}"),
+    }
+}
+
 /// A trait for displaying menus to the user
 pub trait Menu: Sized {
     /// Creates a new instance of the object
@@ -90,7 +240,7 @@ pub trait Menu: Sized {
 
     /// Show a list of options. Will return the index of the option the user selected
     fn show_option_list(&mut self, list: OptionList) -> usize {
-        self.try_show_option_list(list).unwrap()
+        self.try_show_option_list(list).unwrap_or_else(|e| handle_error(e))
     }
     /// Fallible version of [`show_option_list`][Menu::show_option_list]
     fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error>;
@@ -99,7 +249,7 @@ pub trait Menu: Sized {
     /// or a [`Some`] value containing the 0-based index of the option the user selected
     /// (for instance if the user selects the first option in the list the return value will be 0)
     fn show_option_list_cancellable(&mut self, list: OptionList) -> Option<usize> {
-        self.try_show_option_list_cancellable(list).unwrap()
+        self.try_show_option_list_cancellable(list).unwrap_or_else(|e| handle_error(e))
     }
     /// Fallible version of [`show_option_list_cancellable`][Menu::show_option_list_cancellable]
     fn try_show_option_list_cancellable(
@@ -107,27 +257,154 @@ pub trait Menu: Sized {
         list: OptionList,
     ) -> Result<Option<usize>, Error>;
 
+    /// Show a list of options which the user can pick any number of, for actions on several things at once.
+    /// Returns the 0-based indices of the options the user picked, in the order they appear in the list, which may be empty.
+    fn show_multi_select(&mut self, list: OptionList) -> Vec<usize> {
+        self.try_show_multi_select(list).unwrap_or_else(|e| handle_error(e))
+    }
+    /// Fallible version of [`show_multi_select`][Menu::show_multi_select].
+    /// By default, each option is toggled by picking it from a [cancellable list][Menu::try_show_option_list_cancellable],
+    /// and the [cancel option][OptionList::cancel_text] confirms the selection.
+    fn try_show_multi_select(&mut self, list: OptionList) -> Result<Vec<usize>, Error> {
+        let mut selected = vec![false; list.options.len()];
+
+        loop {
+            let options: Vec<String> = list
+                .options
+                .iter()
+                .zip(&selected)
+                .map(|(option, &selected)| format!("[{}] {option}", if selected { "x" } else { " " }))
+                .collect();
+            let count = selected.iter().filter(|&&s| s).count();
+            let cancel_text = format!("{} ({count} selected)", list.cancel_text);
+
+            match self.try_show_option_list_cancellable(OptionList::new(&options, list.prompt).with_cancel_text(&cancel_text))? {
+                Some(i) => selected[i] = !selected[i],
+                None => return Ok((0..selected.len()).filter(|&i| selected[i]).collect()),
+            }
+        }
+    }
+
+    /// Ask the user to type in a number from 0 to `max` (inclusive), such as how many items to take from a stack.
+    /// The user is asked again until they enter a [valid][parse_number] number.
+    fn prompt_number(&mut self, prompt: &str, max: usize) -> usize {
+        self.try_prompt_number(prompt, max).unwrap_or_else(|e| handle_error(e))
+    }
+    /// Fallible version of [`prompt_number`][Menu::prompt_number]
+    fn try_prompt_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error>;
+
     /// Show a screen
     fn show_screen(&mut self, screen: Screen) {
-        self.try_show_screen(screen).unwrap();
+        self.try_show_screen(screen).unwrap_or_else(|e| handle_error(e));
     }
     /// Fallible version of [`try_show_screen`][Menu::show_screen]
     fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error>;
+
+    /// Ask the user a yes or no question. Returns `true` if they answered yes.
+    /// This should be used before anything which would lose the user's progress.
+    fn confirm(&mut self, prompt: &str) -> bool {
+        self.try_confirm(prompt).unwrap_or_else(|e| handle_error(e))
+    }
+    /// Fallible version of [`confirm`][Menu::confirm]
+    fn try_confirm(&mut self, prompt: &str) -> Result<bool, Error>;
+
+    /// Gets a short explanation of the controls for this menu, to show on help screens
+    fn get_controls(&self) -> &'static str {
+        "Pick an option from each list to carry on."
+    }
 }
 
 /// Implementation of the [`Menu`] trait for unix platforms using the [`termion`] library
 //#[cfg(all(unix, not(debug_assertions)))]
 mod unix;
-#[cfg(all(unix, not(debug_assertions)))]
-use unix::Tui;
 
-/// Fallback implementation of the [`Menu`] trait for platforms which don't support ANSI escape codes
-#[cfg(any(not(unix), debug_assertions))]
+/// Fallback implementation of the [`Menu`] trait for platforms which don't support ANSI escape codes.
+/// This is also used in [accessible mode][is_accessible], as it shows everything as plain lines of text.
 mod fallback;
 #[cfg(any(not(unix), debug_assertions))]
 use fallback::Tui;
 
-/// Initialises and returns a type which implements [`Menu`] for the current platform
+/// The menu for release builds on unix, which shows the full-terminal menu unless [accessible mode][is_accessible] is on
+#[cfg(all(unix, not(debug_assertions)))]
+enum Tui {
+    /// The full-terminal menu, with borders, scrolling text and arrow key selection
+    Terminal(unix::Tui),
+    /// The line-based menu, which screen readers can read from top to bottom
+    Plain(fallback::Tui),
+}
+
+#[cfg(all(unix, not(debug_assertions)))]
+impl Menu for Tui {
+    fn new() -> Result<Self, std::io::Error> {
+        if is_accessible() {
+            Ok(Self::Plain(fallback::Tui::new()?))
+        } else {
+            Ok(Self::Terminal(unix::Tui::new()?))
+        }
+    }
+
+    fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
+        match self {
+            Self::Terminal(tui) => tui.try_show_option_list(list),
+            Self::Plain(tui) => tui.try_show_option_list(list),
+        }
+    }
+
+    fn try_show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, Error> {
+        match self {
+            Self::Terminal(tui) => tui.try_show_option_list_cancellable(list),
+            Self::Plain(tui) => tui.try_show_option_list_cancellable(list),
+        }
+    }
+
+    fn try_prompt_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error> {
+        match self {
+            Self::Terminal(tui) => tui.try_prompt_number(prompt, max),
+            Self::Plain(tui) => tui.try_prompt_number(prompt, max),
+        }
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error> {
+        match self {
+            Self::Terminal(tui) => tui.try_show_screen(screen),
+            Self::Plain(tui) => tui.try_show_screen(screen),
+        }
+    }
+
+    fn try_confirm(&mut self, prompt: &str) -> Result<bool, Error> {
+        match self {
+            Self::Terminal(tui) => tui.try_confirm(prompt),
+            Self::Plain(tui) => tui.try_confirm(prompt),
+        }
+    }
+
+    fn get_controls(&self) -> &'static str {
+        match self {
+            Self::Terminal(tui) => tui.get_controls(),
+            Self::Plain(tui) => tui.get_controls(),
+        }
+    }
+}
+
+/// Whether accessible mode was turned on at startup. If it hasn't been set, [`config::ACCESSIBLE_MODE`] is used.
+static ACCESSIBLE: OnceLock<bool> = OnceLock::new();
+
+/// Turns on accessible mode if the `--accessible` command line argument is given
+fn init_accessible_mode() {
+    if std::env::args().any(|arg| arg == "--accessible") {
+        let _ = ACCESSIBLE.set(true);
+    }
+}
+
+/// Gets whether the menu is in accessible mode.
+/// In accessible mode, everything is shown as plain lines of text for screen readers, without colours, ASCII art, borders or line wrapping.
+fn is_accessible() -> bool {
+    *ACCESSIBLE.get().unwrap_or(&config::ACCESSIBLE_MODE)
+}
+
+/// Initialises and returns a type which implements [`Menu`] for the current platform, using the [`Theme`] and accessible mode chosen on the command line
 pub fn init() -> Result<impl Menu, std::io::Error> {
+    init_theme();
+    init_accessible_mode();
     Tui::new()
 }
@@ -1,7 +1,12 @@
+use std::fmt::Write as _;
 use std::io::Write;
 use std::{io::StdoutLock, iter};
 
-use super::{Error, Menu, OptionList};
+use crate::config;
+
+use super::{get_option_code, get_title_code, is_accessible, parse_number, Error, Menu, OptionList, Screen, ANSI_RESET_COLOR, QUIT_PROMPT};
+
+mod tests;
 
 /// A struct which implements [`Menu`] for any platform
 pub struct Tui;
@@ -17,75 +22,251 @@ impl Menu for Tui {
     ) -> Result<Option<usize>, Error> {
         let mut stdout = std::io::stdout().lock();
 
-        let num_options = list.options.len() + 1;
-        let max_width = num_options.to_string().len();
-
-        let options_text: String = list
+        // Add the cancel message as the last option
+        let options: Vec<_> = list
             .options
-            .iter() // Get the strings as an iterator
-            .chain(iter::once(&"Cancel".to_string())) // Add the quit message
-            .enumerate() // Get the indices of the items
-            .map(|(i, s)| format!("{: >max_width$}) {}\n", i + 1, s)) // Convert each item to a string with numbers right aligned
+            .iter()
+            .map(String::as_str)
+            .chain(iter::once(list.cancel_text))
             .collect();
 
-        writeln!(stdout, "{}", list.prompt)?;
-        writeln!(stdout, "{options_text}")?;
-
-        let choice = number_input(num_options, &mut stdout)?;
+        let choice = paginated_input(&options, list.prompt, &mut stdout)?;
 
         writeln!(stdout)?;
 
         match choice {
-            u if u == num_options => Ok(None),
-            u => Ok(Some(u - 1)),
+            u if u == list.options.len() => Ok(None),
+            u => Ok(Some(u)),
         }
     }
 
     fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
         let mut stdout = std::io::stdout().lock();
 
-        let num_options = list.options.len();
-        let max_width = num_options.to_string().len();
+        let options: Vec<_> = list.options.iter().map(String::as_str).collect();
 
-        let options_text: String = list
-            .options
-            .iter() // Get the strings as an iterator
-            .enumerate() // Get the indices of the items
-            .map(|(i, s)| format!("{: >max_width$}) {}\n", i + 1, s)) // Convert each item to a string with numbers right aligned
-            .collect();
+        let choice = paginated_input(&options, list.prompt, &mut stdout)?;
 
-        writeln!(stdout, "{}", list.prompt)?;
-        writeln!(stdout, "{options_text}")?;
+        writeln!(stdout)?;
 
-        let choice = number_input(num_options, &mut stdout)?;
+        Ok(choice)
+    }
 
+    fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error> {
+        let mut stdout = std::io::stdout().lock();
+
+        writeln!(stdout, "{}", format_screen(&screen, get_terminal_width(), is_accessible()))?;
         writeln!(stdout)?;
 
-        // Input is 1-based but return value is 0-based, so subtract 1
-        Ok(choice - 1)
+        Ok(())
     }
 
-    fn try_show_screen(&mut self, screen: super::Screen) -> Result<(), Error> {
+    fn try_confirm(&mut self, prompt: &str) -> Result<bool, Error> {
         let mut stdout = std::io::stdout().lock();
 
-        writeln!(stdout, "{}", screen.title)?;
-        writeln!(stdout, "{}", screen.content)?;
+        let answer = yes_no_input(prompt, &mut stdout)?;
         writeln!(stdout)?;
 
-        Ok(())
+        Ok(answer)
+    }
+
+    fn try_prompt_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error> {
+        let mut stdout = std::io::stdout().lock();
+
+        loop {
+            write!(stdout, "{prompt} (0 to {max}, or q to quit): ")?;
+            stdout.flush()?;
+
+            let mut buf = String::new();
+            std::io::stdin().read_line(&mut buf)?;
+
+            if buf.trim() == "q" {
+                if yes_no_input(QUIT_PROMPT, &mut stdout)? {
+                    return Err(Error::Quit);
+                }
+                continue;
+            }
+
+            match parse_number(&buf, max) {
+                Ok(n) => {
+                    writeln!(stdout)?;
+                    return Ok(n);
+                }
+                Err(message) => writeln!(stdout, "{message}")?,
+            }
+        }
+    }
+
+    fn get_controls(&self) -> &'static str {
+        "Type the number of an option and press enter to choose it. Long lists are split into pages, which you can move between with the last options on each page. Type q instead of a number to quit."
+    }
+}
+
+/// Gets the text to show for a [`Screen`] on a terminal `width` characters wide.
+/// In accessible mode, the title and content are given as plain text without colours, art or line wrapping, so screen readers can read them straight through.
+fn format_screen(screen: &Screen, width: usize, accessible: bool) -> String {
+    if accessible {
+        return format!("{}\n{}", screen.title, screen.content);
+    }
+
+    let title = colorize(screen.title, get_title_code(screen.title_color));
+    let art = screen.art.map(|art| centre_block(art, width));
+    let content = wrap_text(screen.content, width);
+
+    iter::once(title).chain(art).chain(iter::once(content)).collect::<Vec<_>>().join("\n")
+}
+
+/// Wraps `text` in the given ANSI escape code, from the active [`Theme`][super::Theme].
+/// Returns `text` unchanged if there is no code.
+fn colorize(text: &str, code: Option<&str>) -> String {
+    match code {
+        Some(code) => format!("{code}{text}{ANSI_RESET_COLOR}"),
+        None => text.to_string(),
+    }
+}
+
+/// Gets the width of the terminal in columns.
+/// If it can't be detected, the `COLUMNS` environment variable is used, and then [`FALLBACK_TERMINAL_WIDTH`][config::FALLBACK_TERMINAL_WIDTH].
+fn get_terminal_width() -> usize {
+    #[cfg(unix)]
+    if let Ok((width, _)) = termion::terminal_size() {
+        return width.into();
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(config::FALLBACK_TERMINAL_WIDTH)
+}
+
+/// Indents every line of `block` by the same amount, so that the block as a whole is centred in `width` characters.
+/// The lines aren't changed otherwise, so ASCII art keeps its shape. If the block is too wide, it is left as it is.
+fn centre_block(block: &str, width: usize) -> String {
+    let block_width = block.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let indent = " ".repeat(width.saturating_sub(block_width) / 2);
+
+    block
+        .lines()
+        .map(|line| format!("{indent}{line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Word-wraps `text` so that no line is wider than `width` characters, keeping any line breaks already in the text.
+/// Words longer than `width` are put on their own line rather than being split.
+fn wrap_text(text: &str, width: usize) -> String {
+    text.split('\n')
+        .map(|line| {
+            let mut wrapped = String::new();
+            // The width of the current wrapped line so far
+            let mut x = 0;
+
+            for word in line.split(' ') {
+                let word_width = word.chars().count();
+
+                if x > 0 && x + 1 + word_width > width {
+                    wrapped.push('\n');
+                    x = 0;
+                } else if x > 0 {
+                    wrapped.push(' ');
+                    x += 1;
+                }
+
+                wrapped.push_str(word);
+                x += word_width;
+            }
+
+            wrapped
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Shows a list of options [`OPTIONS_PER_PAGE`][config::OPTIONS_PER_PAGE] at a time, with options to move between pages.
+/// Returns the 0-based index into `options` of the option the user selected.
+fn paginated_input(options: &[&str], prompt: &str, stdout: &mut StdoutLock) -> Result<usize, Error> {
+    let num_pages = options.len().div_ceil(config::OPTIONS_PER_PAGE);
+    let mut page = 0;
+
+    loop {
+        let start = page * config::OPTIONS_PER_PAGE;
+        let end = (start + config::OPTIONS_PER_PAGE).min(options.len());
+        let has_next = page + 1 < num_pages;
+        let has_previous = page > 0;
+
+        // Add the navigation options after the options on this page
+        let page_options: Vec<_> = options[start..end]
+            .iter()
+            .copied()
+            .chain(has_next.then_some("Next page"))
+            .chain(has_previous.then_some("Previous page"))
+            .collect();
+
+        let num_options = page_options.len();
+        let max_width = num_options.to_string().len();
+
+        // Number each option, with the numbers right aligned
+        let options_text = page_options.iter().enumerate().fold(String::new(), |mut text, (i, s)| {
+            // Writing to a String can't fail
+            let _ = writeln!(text, "{: >max_width$}) {}", i + 1, colorize(s, get_option_code()));
+            text
+        });
+
+        if num_pages > 1 {
+            writeln!(stdout, "{prompt} (page {} of {num_pages})", page + 1)?;
+        } else {
+            writeln!(stdout, "{prompt}")?;
+        }
+        writeln!(stdout, "{options_text}")?;
+
+        // Input is 1-based but return value is 0-based, so subtract 1
+        let choice = number_input(num_options, stdout)? - 1;
+
+        match choice {
+            c if c < end - start => return Ok(start + c),
+            c if has_next && c == end - start => page += 1,
+            _ => page -= 1,
+        }
+    }
+}
+
+/// Asks the user a yes or no question. Will get the user to retype their input until they answer 'y' or 'n'.
+fn yes_no_input(prompt: &str, stdout: &mut StdoutLock) -> Result<bool, Error> {
+    loop {
+        write!(stdout, "{prompt} (y/n): ")?;
+        stdout.flush()?;
+
+        let mut buf = String::new();
+        std::io::stdin().read_line(&mut buf)?;
+
+        match buf.trim().to_lowercase().as_str() {
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => writeln!(stdout, "Please answer y or n")?,
+        }
     }
 }
 
 /// Gets an integer input from the user from 1 to a maximum value (inclusive). Will get the user to retype their input until a valid value is entered.
+/// The user can also type 'q' to quit, which will return an [`Error::Quit`] if they confirm it.
 fn number_input(max: usize, stdout: &mut StdoutLock) -> Result<usize, Error> {
     loop {
-        write!(stdout, "Enter your selection from 1 to {max}: ")?;
+        write!(stdout, "Enter your selection from 1 to {max} (or q to quit): ")?;
         stdout.flush()?;
 
         let mut buf = String::new();
         std::io::stdin().read_line(&mut buf)?;
 
         let selection = buf.trim_end();
+
+        if selection == "q" {
+            if yes_no_input(QUIT_PROMPT, stdout)? {
+                return Err(Error::Quit);
+            }
+            continue;
+        }
+
         match selection.parse() {
             Ok(u) => match u {
                 0 => writeln!(stdout, "Value can't be 0")?,
@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+use super::{centre_block, format_screen, wrap_text};
+use crate::menu::{Color, Screen};
+
+/// Tests that long lines are wrapped between words
+#[test]
+fn test_wrap_text() {
+    let text = "AAAA ".repeat(20);
+    let wrapped = wrap_text(text.trim_end(), 50);
+
+    let lines: Vec<_> = wrapped.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "AAAA ".repeat(10).trim_end());
+    assert_eq!(lines[1], "AAAA ".repeat(10).trim_end());
+}
+
+/// Tests that line breaks already in the text are kept, and that words longer than a line are left whole
+#[test]
+fn test_wrap_text_keeps_line_breaks() {
+    assert_eq!(wrap_text("You eat the apple.\nYou heal 2 HP.", 50), "You eat the apple.\nYou heal 2 HP.");
+    assert_eq!(wrap_text("a bbbbbbbbbb c", 5), "a\nbbbbbbbbbb\nc");
+}
+
+/// Tests that ASCII art is indented as a block rather than line by line, and is left alone if it doesn't fit
+#[test]
+fn test_centre_block() {
+    assert_eq!(centre_block("/\\\n\\__/", 10), "   /\\\n   \\__/");
+    assert_eq!(centre_block("/\\\n\\__/", 3), "/\\\n\\__/");
+}
+
+/// Tests that accessible mode shows a screen as plain text, without escape codes, art or line wrapping
+#[test]
+fn test_accessible_screen() {
+    let screen = Screen {
+        title: "Your vision fades to black",
+        content: &"You collapse to the floor. ".repeat(10),
+        title_color: Some(Color::Red),
+        art: Some("  X   X\n    ^"),
+    };
+
+    let plain = format_screen(&screen, 40, true);
+    assert!(!plain.contains('\x1b'));
+    assert!(!plain.contains("X   X"));
+    assert_eq!(plain, format!("{}\n{}", screen.title, screen.content));
+
+    let styled = format_screen(&screen, 40, false);
+    assert!(styled.contains("X   X"));
+    assert_eq!(styled.contains('\x1b'), crate::settings::get().colors);
+}
@@ -4,11 +4,52 @@ use std::collections::VecDeque;
 
 use super::*;
 
+/// A [`Menu`] for tests, which answers from queues of predefined choices and records every [`Screen`] it is shown
 #[derive(Debug, Clone, Default)]
 pub struct MockMenu {
-    last_screen: Option<(String, String)>,
+    screens: Vec<(String, String)>,
     last_list: Option<(String, Vec<String>)>,
     numbers_to_produce: VecDeque<Option<usize>>,
+    confirmations_to_produce: VecDeque<bool>,
+    typed_numbers: VecDeque<String>,
+}
+
+impl MockMenu {
+    /// Creates a [`MockMenu`] which will answer option lists with the given choices, in order
+    pub fn with_numbers(numbers: impl IntoIterator<Item = Option<usize>>) -> Self {
+        Self {
+            numbers_to_produce: numbers.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Takes a [`MockMenu`] by value and returns a new one which will answer [number prompts][Menu::prompt_number] by typing the given text, in order.
+    /// Invalid text is rejected in the same way as a real menu, and the next text is typed instead.
+    pub fn with_typed_numbers(mut self, typed: impl IntoIterator<Item = &'static str>) -> Self {
+        self.typed_numbers = typed.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Takes a [`MockMenu`] by value and returns a new one which will answer [confirmations][Menu::confirm] with the given answers, in order
+    pub fn with_confirmations(mut self, answers: impl IntoIterator<Item = bool>) -> Self {
+        self.confirmations_to_produce = answers.into_iter().collect();
+        self
+    }
+
+    /// Gets the title and content of the last [`Screen`] which was shown
+    pub fn get_last_screen(&self) -> Option<&(String, String)> {
+        self.screens.last()
+    }
+
+    /// Gets the title and content of every [`Screen`] which has been shown, in order
+    pub fn get_screens(&self) -> &[(String, String)] {
+        &self.screens
+    }
+
+    /// Gets the prompt and options of the last [`OptionList`] which was shown
+    pub fn get_last_list(&self) -> Option<&(String, Vec<String>)> {
+        self.last_list.as_ref()
+    }
 }
 
 impl Menu for MockMenu {
@@ -27,8 +68,53 @@ impl Menu for MockMenu {
     }
 
     fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error> {
-        self.last_screen = Some((screen.title.to_string(), screen.content.to_string()));
+        self.screens.push((screen.title.to_string(), screen.content.to_string()));
         Ok(())
     }
+
+    fn try_prompt_number(&mut self, _prompt: &str, max: usize) -> Result<usize, Error> {
+        loop {
+            if let Ok(n) = parse_number(&self.typed_numbers.pop_front().unwrap(), max) {
+                return Ok(n);
+            }
+        }
+    }
+
+    fn try_confirm(&mut self, _prompt: &str) -> Result<bool, Error> {
+        Ok(self.confirmations_to_produce.pop_front().unwrap())
+    }
 }
 
+/// Tests that typed numbers from 0 up to the max are accepted, and that anything else is rejected
+#[test]
+fn test_parse_number() {
+    assert_eq!(parse_number("0", 5), Ok(0));
+    assert_eq!(parse_number("5", 5), Ok(5));
+    assert_eq!(parse_number(" 3\n", 5), Ok(3));
+    assert!(parse_number("6", 5).is_err());
+    assert!(parse_number("-1", 5).is_err());
+    assert!(parse_number("three", 5).is_err());
+    assert!(parse_number("", 5).is_err());
+}
+
+/// Tests that the mock menu keeps asking for a number until it gets a valid one, like a real menu
+#[test]
+fn test_prompt_number() {
+    let mut menu = MockMenu::default().with_typed_numbers(["lots", "6", "0", "5"]);
+    assert_eq!(menu.prompt_number("", 5), 0);
+    assert_eq!(menu.prompt_number("", 5), 5);
+}
+
+/// Tests that themes can be chosen by name, and that the high contrast theme colours every title and option
+#[test]
+fn test_theme() {
+    assert_eq!(Theme::from_name("default"), Some(Theme::Default));
+    assert_eq!(Theme::from_name("high-contrast"), Some(Theme::HighContrast));
+    assert_eq!(Theme::from_name("neon"), None);
+
+    assert_eq!(Theme::Default.get_plain_title_code(), None);
+    assert_eq!(Theme::Default.get_option_code(), None);
+    assert!(Theme::HighContrast.get_plain_title_code().is_some());
+    assert!(Theme::HighContrast.get_option_code().is_some());
+    assert_ne!(Theme::Default.get_color_code(Color::Red), Theme::HighContrast.get_color_code(Color::Red));
+}
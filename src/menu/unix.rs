@@ -7,7 +7,7 @@ use nix::sys::select::{select, FdSet};
 use nix::sys::time::TimeVal;
 
 use termion::raw::{IntoRawMode, RawTerminal};
-use termion::screen::{AlternateScreen, IntoAlternateScreen};
+use termion::screen::{AlternateScreen, IntoAlternateScreen, ToMainScreen};
 use termion::{cursor, terminal_size};
 
 #[cfg(feature = "no-flicker")]
@@ -15,7 +15,9 @@ use termion::clear;
 
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{Error, Menu, OptionList};
+use crate::config;
+
+use super::{get_title_code, Error, Menu, OptionList, ANSI_RESET_COLOR, QUIT_PROMPT};
 
 mod consts;
 mod rendering;
@@ -100,6 +102,16 @@ fn get_size_checked() -> Result<(u16, u16), TuiError> {
     }
 }
 
+impl Tui {
+    /// Leaves the alternate screen, shows the cursor and disables raw mode.
+    /// This must be called before the program exits without dropping the [`Tui`], e.g. on [`Error::Quit`].
+    fn restore_terminal(&mut self) -> Result<(), std::io::Error> {
+        write!(self.stdout, "{}{}", ToMainScreen, cursor::Show)?;
+        self.stdout.flush()?;
+        self.stdout.get_ref().suspend_raw_mode()
+    }
+}
+
 impl Drop for Tui {
     fn drop(&mut self) {
         // Can't return a Result from drop, so unwrap Result values
@@ -145,7 +157,7 @@ impl Menu for Tui {
             .options
             .iter()
             .map(String::as_str)
-            .chain(std::iter::once("Cancel"))
+            .chain(std::iter::once(list.cancel_text))
             .collect();
 
         // Show list UI
@@ -172,8 +184,8 @@ impl Menu for Tui {
 
         // The number of milliseconds that have passed, used to compute how many graphemes to render
         let mut ms = 0;
-        // Whether to render all graphemes in the string
-        let mut render_all_graphemes = false;
+        // Whether to render all graphemes in the string. If the typewriter effect is off, the whole string is shown straight away.
+        let mut render_all_graphemes = !config::TYPEWRITER;
 
         // Loop until the user quits
         loop {
@@ -204,9 +216,20 @@ impl Menu for Tui {
                 Ok(()) => (),
             };
 
-            self.render_graphemes_from_str(screen.content, graphemes, &mut layout)?;
+            // Any art goes straight under the title, with a blank line between it and the content
+            let art_lines = screen.art.map_or(0, |art| art.lines().count() + 1);
+            if let Some(art) = screen.art {
+                self.render_block_centred(art, TOP_OFFSET + 1)?;
+            }
+
+            self.render_graphemes_from_str(screen.content, graphemes, &mut layout, art_lines)?;
 
+            // The title is measured to centre it, so set the colour around it rather than colouring the text itself
+            if let Some(code) = get_title_code(screen.title_color) {
+                write!(self.stdout, "{code}")?;
+            }
             self.render_text_centred(screen.title, TOP_OFFSET)?;
+            write!(self.stdout, "{ANSI_RESET_COLOR}")?;
 
             if let Some(char) = poll_stdin(&mut stdin)? {
                 // If the scroll has finished, break
@@ -215,7 +238,14 @@ impl Menu for Tui {
                 }
 
                 if char == "q" {
-                    return Err(Error::Quit);
+                    // Release stdin so that the confirmation can read from it
+                    drop(stdin);
+                    if self.try_confirm(QUIT_PROMPT)? {
+                        self.restore_terminal()?;
+                        return Err(Error::Quit);
+                    }
+                    stdin = std::io::stdin().lock();
+                    continue;
                 }
 
                 // Otherwise, skip the rest of the scroll
@@ -225,4 +255,23 @@ impl Menu for Tui {
 
         Ok(())
     }
+
+    fn try_confirm(&mut self, prompt: &str) -> Result<bool, Error> {
+        #[cfg(feature = "no-flicker")]
+        write!(self.stdout, "{}", clear::All)?;
+
+        let choice = self.choose_from_list(&["Yes", "No"], prompt)?;
+        Ok(choice == 0)
+    }
+
+    fn try_prompt_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error> {
+        #[cfg(feature = "no-flicker")]
+        write!(self.stdout, "{}", clear::All)?;
+
+        self.input_number(prompt, max)
+    }
+
+    fn get_controls(&self) -> &'static str {
+        "Use the up and down arrow keys to move through a list, and enter to choose an option. Press any key to skip scrolling text or to move on from a screen, and q to quit."
+    }
 }
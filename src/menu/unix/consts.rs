@@ -31,7 +31,5 @@ pub(super) const FPS: u64 = 30;
 /// The number of milliseconds per frame based on [`FPS`]
 pub(super) const MS_PER_FRAME: u64 = 1000 / FPS;
 
-/// The target number of characters to print per second when scrolling text
-pub(super) const CHARS_PER_SECOND: u64 = 50;
-/// The number of milliseconds between characters based on [`CHARS_PER_SECOND`]
-pub(super) const MS_PER_CHAR: u64 = 1000 / CHARS_PER_SECOND;
+/// The number of milliseconds between characters when scrolling text, based on [`TYPEWRITER_CHARS_PER_SECOND`][crate::config::TYPEWRITER_CHARS_PER_SECOND]
+pub(super) const MS_PER_CHAR: u64 = 1000 / crate::config::TYPEWRITER_CHARS_PER_SECOND;
@@ -8,7 +8,7 @@ use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use super::*;
-use crate::menu::Error;
+use crate::menu::{get_option_code, parse_number, Error, ANSI_RESET_COLOR};
 
 impl Tui {
     /// Moves the cursor to a specified position. The position is 0-based and relative to [`LEFT_OFFSET`] and [`TOP_OFFSET`].
@@ -119,11 +119,16 @@ impl Tui {
                 write!(self.stdout, "{}", style::Invert)?;
             }
 
+            // Show the option in the active theme's colour
+            if let Some(code) = get_option_code() {
+                write!(self.stdout, "{code}")?;
+            }
+
             // Write the line text
             self.render_text_with_max_width(line, max_width)?;
 
-            // Undo any highlighting
-            write!(self.stdout, "{}", style::NoInvert)?;
+            // Undo any highlighting and colour
+            write!(self.stdout, "{}{ANSI_RESET_COLOR}", style::NoInvert)?;
         }
 
         // If the
@@ -231,6 +236,30 @@ impl Tui {
         Ok(())
     }
 
+    /// Renders a block of lines, such as ASCII art, starting at the given line. The block is centred as a whole between [`LEFT_OFFSET`] and [`RIGHT_OFFSET`],
+    /// so every line starts in the same column. Lines which are too long will be cut off with an ellipsis.
+    ///
+    /// ### Panics
+    /// * If the terminal is too small, based on if [`get_size_checked`] fails
+    pub(super) fn render_block_centred(&mut self, block: &str, line: u16) -> Result<(), Error> {
+        let (w, _) = get_size_checked().unwrap();
+        let max_width = w - LEFT_OFFSET - RIGHT_OFFSET;
+
+        let block_width = block.lines().map(UnicodeWidthStr::width).max().unwrap_or(0);
+        let left_offset = max_width.saturating_sub(block_width.try_into().unwrap_or(u16::MAX)) / 2;
+
+        for (i, text) in (0..).zip(block.lines()) {
+            write!(
+                self.stdout,
+                "{}",
+                cursor::Goto(left_offset + LEFT_OFFSET + 1, line + i)
+            )?;
+            self.render_text_with_max_width(text, max_width)?;
+        }
+
+        Ok(())
+    }
+
     /// Renders an empty screen with text saying 'terminal too small'.
     pub(super) fn render_too_small_error_screen(&mut self) -> Result<(), std::io::Error> {
         write!(self.stdout, "{}", clear::All)?;
@@ -297,12 +326,74 @@ impl Tui {
         }
     }
 
+    /// Shows a prompt and lets the user type in a number from 0 to `max` (inclusive), which is [checked][parse_number] when they press enter.
+    /// If it isn't valid, the problem is shown and they can carry on typing.
+    pub(super) fn input_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error> {
+        let mut typed = String::new();
+        let mut problem = String::new();
+
+        // Lock stdin
+        let mut stdin = stdin().lock();
+
+        // Loop until the user enters a valid number
+        loop {
+            // Show the frame and wait
+            self.stdout.flush()?;
+            std::thread::sleep(Duration::from_millis(MS_PER_FRAME));
+
+            // Render the border, propagating errors
+            if let Err(e) = self.new_frame() {
+                match e {
+                    TuiError::TerminalTooSmall => {
+                        self.render_too_small_error_screen()?;
+                        continue;
+                    }
+                    TuiError::MenuError(m) => return Err(m),
+                }
+            }
+
+            // Render the prompt, what has been typed so far, and what was wrong with the last number
+            self.render_text_centred(prompt, TOP_OFFSET)?;
+            self.render_text_centred(&format!("{typed}_ (0 to {max})"), TOP_OFFSET + 2)?;
+            self.render_text_centred(&problem, TOP_OFFSET + 4)?;
+
+            // Handle user input
+            if let Some(input) = poll_stdin(&mut stdin)? {
+                // Enter
+                if input == "\r" || input == "\n" {
+                    match parse_number(&typed, max) {
+                        Ok(n) => return Ok(n),
+                        Err(message) => problem = message,
+                    }
+                    typed.clear();
+                }
+                // Backspace
+                else if input == "\x7f" || input == "\x08" {
+                    typed.pop();
+                }
+                else if input == "q" {
+                    // Release stdin so that the confirmation can read from it
+                    drop(stdin);
+                    if self.try_confirm(QUIT_PROMPT)? {
+                        self.restore_terminal()?;
+                        return Err(Error::Quit);
+                    }
+                    stdin = std::io::stdin().lock();
+                }
+                else {
+                    typed += &input;
+                }
+            }
+        }
+    }
+
     /// Renders a given number of graphemes from a string.
     ///
     /// ### Params:
     /// * text: the text to render from
     /// * graphemes: the number of characters to render
     /// * layout: a reference to cache the generated [`TextLayout`]
+    /// * `skip_lines`: how many lines to leave empty above the text, e.g. for [art][crate::menu::Screen::art]
     ///
     /// ### Panics:
     /// * If the terminal is too small, based on if [`get_size_checked`] fails
@@ -311,13 +402,14 @@ impl Tui {
         text: &'a str,
         graphemes: usize,
         layout: &'b mut TextLayout<'a>,
+        skip_lines: usize,
     ) -> Result<(), Error> {
         // Get the size of the terminal
         let (w, h) = get_size_checked().unwrap();
 
         // Calculate the maximum width and height
         let max_width = (w - LEFT_OFFSET - RIGHT_OFFSET - 1) as usize;
-        let max_lines = (h - TOP_OFFSET - BOTTOM_OFFSET) as usize;
+        let max_lines = ((h - TOP_OFFSET - BOTTOM_OFFSET) as usize).saturating_sub(skip_lines);
 
         // Regenerate layout if it was generated for a different width
         if layout.max_width != max_width {
@@ -356,7 +448,7 @@ impl Tui {
         {
             // If the whole line must be printed
             if render_all_lines || layout_line != needed_lines - 1 {
-                self.move_cursor(0, screen_line)?;
+                self.move_cursor(0, screen_line + skip_lines)?;
                 write!(self.stdout, "{}", line.content)?;
 
                 // Print dash for words split over multiple lines
@@ -371,7 +463,7 @@ impl Tui {
                     .grapheme_indices(true)
                     .nth(graphemes - graphemes_so_far)
                     .unwrap();
-                self.move_cursor(0, screen_line)?;
+                self.move_cursor(0, screen_line + skip_lines)?;
                 write!(self.stdout, "{}", &line.content[..end_index])?;
             }
         }
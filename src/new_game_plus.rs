@@ -0,0 +1,68 @@
+//! Contains the [`NewGamePlus`] type, which carries some of the [`Player`]'s progress from a won run into the next one
+
+use crate::items::Item;
+use crate::menu::{Menu, OptionList};
+use crate::player::Player;
+
+/// The progress carried into a New Game Plus run. This is kept when the time loop resets, until the [`Player`] starts a fresh run.
+#[derive(Debug, Clone)]
+pub struct NewGamePlus {
+    /// How many times the [`Player`] has won and carried on. Enemies get tougher with each cycle - see [`Enemy::scale_for_cycle`][crate::combat::Enemy::scale_for_cycle].
+    pub cycle: usize,
+    /// The XP the [`Player`] had when they won. They start with this much, at the level it would have got them to.
+    pub xp: usize,
+    /// The [`Item`] the [`Player`] chose to bring with them, if any
+    pub item: Option<Item>,
+}
+
+/// What the [`Player`] chooses to do after winning
+#[derive(Debug)]
+pub enum NextRun {
+    /// Start again, keeping some progress
+    NewGamePlus(NewGamePlus),
+    /// Start again from scratch
+    FreshRun,
+    /// Stop playing
+    Stop,
+}
+
+impl NextRun {
+    /// Asks the [`Player`] what to do now they've won. If they pick New Game Plus, they also choose an [`Item`] to bring with them.
+    pub fn choose(player: &Player, menu: &mut impl Menu) -> Self {
+        let options = [
+            format!("Start New Game Plus - keep your level and one item, but face tougher enemies (cycle {})", player.new_game_plus_cycle + 1),
+            "Start a fresh run".to_string(),
+            "Stop playing".to_string(),
+        ];
+
+        match menu.show_option_list(OptionList::new(&options, "What now?")) {
+            0 => Self::NewGamePlus(NewGamePlus {
+                cycle: player.new_game_plus_cycle + 1,
+                xp: player.xp,
+                item: choose_item(player, menu),
+            }),
+            1 => Self::FreshRun,
+            _ => Self::Stop,
+        }
+    }
+}
+
+/// Asks the [`Player`] which [`Item`] to bring into New Game Plus. Only weapons, armor and food can be brought, so the escape can't be skipped.
+fn choose_item(player: &Player, menu: &mut impl Menu) -> Option<Item> {
+    let items = player
+        .inventory
+        .iter()
+        .map(|(item, _)| item)
+        .filter(|item| matches!(item, Item::Weapon(_) | Item::Armor(_) | Item::Food(_)))
+        .collect::<Vec<_>>();
+
+    if items.is_empty() {
+        return None;
+    }
+
+    let items_str = items.iter().map(|item| item.get_name().to_string()).collect::<Vec<_>>();
+    let list = OptionList::new(&items_str, "Which item do you bring with you?").with_cancel_text("Bring nothing");
+    let choice = menu.show_option_list_cancellable(list)?;
+
+    Some(items[choice].clone())
+}
@@ -0,0 +1,153 @@
+//! Functionality related to [`Npc`]s, who the [`Player`] can talk to instead of fighting
+
+use crate::combat::{Damage, Enemy, Health};
+use crate::config;
+use crate::items::{DamageType, Item};
+use crate::menu::{Menu, OptionList, Screen};
+use crate::player::Player;
+use crate::rooms::Room;
+
+/// A character who the [`Player`] can talk to.
+/// Npcs are stored on their [`RoomState`][crate::rooms::RoomState], so they remember where the conversation got to when the player leaves.
+#[derive(Debug)]
+pub struct Npc {
+    /// The npc's name
+    pub name: &'static str,
+    /// The npc's dialogue tree. Each [`Response`] leads to another node by its index into this list.
+    pub dialogue: &'static [DialogueNode],
+    /// The index into the [`dialogue`][Npc::dialogue] of the node to start from the next time the [`Player`] talks to the npc
+    pub start_node: usize,
+    /// How the npc fights alongside the [`Player`] once they are one of their [companions][Player::companions], or [`None`] if they stay out of fights
+    pub fighter: Option<Fighter>,
+}
+
+/// How an [`Npc`] fights alongside the [`Player`] in [battle][crate::combat::battle] once they are one of their [companions][Player::companions].
+/// Their health is kept between battles, and they are lost for good if it runs out.
+#[derive(Debug, Clone, Copy)]
+pub struct Fighter {
+    /// The companion's current health
+    pub health: Health,
+    /// The companion's max health
+    pub max_health: Health,
+    /// How much damage the companion deals when they hit the enemy
+    pub damage: Damage,
+    /// What kind of damage the companion deals
+    pub damage_type: DamageType,
+}
+
+/// How [companions][Player::companions] decide whether to join in each turn of a battle. This is set with [`config::COMPANION_AI`].
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // Only one mode is configured at a time
+pub enum CompanionAi {
+    /// Companions attack every turn, however hurt they are
+    Brave,
+    /// Companions attack until they are down to [`COMPANION_RETREAT_HEALTH_PERCENT`][config::COMPANION_RETREAT_HEALTH_PERCENT] of their health,
+    /// then keep back out of the enemy's reach
+    Cautious,
+}
+
+impl CompanionAi {
+    /// Gets whether a companion with the given [`Fighter`] stats gets stuck in this turn.
+    /// Companions who hang back don't attack, but can't be hit either.
+    pub fn engages(self, fighter: &Fighter) -> bool {
+        match self {
+            Self::Brave => true,
+            Self::Cautious => fighter.health.get_percentage(fighter.max_health) > config::COMPANION_RETREAT_HEALTH_PERCENT,
+        }
+    }
+}
+
+/// One point in a conversation with an [`Npc`], where they say something and the [`Player`] picks a [`Response`]
+#[derive(Debug)]
+pub struct DialogueNode {
+    /// What the [`Npc`] says
+    pub text: &'static str,
+    /// The responses the [`Player`] can give
+    pub responses: &'static [Response],
+}
+
+/// Something the [`Player`] can say to an [`Npc`]
+#[derive(Debug)]
+pub struct Response {
+    /// What the [`Player`] says
+    pub text: &'static str,
+    /// The index into the [npc's dialogue][Npc::dialogue] of the node this leads to, or [`None`] if it ends the conversation
+    pub next: Option<usize>,
+    /// What happens when the [`Player`] picks this response, in order
+    pub effects: &'static [DialogueEffect],
+}
+
+/// Something that happens as a result of a [`Response`]
+#[derive(Debug)]
+pub enum DialogueEffect {
+    /// The [`Npc`] gives the [`Player`] an [`Item`]
+    GiveItem(fn() -> Item),
+    /// The [`Npc`] tells the [`Player`] where a [`Room`] is, so it counts as [visited][crate::rooms::RoomState::visited]
+    RevealRoom(Room),
+    /// The [`Npc`] turns on the [`Player`] and becomes an [`Enemy`], ending the conversation
+    StartFight(fn() -> Enemy),
+    /// The [`Npc`] leaves the room to follow the [`Player`] as one of their [companions][Player::companions], ending the conversation
+    JoinPlayer,
+    /// The next conversation with the [`Npc`] starts from the node at the given index into their [dialogue][Npc::dialogue]
+    SetStartNode(usize),
+}
+
+/// How a conversation with an [`Npc`] ended
+#[derive(Debug)]
+pub enum ConversationResult {
+    /// The [`Player`] walked away, and the [`Npc`] is still there to talk to
+    Ended,
+    /// The [`Npc`] turned into an [`Enemy`], which should replace them in the room
+    Fight(Box<Enemy>),
+    /// The [`Npc`] left the room to follow the [`Player`]
+    Joined,
+}
+
+impl Npc {
+    /// Creates a new [`Npc`] with the given name and dialogue tree, which starts from the first node
+    pub const fn new(name: &'static str, dialogue: &'static [DialogueNode]) -> Self {
+        Self { name, dialogue, start_node: 0, fighter: None }
+    }
+
+    /// Takes an [`Npc`] by value and returns a new one who fights alongside the [`Player`] with the given [`Fighter`] stats once they've joined them
+    pub const fn with_fighter(mut self, fighter: Fighter) -> Self {
+        self.fighter = Some(fighter);
+        self
+    }
+
+    /// Talks to the [`Player`] until they end the conversation or the [`Npc`] starts a fight
+    pub fn talk(&mut self, player: &mut Player, menu: &mut impl Menu) -> ConversationResult {
+        let dialogue = self.dialogue;
+        let mut node = &dialogue[self.start_node];
+
+        loop {
+            let responses = node.responses.iter().map(|r| format!("\"{}\"", r.text)).collect::<Vec<_>>();
+            let prompt = format!("{}: \"{}\"", self.name, node.text);
+            let choice = menu.show_option_list(OptionList::new(&responses, &prompt));
+            let response = &node.responses[choice];
+
+            for effect in response.effects {
+                match effect {
+                    DialogueEffect::GiveItem(item) => player.pick_up_item(menu, item()),
+                    DialogueEffect::RevealRoom(room) => {
+                        player.room_graph.get_state_mut(*room).visited = true;
+                        menu.show_screen(Screen {
+                            title: &format!("The {} tells you the way to the {}", self.name, room.get_name()),
+                            content: &format!("You commit the route to memory. You can head straight to the {} from now on.", room.get_name()),
+                            title_color: None,
+                            art: None,
+                        });
+                    }
+                    DialogueEffect::StartFight(enemy) => return ConversationResult::Fight(Box::new(enemy())),
+                    DialogueEffect::JoinPlayer => return ConversationResult::Joined,
+                    DialogueEffect::SetStartNode(i) => self.start_node = *i,
+                }
+            }
+
+            match response.next {
+                Some(i) => node = &dialogue[i],
+                None => return ConversationResult::Ended,
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Contains the [`Objectives`] type, which tracks what the [`Player`][crate::player::Player] needs to do to escape
+
+use crate::menu::{Color, Menu, Screen};
+
+/// A step towards escaping the ship
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Carry the [`EscapePodKeys`][crate::items::Item::EscapePodKeys]
+    FindKeyCard,
+    /// Get into the [`EscapePod`][crate::rooms::Room::EscapePod]
+    ReachEscapePod,
+    /// Carry the [`Maps`][crate::items::Item::Maps], which the escape pod needs to launch
+    FindMaps,
+    /// Take off in the escape pod
+    Escape,
+}
+
+impl Objective {
+    /// Every [`Objective`], in the order the [`Player`][crate::player::Player] is expected to complete them
+    const ALL: [Self; 4] = [
+        Self::FindKeyCard,
+        Self::ReachEscapePod,
+        Self::FindMaps,
+        Self::Escape,
+    ];
+
+    /// Gets a description of what the [`Player`][crate::player::Player] needs to do to complete the [`Objective`]
+    pub const fn get_description(self) -> &'static str {
+        match self {
+            Self::FindKeyCard => "Find the key card for the escape pod",
+            Self::ReachEscapePod => "Get into the escape pod",
+            Self::FindMaps => "Find some up-to-date maps for the escape pod",
+            Self::Escape => "Take off and get off the ship",
+        }
+    }
+}
+
+/// The [`Objective`]s the [`Player`][crate::player::Player] has completed.
+/// Unlike [`Achievements`][crate::achievements::Achievements], these are reset when the time loop resets.
+#[derive(Debug, Default, Clone)]
+pub struct Objectives {
+    /// The [`Objective`]s which have been completed, in the order they were completed
+    pub completed: Vec<Objective>,
+}
+
+impl Objectives {
+    /// Gets whether the given [`Objective`] is complete
+    pub fn is_complete(&self, objective: Objective) -> bool {
+        self.completed.contains(&objective)
+    }
+
+    /// Gets the first [`Objective`] which isn't complete yet, or [`None`] if they all are
+    pub fn get_active(&self) -> Option<Objective> {
+        Objective::ALL.into_iter().find(|o| !self.is_complete(*o))
+    }
+
+    /// Completes the given [`Objective`]. If it wasn't already complete, a [`Screen`] is shown to tell the player.
+    pub fn complete(&mut self, menu: &mut impl Menu, objective: Objective) {
+        if self.is_complete(objective) {
+            return;
+        }
+        self.completed.push(objective);
+
+        let content = match self.get_active() {
+            Some(next) => format!("Next: {}", next.get_description()),
+            None => "That's everything.".to_string(),
+        };
+
+        let screen = Screen {
+            title: &format!("Objective complete: {}", objective.get_description()),
+            content: &content,
+            title_color: Some(Color::Green),
+            art: None,
+        };
+        menu.show_screen(screen);
+    }
+
+    /// Shows a [`Screen`] listing every [`Objective`] and whether it is complete
+    pub fn show(&self, menu: &mut impl Menu) {
+        let list = Objective::ALL
+            .iter()
+            .map(|o| {
+                let mark = if self.is_complete(*o) { "✓" } else { "☐" };
+                format!("{mark} {}", o.get_description())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let screen = Screen {
+            title: &format!("Objectives - {}/{} complete", self.completed.len(), Objective::ALL.len()),
+            content: &list,
+            title_color: None,
+            art: None,
+        };
+        menu.show_screen(screen);
+    }
+}
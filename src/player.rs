@@ -2,29 +2,144 @@
 
 mod tests;
 
-use crate::combat::{self, Health};
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use crate::achievements::{Achievement, Achievements};
+use crate::balance::DynamicBalance;
+use crate::combat::{self, AttackPattern, Damage, Enemy, Health};
 use crate::config::{self, STARTING_ROOM};
-use crate::items::Item;
+use crate::dev;
+use crate::endings::Ending;
+use crate::journal::Journal;
+use crate::lang;
+use crate::items::{self, list_item_stacks, list_items, Armor, ArmorSlot, AttackDirection, Food, Item, Rarity, Weapon};
 use crate::map;
-use crate::menu::{Menu, OptionList, Screen};
-use crate::rooms::{Room, RoomGraph, RoomState, RoomTransition};
+use crate::new_game_plus::NewGamePlus;
+use crate::menu::{Color, Menu, OptionList, Screen};
+use crate::npc::{ConversationResult, Npc};
+use crate::objectives::{Objective, Objectives};
+use crate::rng::Rng;
+use crate::rooms::{DoorState, Room, RoomGraph, RoomState, RoomTransition};
+use crate::settings;
+use crate::speedrun::{Finish, Timer};
+use crate::stats::RunStats;
+use crate::tutorial::{Tutorial, TutorialPrompt};
 
 /// The state of the player
 #[derive(Debug)]
 pub struct Player {
     /// Which [`Room`] the [`Player`] is in
     pub room: Room,
-    /// The [`Player`]'s inventory
-    pub inventory: Vec<Item>,
+    /// The [`Room`] the [`Player`] was in before their last move, which they can [go back][PassiveAction::GoBack] to.
+    /// This is [`None`] at the start of the loop, after going back, and after a battle.
+    pub previous_room: Option<Room>,
+    /// Whether the [`Player`] has [sneaked][Player::sneak] past the enemy in the current room without being noticed. This is reset when they move.
+    pub hidden: bool,
+    /// The [`Player`]'s inventory, as stacks of [identical items][Item::can_stack_with] and how many are in each.
+    /// Indices into the inventory refer to whole stacks.
+    pub inventory: Vec<(Item, usize)>,
+    /// The index into the [player's inventory][Player::inventory] of the [`Weapon`] they have equipped, if any.
+    /// This is the weapon they [counter-attack][crate::combat] with.
+    pub equipped_weapon: Option<usize>,
+    /// The index into the [player's inventory][Player::inventory] of the [`Armor`] they are wearing, if any.
+    /// Only the equipped armor reduces the damage they take in battle.
+    pub equipped_armor: Option<usize>,
+    /// The index into the [player's inventory][Player::inventory] of the [off-hand][ArmorSlot::OffHand] [`Armor`] they are holding, if any.
+    /// This also reduces the damage they take, but can't be used alongside a [two-handed][Weapon::two_handed] weapon.
+    pub equipped_off_hand: Option<usize>,
     /// The [`Player`]'s current health
     pub health: Health,
     /// The maximum health the [`Player`] can reach
     pub max_health: Health,
+    /// How much stamina the [`Player`] has. This is spent on defensive [combat actions][combat::Action::get_stamina_cost].
+    pub stamina: usize,
+    /// The maximum stamina the [`Player`] can reach
+    pub max_stamina: usize,
+    /// How many more turns of battle the [`Player`] has to wait before they can dodge again. See [`DODGE_COOLDOWN_TURNS`][config::DODGE_COOLDOWN_TURNS].
+    pub dodge_cooldown: usize,
+    /// How strong the [`Player`] is. This adds to the damage of their attacks - see [`combat::attack_damage`].
+    pub strength: usize,
+    /// The total XP the [`Player`] has earned from defeating enemies
+    pub xp: usize,
+    /// The [`Player`]'s level. This increases when their [XP][Player::xp] reaches the next of the [`LEVEL_XP_THRESHOLDS`][config::LEVEL_XP_THRESHOLDS].
+    pub level: usize,
+    /// The maximum total [weight][Item::get_weight] of items the [`Player`] can carry, in kg
+    pub max_weight: usize,
+    /// How much gold the [`Player`] has to spend in [`Shop`][crate::shop::Shop]s
+    pub gold: u32,
     /// The number of turns the user has left before the loop resets
     pub remaining_turns: usize,
+    /// The number of turns left until the ship self-destructs, or [`None`] if it isn't going to. See [`config::SELF_DESTRUCT_MODE`].
+    pub self_destruct_turns: Option<usize>,
+    /// How hungry the [`Player`] is. This rises each turn and is reduced by eating [`Food`].
+    /// Once it reaches [`HUNGER_STARVING`][config::HUNGER_STARVING], the [`Player`] will start losing health.
+    pub hunger: usize,
+    /// How much oxygen the [`Player`] has left. This drops each turn and is refilled in the [`EngineRoom`][Room::EngineRoom].
+    /// Once it runs out, the [`Player`] will start losing health.
+    pub oxygen: usize,
+    /// How many more turns the [`Player`]'s [bandage][Item::Bandage] will keep healing them for, or 0 if they aren't wearing one
+    pub bandage_turns: usize,
+    /// How many more turns the [`Player`] will feel fresh from a shower in the [`WashRoom`][Room::WashRoom] for, or 0 if they don't
+    pub fresh_turns: usize,
+
+    /// The order the [`Player`] has chosen to keep their [inventory][Player::inventory] in, or [`None`] if they arrange it by hand.
+    /// It is [sorted][Player::sort_inventory] each time they open it.
+    pub inventory_sort: Option<InventorySort>,
 
     /// The current state of the rooms
     pub room_graph: RoomGraph,
+    /// The [`Npc`]s who have joined the [`Player`] to escape with them, in the order they joined
+    pub companions: Vec<Npc>,
+    /// The names of the [companions][Player::companions] who didn't fit in the [escape pod][Room::EscapePod] and were left on the ship
+    pub left_behind: Vec<&'static str>,
+    /// The names of the [companions][Player::companions] who were killed fighting alongside the [`Player`]
+    pub fallen_companions: Vec<&'static str>,
+    /// The names of the enemies the [`Player`] has defeated during this loop, in the order they were defeated
+    pub defeated_enemies: Vec<&'static str>,
+    /// What the [`Player`] has done towards escaping during this loop
+    pub objectives: Objectives,
+    /// A log of notable things that have happened during this loop
+    pub journal: Journal,
+    /// Running totals of what the [`Player`] has done during this loop
+    pub stats: RunStats,
+    /// The real time taken by the run. Like [`achievements`][Player::achievements], this should be kept when the time loop resets.
+    pub timer: Timer,
+    /// The feats the [`Player`] has accomplished. Unlike the rest of the [`Player`]'s state, these should be kept when the time loop resets.
+    pub achievements: Achievements,
+    /// The explanations shown to first-time players. Like [`achievements`][Player::achievements], this should be kept when the time loop resets.
+    pub tutorial: Tutorial,
+    /// How much tougher or weaker enemies are made to suit how the [`Player`] is doing. Like [`achievements`][Player::achievements], this should be kept when the time loop resets.
+    pub balance: DynamicBalance,
+    /// Whether the [`Player`] has chosen to [quit the run][PassiveAction::QuitToMenu], and what should happen to its autosave if they have
+    pub quit: Option<Quit>,
+
+    /// How many times the [`Player`] has won and carried on into [New Game Plus][NewGamePlus], or 0 on a normal run
+    pub new_game_plus_cycle: usize,
+
+    /// The seed that [`rng`][Player::rng] was created from, so that the run can be reproduced
+    pub seed: u64,
+    /// The random number generator which all random decisions should be made with
+    pub rng: Rng,
+}
+
+/// The items a [`Player`] was carrying when they died, left in the [`Room`] they died in.
+/// These are carried over into the next time round the loop, on [harder difficulties][crate::difficulty::Difficulty::drops_inventory_on_death].
+#[derive(Debug)]
+pub struct Remains {
+    /// The [`Room`] the [`Player`] died in
+    pub room: Room,
+    /// Everything the [`Player`] was carrying, with stacks split into single items
+    pub items: Vec<Item>,
+}
+
+/// What should happen to the autosave when the [`Player`] [quits the run][PassiveAction::QuitToMenu]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quit {
+    /// Keep the autosave, so the run can be resumed from where the [`Player`] left off
+    Save,
+    /// Delete the autosave, throwing the run away
+    Discard,
 }
 
 /// An action the [`Player`] can take outside of a battle
@@ -32,29 +147,174 @@ pub struct Player {
 enum PassiveAction<'a> {
     /// Print the [`Player`]'s health
     CheckState,
-    /// Go to a [`Room`] which is connected to the current one
+    /// Go to a [`Room`] which is connected to the current one through an open door
     GoToRoom(&'a RoomTransition),
-    /// Use the [`Item`] at the given index into the [player's inventory][Player::inventory]
-    UseItem(usize),
+    /// Open the door to the given [`Room`], if it isn't [locked][DoorState::Locked]
+    OpenDoor(Room),
+    /// Close the door to the given [`Room`]
+    CloseDoor(Room),
+    /// Try to [pick the lock][Player::pick_lock] on the [locked][DoorState::Locked] door to the given [`Room`]
+    PickLock(Room),
+    /// Go back to the [`Player`]'s [previous room][Player::previous_room]
+    GoBack(Room),
+    /// Go straight to a [visited][RoomState::visited] [`Room`], which is the given number of moves away
+    FastTravel(Room, usize),
     /// Add the [`Item`] at the given index into the [current room's inventory][RoomState::items] to the [player's inventory][Player::inventory]
     PickUpItem(usize),
+    /// [Take every item][Player::take_all] in the current room which the [`Player`] can carry
+    TakeAll,
+    /// Search the current room, revealing any of its [hidden items][RoomState::hidden_items]
+    Search,
+    /// [Rest][Player::rest] until the [`Player`] is healed or something interrupts them
+    Rest,
+    /// Show the [`Player`]'s [`Achievements`]
+    ViewAchievements,
+    /// Show the [`Player`]'s [`Objectives`]
+    ViewObjectives,
+    /// Show the [`Player`]'s [`Journal`]
+    ViewJournal,
+    /// Show the [`Player`]'s [`RunStats`]
+    ViewStats,
+    /// Show the [speedrun timer][Player::timer], and let the [`Player`] reset their fastest time
+    ViewTimer,
+    /// Talk to the [`Npc`] in the current room
+    TalkTo,
+    /// Try to [sneak][Player::sneak] past the enemy in the current room, who hasn't noticed the [`Player`] yet
+    Sneak,
+    /// Attack the enemy in the current room, who hasn't noticed the [`Player`] yet
+    Fight,
     /// Carry out the [`RoomAction`][crate::map::RoomAction] at the given index into the [current room's actions][RoomState::actions]
     RoomAction(usize),
+    /// Open the [player's inventory][Player::inventory] to examine, use, drop or combine their [`Item`]s
+    OpenInventory,
+    /// Show a [help screen][Player::show_help] explaining what the [`Player`] can do
+    Help,
+    /// Turn off the [`Tutorial`]. Only offered while it is turned on.
+    SkipTutorial,
+    /// Teleport to any [`Room`], ignoring doors. Only offered in [developer mode][dev].
+    DevTeleport,
+    /// Heal to full health and stamina. Only offered in [developer mode][dev].
+    DevHeal,
+    /// Spawn any [`Item`] into the [player's inventory][Player::inventory]. Only offered in [developer mode][dev].
+    DevSpawnItem,
+    /// Show where each of the current room's connections leads, and what the [`Player`] knows about it
+    InspectConnections,
+    /// Change the game's [settings][settings::show]. This is done while the action is being chosen, so it doesn't use up the turn.
+    Settings,
+    /// Quit the run and go back to the main menu, doing the given thing with the autosave. The player is [asked to confirm][confirm_quit] first.
+    QuitToMenu(Quit),
 }
 
-/// Prints a screen with the details of a [`RoomTransition`] and the player's new [`Room`]
-fn print_room_transition(transition: &RoomTransition, menu: &mut impl Menu) {
-    let screen = Screen {
-        title: &format!("You go to the {}", transition.prompt_text.unwrap_or_else(||transition.to.get_name())),
-        content: &format!(
-            "{}\nYou are now in the {} - {}",
-            transition.message,
-            transition.to.get_name(),
-            transition.to.get_description()
-        ),
+/// Something the [`Player`] can do with an [`Item`] from the [inventory screen][Player::open_inventory]
+#[derive(Debug, Clone, Copy)]
+enum InventoryAction {
+    /// Show a [`Screen`] describing the item
+    Examine,
+    /// [Use][Player::use_item] the item
+    Use,
+    /// [Drop][Player::drop_item] the item in the current room
+    Drop,
+    /// Try to [combine][Player::combine_items] the item with another one
+    Combine,
+    /// [Split][Player::split_stack] some of the items off into a new stack
+    Split,
+    /// [Move][Player::move_stack] the stack to another place in the inventory
+    Move,
+    /// [Equip][Player::equip_item] the weapon or armor
+    Equip,
+    /// [Unequip][Player::unequip_item] the weapon or armor
+    Unequip,
+}
+
+/// An order the [player's inventory][Player::inventory] can be [sorted][Player::sort_inventory] into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventorySort {
+    /// Weapons first, then armor, food, other things which can be used up, and finally everything else. Items of the same type are sorted by name.
+    Type,
+    /// Alphabetically by name
+    Name,
+    /// Heaviest first. Items of the same weight are sorted by name.
+    Weight,
+}
+
+impl InventorySort {
+    /// Every [`InventorySort`], in the order they are offered to the [`Player`]
+    const ALL: [Self; 3] = [Self::Type, Self::Name, Self::Weight];
+
+    /// Gets the text shown to the [`Player`] when they are choosing how to sort their inventory
+    const fn get_name(self) -> &'static str {
+        match self {
+            Self::Type => "By type",
+            Self::Name => "By name",
+            Self::Weight => "By weight, heaviest first",
+        }
+    }
+
+    /// Gets where an [`Item`] goes when sorting [by type][InventorySort::Type]
+    const fn get_type_rank(item: &Item) -> usize {
+        match item {
+            Item::Weapon(_) => 0,
+            Item::Armor(_) => 1,
+            Item::Food(_) => 2,
+            Item::Bandage | Item::Explosive | Item::RepairKit => 3,
+            _ => 4,
+        }
+    }
+
+    /// Compares two [`Item`]s to find which one comes first in this order
+    fn compare(self, a: &Item, b: &Item) -> Ordering {
+        let by_name = a.get_name().cmp(b.get_name());
+
+        match self {
+            Self::Type => Self::get_type_rank(a).cmp(&Self::get_type_rank(b)).then(by_name),
+            Self::Name => by_name,
+            Self::Weight => b.get_weight().cmp(&a.get_weight()).then(by_name),
+        }
+    }
+}
+
+/// Updates an equipped item's index into the [player's inventory][Player::inventory] after the stack at `removed` is taken out of it.
+/// If the equipped item itself was removed, it is unequipped.
+fn shift_equipped_index(equipped: &mut Option<usize>, removed: usize) {
+    *equipped = match *equipped {
+        Some(i) if i == removed => None,
+        Some(i) if i > removed => Some(i - 1),
+        e => e,
     };
+}
+
+/// [Scales][Enemy::scaled] a base [`Enemy`] to suit the [difficulty][settings::Settings::difficulty] and the [`Player`]'s level,
+/// adjusted by their [`DynamicBalance`], then makes it tougher for their [New Game Plus][NewGamePlus] cycle
+fn scale_enemy(base: Enemy, level: usize, balance: DynamicBalance, new_game_plus_cycle: usize) -> Enemy {
+    let mut enemy = Enemy::scaled(base, settings::get().difficulty, level, balance);
+    enemy.scale_for_cycle(new_game_plus_cycle);
+    enemy
+}
+
+/// Asks the [`Player`] whether they want to quit the run, and if so whether to keep its autosave so it can be resumed.
+/// The game doesn't stop here - once the action is taken, [`quit`][Player::quit] is set so that the game loop can go back to the main menu.
+///
+/// ### Returns:
+/// What to do with the autosave, or [`None`] if the [`Player`] changed their mind
+fn confirm_quit(menu: &mut impl Menu) -> Option<Quit> {
+    if !menu.confirm("Quit this run and go back to the main menu?") {
+        return None;
+    }
+
+    Some(if menu.confirm("Save the run so you can resume it later?") { Quit::Save } else { Quit::Discard })
+}
 
-    menu.show_screen(screen);
+/// Tells the [`Player`] that the door to the given [`Room`] can't be opened or closed because the [ship's power][RoomGraph::power_on] is off
+fn show_unpowered_door(menu: &mut impl Menu, to: Room) {
+    menu.show_screen(Screen {
+        title: &format!("The door to the {} won't move", to.get_name()),
+        content: &format!(
+            "You press the button beside the door, but nothing happens. Without power, the doors are stuck where they are until the breakers in the {} are switched back on.",
+            Room::EngineRoom.get_name()
+        ),
+        title_color: None,
+        art: None,
+    });
 }
 
 impl Player {
@@ -72,12 +332,172 @@ impl Player {
     pub fn print_room(&self, menu: &mut impl Menu) {
         let screen = Screen {
             title: &format!("You are in the {}.", self.room.get_name()),
-            content: self.room.get_description(),
+            content: &format!(
+                "{}{}{}{}{}",
+                self.room.get_description(),
+                self.describe_power(),
+                self.describe_hazard(self.room),
+                self.describe_threat(self.room),
+                self.get_room_screen_footer()
+            ),
+            title_color: Some(self.get_room_screen_color()),
+            art: None,
+        };
+
+        menu.show_screen(screen);
+    }
+
+    /// Prints a screen with the details of a [`RoomTransition`] and the player's new [`Room`]
+    fn print_room_transition(&self, transition: &RoomTransition, menu: &mut impl Menu) {
+        let screen = Screen {
+            title: &format!("You go to the {}", transition.prompt_text.unwrap_or_else(||transition.to.get_name())),
+            content: &format!(
+                "{}\nYou are now in the {} - {}{}{}{}{}",
+                transition.message,
+                transition.to.get_name(),
+                transition.to.get_description(),
+                self.describe_power(),
+                self.describe_hazard(transition.to),
+                self.describe_threat(transition.to),
+                self.get_room_screen_footer()
+            ),
+            title_color: Some(self.get_room_screen_color()),
+            art: None,
         };
 
         menu.show_screen(screen);
     }
 
+    /// Gets whether the [`Player`] has escaped the ship, so the run has been won and the win screen should be shown
+    pub const fn has_escaped(&self) -> bool {
+        matches!(self.room, Room::Escape)
+    }
+
+    /// Gets whether the ship's self-destruct countdown has reached zero
+    pub const fn has_self_destructed(&self) -> bool {
+        matches!(self.self_destruct_turns, Some(0))
+    }
+
+    /// Counts down a turn until the ship self-destructs, if it is going to
+    pub fn tick_self_destruct(&mut self) {
+        if let Some(turns) = &mut self.self_destruct_turns {
+            *turns = turns.saturating_sub(1);
+        }
+    }
+
+    /// Gets a line to add to a room's description about the [`Hazard`][crate::rooms::Hazard] in it, or an empty string if there isn't one
+    fn describe_hazard(&self, room: Room) -> String {
+        self.room_graph
+            .get_state(room)
+            .hazard
+            .as_ref()
+            .map_or_else(String::new, |hazard| format!("\n{}", hazard.description))
+    }
+
+    /// Gets a line to add to a room's description if the [ship's power][RoomGraph::power_on] is off, or an empty string if it's on
+    const fn describe_power(&self) -> &'static str {
+        if self.room_graph.power_on {
+            ""
+        } else {
+            "\nThe lights are out. Only the emergency strips along the floor glow a dim red."
+        }
+    }
+
+    /// Gets a line describing the enemy in the given [`Room`] and how [dangerous][combat::rate_threat] a fight with them looks,
+    /// or an empty string if there isn't one
+    fn describe_threat(&self, room: Room) -> String {
+        self.room_graph.get_state(room).enemy.as_ref().map_or_else(String::new, |enemy| {
+            format!("\nThe {} is here. Threat: {}", enemy.name, combat::rate_threat(self, enemy).get_name())
+        })
+    }
+
+    /// Gets the lines to add to the end of room screens, saying what the [active objective][Objectives::get_active] is and how long is left until the ship self-destructs
+    fn get_room_screen_footer(&self) -> String {
+        let objective = self
+            .objectives
+            .get_active()
+            .map_or_else(String::new, |o| format!("\nObjective: {}", o.get_description()));
+
+        let alarm = self.room_graph.alarm;
+        let alarm = if alarm.is_active() {
+            format!("\nThe ship's alarm is sounding (level {}/{}). The crew are looking for you.", alarm.get_level(), config::ALARM_MAX_LEVEL)
+        } else {
+            String::new()
+        };
+
+        format!("{objective}{alarm}{}", self.get_self_destruct_line())
+    }
+
+    /// Raises the ship's [`Alarm`][crate::alarm::Alarm], and tells the [`Player`] why if it got louder.
+    /// Every enemy on the ship becomes [alert][combat::Enemy::alert], so they may [pursue][Player::check_pursuit] the [`Player`].
+    pub fn raise_alarm(&mut self, menu: &mut impl Menu, reason: &str) {
+        if !self.room_graph.alarm.raise() {
+            return;
+        }
+
+        for room_state in self.room_graph.rooms.values_mut() {
+            if let Some(enemy) = &mut room_state.enemy {
+                enemy.alert = true;
+            }
+        }
+
+        self.log(format!("Set off the alarm: {reason}"));
+
+        menu.show_screen(Screen {
+            title: if self.room_graph.alarm.get_level() == 1 { "The alarm goes off" } else { "The alarm gets louder" },
+            content: &format!(
+                "{reason}\nSirens wail throughout the ship (alarm level {}/{}). More of the crew will be out looking for you until it's silenced from the {}.",
+                self.room_graph.alarm.get_level(),
+                config::ALARM_MAX_LEVEL,
+                Room::Bridge.get_name()
+            ),
+            title_color: Some(Color::Red),
+            art: None,
+        });
+    }
+
+    /// Gets a line to add to the end of room screens saying how long is left until the ship self-destructs, or an empty string if it isn't going to.
+    /// This gets more urgent as the deadline nears.
+    fn get_self_destruct_line(&self) -> String {
+        match self.self_destruct_turns {
+            None => String::new(),
+            Some(turns) if turns <= config::SELF_DESTRUCT_CRITICAL_TURNS => format!("\nRED LIGHTS ARE FLASHING. ONLY {turns} TURNS UNTIL SELF-DESTRUCT!"),
+            Some(turns) if turns <= config::SELF_DESTRUCT_WARNING_TURNS => format!("\nAlarms are blaring. The ship will self-destruct in {turns} turns!"),
+            Some(turns) => format!("\nThe ship will self-destruct in {turns} turns."),
+        }
+    }
+
+    /// Gets the title colour for room screens, which changes from cyan to yellow to red as the self-destruct deadline nears
+    const fn get_room_screen_color(&self) -> Color {
+        match self.self_destruct_turns {
+            Some(turns) if turns <= config::SELF_DESTRUCT_CRITICAL_TURNS => Color::Red,
+            Some(turns) if turns <= config::SELF_DESTRUCT_WARNING_TURNS => Color::Yellow,
+            _ => Color::Cyan,
+        }
+    }
+
+    /// Shows a warning [`Screen`] when the self-destruct first gets close, and again on every turn once it is about to go off
+    fn warn_self_destruct(&self, menu: &mut impl Menu) {
+        match self.self_destruct_turns {
+            Some(turns @ 1..) if turns <= config::SELF_DESTRUCT_CRITICAL_TURNS => menu.show_screen(Screen {
+                title: &format!("SELF-DESTRUCT IN {turns}"),
+                content: "The whole ship is shaking. If you aren't off it in the next few moments, you never will be.",
+                title_color: Some(Color::Red),
+                art: None,
+            }),
+            Some(config::SELF_DESTRUCT_WARNING_TURNS) => menu.show_screen(Screen {
+                title: "The self-destruct alarm gets louder",
+                content: &format!(
+                    "\"Warning: self-destruct in {} turns. All crew proceed to the escape pods.\" You don't think they mean you, but you'd better hurry.",
+                    config::SELF_DESTRUCT_WARNING_TURNS
+                ),
+                title_color: Some(Color::Yellow),
+                art: None,
+            }),
+            _ => (),
+        }
+    }
+
     /// Gets a [`String`] representing the number of turns left.
     /// 1 turn = 20 sec
     fn get_remaining_time(&self) -> String {
@@ -86,85 +506,1731 @@ impl Player {
         format!("{mins}:{secs:0<2}")
     }
 
-    /// Asks the user what [`PassiveAction`] to perform given the [`Player`]'s inventory and the current [`RoomState`]
-    fn choose_passive_action(&self, menu: &mut impl Menu) -> PassiveAction {
-        // Init lists of options and their string representations
-        let mut options = vec![PassiveAction::CheckState];
-        let mut options_str = vec!["Check how you're doing".to_string()];
+    /// Asks the user what [`PassiveAction`] to perform given the [`Player`]'s inventory and the current [`RoomState`]
+    #[allow(clippy::too_many_lines)] // Each kind of action is offered in its own block, in the order they are listed
+    fn choose_passive_action(&self, menu: &mut impl Menu) -> PassiveAction {
+        // Until they get past an enemy who hasn't seen them, the player can only fight or try to sneak
+        if let Some(enemy) = self.get_room_state().enemy.as_ref().filter(|_| !self.hidden) {
+            let mut options = vec![
+                PassiveAction::Fight,
+                PassiveAction::Sneak,
+                PassiveAction::CheckState,
+                PassiveAction::OpenInventory,
+                PassiveAction::Help,
+                PassiveAction::InspectConnections,
+                PassiveAction::Settings,
+                // The choice of what to do with the autosave is made after confirming, so this is replaced
+                PassiveAction::QuitToMenu(Quit::Discard),
+            ];
+            let options_str = [
+                format!("Attack the {}", enemy.name),
+                format!("Try to sneak past the {} ({}% chance)", enemy.name, self.get_sneak_chance_percent(enemy)),
+                lang::get("player.action.check_state").to_string(),
+                lang::fill("player.action.inventory", &[&self.get_carried_weight(), &self.max_weight]),
+                "Work out what you can do".to_string(),
+                lang::get("player.action.inspect_connections").to_string(),
+                lang::get("player.action.settings").to_string(),
+                lang::get("player.action.quit").to_string(),
+            ];
+            let prompt = format!("{} - The {} hasn't noticed you yet. What do you do?", self.get_remaining_time(), enemy.name);
+
+            // Loop until the user commits to an action, as they can back out of quitting, and changing the settings doesn't use up the turn
+            loop {
+                let choice = menu.show_option_list(OptionList::new(&options_str, &prompt));
+
+                match options[choice] {
+                    PassiveAction::Settings => settings::show(menu),
+                    PassiveAction::QuitToMenu(_) => {
+                        if let Some(quit) = confirm_quit(menu) {
+                            return PassiveAction::QuitToMenu(quit);
+                        }
+                    }
+                    _ => return options.swap_remove(choice),
+                }
+            }
+        }
+
+        // Init lists of options and their string representations
+        let mut options = vec![
+            PassiveAction::CheckState,
+            PassiveAction::OpenInventory,
+            PassiveAction::Search,
+            PassiveAction::ViewObjectives,
+            PassiveAction::ViewJournal,
+            PassiveAction::ViewStats,
+            PassiveAction::ViewTimer,
+            PassiveAction::ViewAchievements,
+            PassiveAction::Help,
+        ];
+        let mut options_str = vec![
+            lang::get("player.action.check_state").to_string(),
+            lang::fill("player.action.inventory", &[&self.get_carried_weight(), &self.max_weight]),
+            lang::fill("player.action.search", &[&self.room.get_name()]),
+            lang::get("player.action.objectives").to_string(),
+            lang::get("player.action.journal").to_string(),
+            lang::get("player.action.stats").to_string(),
+            lang::get("player.action.timer").to_string(),
+            lang::get("player.action.achievements").to_string(),
+            "Work out what you can do".to_string(),
+        ];
+
+        let room_state = self.get_room_state();
+
+        for connection in &room_state.connections {
+            let name = connection.prompt_text.unwrap_or_else(||connection.to.get_name());
+
+            if connection.door == DoorState::Open {
+                options.push(PassiveAction::GoToRoom(connection));
+                options_str.push(lang::fill("player.action.go", &[&connection.direction.get_name(), &name]));
+            } else {
+                options.push(PassiveAction::OpenDoor(connection.to));
+                options_str.push(lang::fill("player.action.open_door", &[&connection.direction.get_name(), &name]));
+            }
+        }
+
+        for connection in room_state.connections.iter().filter(|c| c.door == DoorState::Open) {
+            options.push(PassiveAction::CloseDoor(connection.to));
+            options_str.push(lang::fill("player.action.close_door", &[&connection.prompt_text.unwrap_or_else(||connection.to.get_name())]));
+        }
+
+        if self.inventory.iter().any(|(item, _)| matches!(item, Item::Lockpick)) {
+            for connection in room_state.connections.iter().filter(|c| c.door == DoorState::Locked && c.lock_pins > 0) {
+                options.push(PassiveAction::PickLock(connection.to));
+                options_str.push(lang::fill("player.action.pick_lock", &[&connection.prompt_text.unwrap_or_else(||connection.to.get_name())]));
+            }
+        }
+
+        if let Some(previous_room) = self.previous_room.filter(|room| self.is_door_open(*room)) {
+            options.push(PassiveAction::GoBack(previous_room));
+            options_str.push(lang::fill("player.action.go_back", &[&previous_room.get_name()]));
+        }
+
+        // The destination is picked from a submenu, so this is replaced
+        let destinations = self.get_fast_travel_destinations();
+        if !destinations.is_empty() {
+            options.push(PassiveAction::FastTravel(self.room, 0));
+            options_str.push(lang::get("player.action.fast_travel").to_string());
+        }
+
+        if self.health < self.max_health {
+            options.push(PassiveAction::Rest);
+            options_str.push(lang::get("player.action.rest").to_string());
+        }
+
+        // A hazard keeps the room's items out of reach, and has to be dealt with before anything else can be done
+        let items = if room_state.hazard.is_some() { &[][..] } else { &room_state.items[..] };
+        for (i, item) in items.iter().enumerate() {
+            options.push(PassiveAction::PickUpItem(i));
+            options_str.push(lang::fill(
+                "player.action.pick_up",
+                &[&item.get_name(), &format!("{}{}", item.get_rarity_label(), item.get_spoiled_label()), &item.get_weight(), &item.get_description()],
+            ));
+        }
+
+        if items.len() >= 2 {
+            options.push(PassiveAction::TakeAll);
+            options_str.push(lang::get("player.action.take_all").to_string());
+        }
+
+        if let Some(npc) = &room_state.npc {
+            options.push(PassiveAction::TalkTo);
+            options_str.push(lang::fill("player.action.talk", &[&npc.name]));
+        }
+
+        let actions = room_state.actions.iter().enumerate().filter(|(_, action)| room_state.hazard.is_none() || action.resolves_hazard());
+        for (i, action) in actions {
+            options.push(PassiveAction::RoomAction(i));
+            options_str.push(action.get_description().to_string());
+        }
+
+        if self.tutorial.enabled {
+            options.push(PassiveAction::SkipTutorial);
+            options_str.push(lang::get("player.action.skip_tutorial").to_string());
+        }
+
+        if dev::is_enabled() {
+            options.extend([PassiveAction::DevTeleport, PassiveAction::DevHeal, PassiveAction::DevSpawnItem]);
+            options_str.extend([
+                "[DEV] Teleport to any room".to_string(),
+                "[DEV] Heal fully".to_string(),
+                "[DEV] Spawn an item".to_string(),
+            ]);
+        }
+
+        options.extend([PassiveAction::InspectConnections, PassiveAction::Settings, PassiveAction::QuitToMenu(Quit::Discard)]);
+        options_str.extend([
+            lang::get("player.action.inspect_connections").to_string(),
+            lang::get("player.action.settings").to_string(),
+            lang::get("player.action.quit").to_string(),
+        ]);
+
+        let prompt = lang::fill("player.action.prompt", &[&self.get_remaining_time()]);
+
+        // Loop until the user commits to an action, as they can go back from the fast travel submenu or from quitting.
+        // Changing the settings doesn't use up the turn either, so the list is shown again afterwards.
+        loop {
+            let option_list = OptionList::new(&options_str, &prompt);
+            let choice = menu.show_option_list(option_list);
+
+            match options[choice] {
+                PassiveAction::FastTravel(..) => (),
+                PassiveAction::Settings => {
+                    settings::show(menu);
+                    continue;
+                }
+                PassiveAction::QuitToMenu(_) => match confirm_quit(menu) {
+                    Some(quit) => return PassiveAction::QuitToMenu(quit),
+                    None => continue,
+                },
+                _ => return options.swap_remove(choice),
+            }
+
+            let destinations_str = destinations
+                .iter()
+                .map(|(room, distance)| lang::fill("player.fast_travel.destination", &[&room.get_name(), distance]))
+                .collect::<Vec<_>>();
+            let list = OptionList::new(&destinations_str, lang::get("player.fast_travel.prompt")).with_cancel_text(lang::get("player.fast_travel.back"));
+
+            if let Some(i) = menu.show_option_list_cancellable(list) {
+                let (room, distance) = destinations[i];
+                return PassiveAction::FastTravel(room, distance);
+            }
+        }
+    }
+
+    /// Gets every [visited][RoomState::visited] [`Room`] the [`Player`] can fast travel to, and how many moves away each one is.
+    /// Fast travel isn't possible with an enemy in the room, and only goes through visited rooms.
+    /// Rooms which are further away than the [`Player`] has turns left for, either before the loop resets or before the ship self-destructs, aren't included.
+    fn get_fast_travel_destinations(&self) -> Vec<(Room, usize)> {
+        if self.get_room_state().enemy.is_some() {
+            return Vec::new();
+        }
+
+        let mut destinations = self
+            .room_graph
+            .rooms
+            .values()
+            .filter(|state| state.visited && state.room != self.room)
+            .filter_map(|state| Some((state.room, self.room_graph.get_visited_distance(self.room, state.room)?)))
+            // The move itself uses this turn, so only the rest need to be left
+            .filter(|(_, distance)| distance - 1 <= self.remaining_turns.min(self.self_destruct_turns.unwrap_or(usize::MAX)))
+            .collect::<Vec<_>>();
+
+        // The rooms come out of a HashMap, so sort them to keep the list in the same order every time
+        destinations.sort_by_key(|(room, _)| room.get_name());
+        destinations
+    }
+
+    /// Sets off the [`Trap`][crate::rooms::Trap] in the current [`Room`], if there is one.
+    /// [One-shot][crate::rooms::Trap::one_shot] traps are disarmed afterwards, so going back in is safe.
+    /// This may kill the [`Player`], so their health should be checked afterwards.
+    fn spring_trap(&mut self, menu: &mut impl Menu) {
+        let Some(trap) = self.get_room_state().trap.clone() else {
+            return;
+        };
+
+        if trap.one_shot {
+            self.get_room_state_mut().trap = None;
+        }
+
+        let damage = self.health.damage(trap.damage);
+        self.stats.record_damage_taken(damage);
+
+        menu.show_screen(Screen {
+            title: trap.name,
+            content: &format!(
+                "{}\nYou lose {} HP.\nYou are now at {}.",
+                trap.description,
+                damage,
+                self.health.display_against(self.max_health)
+            ),
+            title_color: Some(Color::Red),
+            art: None,
+        });
+    }
+
+    /// Gives a [wandering enemy][map::patrolling_guard] a [chance][crate::difficulty::Difficulty::get_encounter_chance_percent] of ambushing the [`Player`] in the current [`Room`].
+    /// The enemy is put into the room, so the battle starts on the next turn.
+    /// Nothing can ambush the [`Player`] in a room which already has an enemy, or once they've made it to the escape pod.
+    /// While there is a [`Distraction`][crate::rooms::Distraction], patrols only turn up in the room the noise is coming from.
+    pub fn roll_random_encounter(&mut self, menu: &mut impl Menu) {
+        if matches!(self.room, Room::EscapePod | Room::Escape) || self.get_room_state().enemy.is_some() {
+            return;
+        }
+
+        if self.room_graph.distraction.is_some_and(|d| d.room != self.room) {
+            return;
+        }
+
+        let alarm = self.room_graph.alarm;
+        if self.rng.below(100) >= settings::get().difficulty.get_encounter_chance_percent() + alarm.get_extra_encounter_chance_percent() {
+            return;
+        }
+
+        let mut enemy = scale_enemy(map::patrolling_guard(), self.level, self.balance, self.new_game_plus_cycle);
+
+        // Guards have already seen the player, and ones responding to the alarm know what they're looking for
+        enemy.alert = true;
+        if alarm.is_active() {
+            enemy.attack_pattern = AttackPattern::Aggressive;
+        }
+
+        menu.show_screen(Screen {
+            title: "You're not alone",
+            content: &format!("A {} rounds the corner on their patrol and stops dead when they see you. They reach for their weapon.", enemy.name),
+            title_color: Some(Color::Red),
+            art: None,
+        });
+
+        self.get_room_state_mut().enemy = Some(enemy);
+    }
+
+    /// Gets whether the door from the current [`Room`] to the given one is [open][DoorState::Open]
+    fn is_door_open(&self, to: Room) -> bool {
+        self.get_room_state().connections.iter().any(|c| c.to == to && c.door == DoorState::Open)
+    }
+
+    /// Plays the lock-picking minigame on the [locked][DoorState::Locked] door from the current [`Room`] to the given one, using the player's [lockpick][Item::Lockpick].
+    /// Each of the lock's [pins][RoomTransition::lock_pins] has to be pushed to the right position, from 1 to [`LOCK_PIN_POSITIONS`][config::LOCK_PIN_POSITIONS],
+    /// which the player types in. After a wrong guess they're told whether to push further or not as far, and typing 0 gives up.
+    ///
+    /// Picking every pin unlocks the door, leaving it closed. Each wrong guess has a [`LOCKPICK_BREAK_PERCENT`][config::LOCKPICK_BREAK_PERCENT] chance of snapping the lockpick,
+    /// and once the player has made [`LOCKPICK_MISTAKES_PER_PIN`][config::LOCKPICK_MISTAKES_PER_PIN] mistakes for each pin in the lock, its tamper alarm [raises the alarm][Player::raise_alarm].
+    fn pick_lock(&mut self, menu: &mut impl Menu, to: Room) {
+        let pins = self.get_room_state().connections.iter().find(|c| c.to == to).unwrap().lock_pins;
+        let mistake_limit = pins * config::LOCKPICK_MISTAKES_PER_PIN;
+        let mut mistakes = 0;
+
+        menu.show_screen(Screen {
+            title: &format!("You kneel down by the lock on the door to the {}", to.get_name()),
+            content: &format!(
+                "It's an old mechanical lock with {pins} pins. Each one needs pushing to just the right position, from 1 to {}, before the next will move. \
+If a pin springs back you'll feel whether you need to push further or not as far - but fumble too much and the lock's tamper alarm will go off.",
+                config::LOCK_PIN_POSITIONS
+            ),
+            title_color: None,
+            art: None,
+        });
+
+        for pin in 1..=pins {
+            let position = self.rng.below(config::LOCK_PIN_POSITIONS) + 1;
+            let mut hint = "";
+
+            loop {
+                let prompt = format!(
+                    "{hint}Pin {pin}/{pins} - how far do you push it, from 1 to {}? ({} mistakes left, or 0 to give up)",
+                    config::LOCK_PIN_POSITIONS,
+                    mistake_limit - mistakes
+                );
+                let guess = menu.prompt_number(&prompt, config::LOCK_PIN_POSITIONS);
+
+                if guess == 0 {
+                    menu.show_screen(Screen {
+                        title: "You give up on the lock",
+                        content: "You ease your lockpick out and the pins fall back into place. Maybe there's another way in.",
+                        title_color: None,
+                        art: None,
+                    });
+                    return;
+                }
+                if guess == position {
+                    break;
+                }
+
+                if self.rng.below(100) < config::LOCKPICK_BREAK_PERCENT {
+                    let i = self.inventory.iter().position(|(item, _)| matches!(item, Item::Lockpick)).unwrap();
+                    self.take_item(i);
+
+                    menu.show_screen(Screen {
+                        title: "Your lockpick snaps",
+                        content: "You twist a little too hard and the pick snaps off in the lock. You'll have to find another way in.",
+                        title_color: Some(Color::Red),
+                        art: None,
+                    });
+                    return;
+                }
+
+                mistakes += 1;
+                if mistakes >= mistake_limit {
+                    menu.show_screen(Screen {
+                        title: "The lock's tamper alarm goes off",
+                        content: "You've been fumbling with the lock for too long. Something inside the door starts beeping shrilly, and the pins drop back into place.",
+                        title_color: Some(Color::Red),
+                        art: None,
+                    });
+                    self.raise_alarm(menu, &format!("The lock on the door to the {} was tampered with.", to.get_name()));
+                    return;
+                }
+
+                hint = if guess < position {
+                    "The pin springs back - it needs pushing further. "
+                } else {
+                    "The pin jams - you pushed it too far. "
+                };
+            }
+        }
+
+        self.room_graph.set_door(self.room, to, DoorState::Closed);
+        self.log(format!("Picked the lock on the door to the {}", to.get_name()));
+
+        menu.show_screen(Screen {
+            title: "The lock clicks open",
+            content: &format!("The last pin slides into place and the lock turns. The door to the {} can be opened now.", to.get_name()),
+            title_color: Some(Color::Green),
+            art: None,
+        });
+    }
+
+    /// Opens the door from the current [`Room`] to the given one, or shows a [`Screen`] saying it is [locked][DoorState::Locked]
+    fn open_door(&mut self, menu: &mut impl Menu, to: Room) {
+        if !self.room_graph.power_on {
+            show_unpowered_door(menu, to);
+            return;
+        }
+
+        let door = self.get_room_state().connections.iter().find(|c| c.to == to).unwrap().door;
+
+        if door == DoorState::Locked {
+            menu.show_screen(Screen {
+                title: &format!("The door to the {} is locked", to.get_name()),
+                content: "You walk up to the door expecting it to slide open when you get close like all the rest, but it doesn't budge.",
+                title_color: None,
+                art: None,
+            });
+            return;
+        }
+
+        self.room_graph.set_door(self.room, to, DoorState::Open);
+
+        menu.show_screen(Screen {
+            title: &format!("You open the door to the {}", to.get_name()),
+            content: "You press the button beside the door and it slides open with a hiss.",
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Closes the door from the current [`Room`] to the given one
+    fn close_door(&mut self, menu: &mut impl Menu, to: Room) {
+        if !self.room_graph.power_on {
+            show_unpowered_door(menu, to);
+            return;
+        }
+
+        self.room_graph.set_door(self.room, to, DoorState::Closed);
+
+        menu.show_screen(Screen {
+            title: &format!("You close the door to the {}", to.get_name()),
+            content: "You press the button beside the door and it slides shut. Anyone who wants to follow you will have to open it again.",
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Moves the [`Player`] into the given [`Room`], marking it as [visited][RoomState::visited]
+    pub fn enter_room(&mut self, room: Room) {
+        self.previous_room = Some(self.room);
+        self.room = room;
+        self.hidden = false;
+        self.stats.distance_traveled += 1;
+
+        if !self.get_room_state().visited {
+            self.log(format!("Found your way into the {}", room.get_name()));
+            self.stats.rooms_visited += 1;
+        }
+        self.get_room_state_mut().visited = true;
+    }
+
+    /// Gets how many turns have passed since the start of the loop
+    const fn get_turn_number(&self) -> usize {
+        config::MAX_TURNS - self.remaining_turns
+    }
+
+    /// Adds an event to the [`Player`]'s [`Journal`], on the current turn
+    pub fn log(&mut self, event: String) {
+        self.journal.add(self.get_turn_number(), event);
+    }
+
+    /// Shows the [player's inventory][Player::inventory], and lets them pick items to examine, use, drop or combine.
+    /// This loops until the user closes the inventory or has nothing left in it.
+    /// If they have chosen an [order][Player::inventory_sort] for their inventory, it is sorted before the list is built each time round.
+    fn open_inventory(&mut self, menu: &mut impl Menu) {
+        loop {
+            if let Some(sort) = self.inventory_sort {
+                self.sort_inventory(sort);
+            }
+
+            if self.inventory.is_empty() {
+                menu.show_screen(Screen {
+                    title: "Your pockets are empty",
+                    content: "You aren't carrying anything.",
+                    title_color: None,
+                    art: None,
+                });
+                return;
+            }
+
+            let stacks = self
+                .inventory
+                .iter()
+                .enumerate()
+                .map(|(i, (item, count))| {
+                    let count = if *count > 1 { format!(" (x{count})") } else { String::new() };
+                    format!(
+                        "{}{}{}{}{} ({} kg)",
+                        item.get_name(),
+                        item.get_rarity_label(),
+                        count,
+                        item.get_spoiled_label(),
+                        self.get_equipped_label(i),
+                        item.get_weight()
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            // Dropping several things at once and sorting are shown after the items, so they don't change their indices
+            let mut options_str = stacks.clone();
+            if stacks.len() >= 2 {
+                options_str.push("Drop several things".to_string());
+                options_str.push("Sort your inventory".to_string());
+            }
+
+            let prompt = format!("Your inventory - {}/{} kg", self.get_carried_weight(), self.max_weight);
+            let list = OptionList::new(&options_str, &prompt).with_cancel_text("Close");
+
+            let Some(i) = menu.show_option_list_cancellable(list) else {
+                return;
+            };
+
+            if i == stacks.len() {
+                self.drop_several_items(menu, &stacks);
+                continue;
+            }
+
+            if i == stacks.len() + 1 {
+                self.choose_inventory_sort(menu);
+                continue;
+            }
+
+            self.choose_inventory_action(menu, i);
+        }
+    }
+
+    /// Asks the user what [`InventoryAction`] to perform with the [`Item`] at the given index into the [player's inventory][Player::inventory], and carries it out.
+    /// Nothing happens if they go back.
+    fn choose_inventory_action(&mut self, menu: &mut impl Menu, i: usize) {
+        let item = &self.inventory[i].0;
+
+        let mut options = vec![InventoryAction::Examine];
+        let mut options_str = vec!["Examine it".to_string()];
+
+        match item {
+            Item::Food(_) => {
+                options.push(InventoryAction::Use);
+                options_str.push("Eat it".to_string());
+            }
+            Item::CaptainsDiary(_) => {
+                options.push(InventoryAction::Use);
+                options_str.push("Read it".to_string());
+            }
+            Item::Bandage => {
+                options.push(InventoryAction::Use);
+                options_str.push("Put it on".to_string());
+            }
+            Item::RepairKit => {
+                options.push(InventoryAction::Use);
+                options_str.push("Repair a weapon with it".to_string());
+            }
+            Item::ShipMap => {
+                options.push(InventoryAction::Use);
+                options_str.push("Look at it".to_string());
+            }
+            Item::Weapon(_) | Item::Armor(_) => {
+                if self.is_equipped(i) {
+                    options.push(InventoryAction::Unequip);
+                    options_str.push("Unequip it".to_string());
+                } else {
+                    options.push(InventoryAction::Equip);
+                    options_str.push("Equip it".to_string());
+                }
+            }
+            _ => (),
+        }
+
+        // Weightless items don't need to be dropped
+        if item.get_weight() > 0 {
+            options.push(InventoryAction::Drop);
+            options_str.push("Drop it".to_string());
+        }
+
+        if self.inventory.len() >= 2 {
+            options.push(InventoryAction::Combine);
+            options_str.push("Try combining it with something else".to_string());
+        }
+
+        if self.inventory[i].1 > 1 {
+            options.push(InventoryAction::Split);
+            options_str.push("Split the stack".to_string());
+        }
+
+        if self.inventory.len() >= 2 {
+            options.push(InventoryAction::Move);
+            options_str.push("Move it somewhere else in your inventory".to_string());
+        }
+
+        let prompt = format!("What do you do with your {}?", item.get_name());
+        let list = OptionList::new(&options_str, &prompt).with_cancel_text("Back");
+
+        let Some(choice) = menu.show_option_list_cancellable(list) else {
+            return;
+        };
+
+        match options[choice] {
+            InventoryAction::Examine => self.examine_item(menu, i),
+            // Several pieces of food can be eaten at once, while other items are only used one at a time
+            InventoryAction::Use if matches!(self.inventory[i].0, Item::Food(_)) => {
+                for _ in 0..self.choose_quantity(menu, i, "eat") {
+                    self.use_item(menu, i);
+                }
+            }
+            InventoryAction::Use => self.use_item(menu, i),
+            InventoryAction::Drop => self.drop_item(menu, i),
+            InventoryAction::Combine => {
+                if let Some(other) = self.choose_item_to_combine_with(menu, i) {
+                    self.combine_items(menu, i, other);
+                }
+            }
+            InventoryAction::Equip => self.equip_item(menu, i),
+            InventoryAction::Unequip => self.unequip_item(menu, i),
+            InventoryAction::Split => self.split_stack(menu, i),
+            InventoryAction::Move => {
+                // Moving something by hand means the player wants to arrange their inventory themselves
+                if let Some(to) = self.choose_stack_position(menu, i) {
+                    self.move_stack(i, to);
+                    self.inventory_sort = None;
+                }
+            }
+        }
+    }
+
+    /// Asks the user how many [`Item`]s to split off the stack at the given index into the [player's inventory][Player::inventory],
+    /// and puts them in a new stack straight after it. Nothing happens if they choose none.
+    /// Only stacks of more than one item can be split, so weapons and armor, which [never stack][Item::can_stack_with], can't be.
+    pub fn split_stack(&mut self, menu: &mut impl Menu, i: usize) {
+        let (item, count) = &self.inventory[i];
+        if *count < 2 || !item.can_stack_with(item) {
+            return;
+        }
+
+        let prompt = format!("How many of your {} do you put in a new stack? You have {count}.", item.get_name());
+        let split = menu.prompt_number(&prompt, count - 1);
+        if split == 0 {
+            return;
+        }
+
+        let item = item.clone();
+        self.inventory[i].1 -= split;
+        self.inventory.push((item, split));
+        self.move_stack(self.inventory.len() - 1, i + 1);
+    }
+
+    /// Asks the user where to move the stack at the given index into the [player's inventory][Player::inventory] to,
+    /// and returns the index it should end up at, or [`None`] if they go back
+    fn choose_stack_position(&self, menu: &mut impl Menu, i: usize) -> Option<usize> {
+        let options = (0..self.inventory.len())
+            .map(|to| match to.cmp(&i) {
+                Ordering::Equal => "Leave it where it is".to_string(),
+                Ordering::Less => format!("Before your {}", self.inventory[to].0.get_name()),
+                Ordering::Greater => format!("After your {}", self.inventory[to].0.get_name()),
+            })
+            .collect::<Vec<_>>();
+
+        let prompt = format!("Where do you put your {}?", self.inventory[i].0.get_name());
+        menu.show_option_list_cancellable(OptionList::new(&options, &prompt).with_cancel_text("Back"))
+    }
+
+    /// Moves the stack at index `from` in the [player's inventory][Player::inventory] so that it ends up at index `to`,
+    /// keeping track of which items are equipped
+    pub fn move_stack(&mut self, from: usize, to: usize) {
+        let stack = self.inventory.remove(from);
+        self.inventory.insert(to, stack);
+
+        for equipped in [&mut self.equipped_weapon, &mut self.equipped_armor, &mut self.equipped_off_hand] {
+            *equipped = equipped.map(|e| match e {
+                e if e == from => to,
+                e if from < e && e <= to => e - 1,
+                e if to <= e && e < from => e + 1,
+                e => e,
+            });
+        }
+    }
+
+    /// Asks the user which [`InventorySort`] to keep their [inventory][Player::inventory] in, and sorts it.
+    /// The order is kept until they move a stack by hand. Nothing changes if they go back.
+    fn choose_inventory_sort(&mut self, menu: &mut impl Menu) {
+        let options: Vec<_> = InventorySort::ALL.iter().map(|sort| sort.get_name().to_string()).collect();
+        let list = OptionList::new(&options, "How do you sort your inventory? It will stay that way until you move something yourself.")
+            .with_cancel_text("Back");
+
+        if let Some(choice) = menu.show_option_list_cancellable(list) {
+            self.inventory_sort = Some(InventorySort::ALL[choice]);
+            self.sort_inventory(InventorySort::ALL[choice]);
+        }
+    }
+
+    /// Sorts the [player's inventory][Player::inventory] into the given order.
+    /// Stacks which compare equal stay in the order they were in, and equipped items stay equipped.
+    pub fn sort_inventory(&mut self, sort: InventorySort) {
+        let mut order: Vec<usize> = (0..self.inventory.len()).collect();
+        order.sort_by(|&a, &b| sort.compare(&self.inventory[a].0, &self.inventory[b].0));
+
+        for equipped in [&mut self.equipped_weapon, &mut self.equipped_armor, &mut self.equipped_off_hand] {
+            *equipped = equipped.and_then(|e| order.iter().position(|&i| i == e));
+        }
+
+        self.inventory = order.iter().map(|&i| self.inventory[i].clone()).collect();
+    }
+
+    /// Gets whether the [`Item`] at the given index into the [player's inventory][Player::inventory] is equipped
+    fn is_equipped(&self, i: usize) -> bool {
+        [self.equipped_weapon, self.equipped_armor, self.equipped_off_hand].contains(&Some(i))
+    }
+
+    /// Gets a label to show after the name of the [`Item`] at the given index into the [player's inventory][Player::inventory] if it is equipped, or an empty string otherwise
+    fn get_equipped_label(&self, i: usize) -> &'static str {
+        if self.is_equipped(i) {
+            " (equipped)"
+        } else {
+            ""
+        }
+    }
+
+    /// Equips the weapon or armor at the given index into the [player's inventory][Player::inventory], replacing whatever was in its slot.
+    /// This can only be done from the [inventory][Player::open_inventory], so the [`Player`] can't change their equipment mid-battle.
+    ///
+    /// A [two-handed][Weapon::two_handed] weapon and an [off-hand][ArmorSlot::OffHand] item can't be equipped together.
+    /// Trying to equip one while the other is equipped shows a [`Screen`] explaining why, and leaves the equipment as it was.
+    fn equip_item(&mut self, menu: &mut impl Menu, i: usize) {
+        let item = &self.inventory[i].0;
+
+        let content = match item {
+            Item::Weapon(w) if w.two_handed && self.equipped_off_hand.is_some() => {
+                let Some(off_hand) = self.equipped_off_hand else {unreachable!()};
+                menu.show_screen(Screen {
+                    title: &format!("You can't equip your {}", w.name),
+                    content: &format!(
+                        "It needs both hands, and you're holding your {} in your off hand. Unequip it first.",
+                        self.inventory[off_hand].0.get_name()
+                    ),
+                    title_color: None,
+                    art: None,
+                });
+                return;
+            }
+            Item::Armor(a) if a.slot == ArmorSlot::OffHand && self.get_equipped_weapon().is_some_and(|w| w.two_handed) => {
+                let Some(w) = self.get_equipped_weapon() else {unreachable!()};
+                menu.show_screen(Screen {
+                    title: &format!("You can't equip your {}", a.name),
+                    content: &format!("Your {} needs both hands, so you have no hand free to hold it. Unequip it first.", w.name),
+                    title_color: None,
+                    art: None,
+                });
+                return;
+            }
+            Item::Weapon(w) => {
+                self.equipped_weapon = Some(i);
+                format!("You ready your {}. It's the weapon you'll reach for when you see an opening.", w.name)
+            }
+            Item::Armor(a) => {
+                match a.slot {
+                    ArmorSlot::Body => self.equipped_armor = Some(i),
+                    ArmorSlot::OffHand => self.equipped_off_hand = Some(i),
+                }
+                format!("You put on your {}. Hits will deal {} less damage while you wear it.", a.name, a.defense)
+            }
+            _ => unreachable!(),
+        };
+
+        menu.show_screen(Screen {
+            title: &format!("You equip your {}", item.get_name()),
+            content: &content,
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Unequips the weapon or armor at the given index into the [player's inventory][Player::inventory]
+    fn unequip_item(&mut self, menu: &mut impl Menu, i: usize) {
+        if self.equipped_weapon == Some(i) {
+            self.equipped_weapon = None;
+        }
+        if self.equipped_armor == Some(i) {
+            self.equipped_armor = None;
+        }
+        if self.equipped_off_hand == Some(i) {
+            self.equipped_off_hand = None;
+        }
+
+        menu.show_screen(Screen {
+            title: &format!("You unequip your {}", self.inventory[i].0.get_name()),
+            content: "You put it away, but keep it with you.",
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Gets the [`Armor`] the [`Player`] has equipped in the given slot, if any
+    fn get_equipped_armor(&self, slot: ArmorSlot) -> Option<&Armor> {
+        let i = match slot {
+            ArmorSlot::Body => self.equipped_armor?,
+            ArmorSlot::OffHand => self.equipped_off_hand?,
+        };
+
+        match &self.inventory[i].0 {
+            Item::Armor(a) => Some(a),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Gets the [`Weapon`] the [`Player`] fights with outside of their chosen attacks, such as when counter-attacking.
+    /// This is their [equipped weapon][Player::equipped_weapon], or the first one they [can wield][Player::can_wield] if none is equipped.
+    pub fn get_main_weapon(&self) -> Option<&Weapon> {
+        self.get_equipped_weapon().or_else(|| {
+            self.inventory.iter().find_map(|(item, _)| match item {
+                Item::Weapon(w) if self.can_wield(w) => Some(w),
+                _ => None,
+            })
+        })
+    }
+
+    /// Gets how far the [`Player`] can reach with their [main weapon][Player::get_main_weapon], or [`UNARMED_REACH`][config::UNARMED_REACH] without one
+    pub fn get_reach(&self) -> usize {
+        self.get_main_weapon().map_or(config::UNARMED_REACH, |w| w.reach)
+    }
+
+    /// Gets whether the [`Player`] can swing the given [`Weapon`]. [Two-handed][Weapon::two_handed] weapons can't be used while something is held in the off hand.
+    pub fn can_wield(&self, weapon: &Weapon) -> bool {
+        !weapon.two_handed || self.equipped_off_hand.is_none()
+    }
+
+    /// Gets the [`Weapon`] the [`Player`] has equipped, if any
+    pub fn get_equipped_weapon(&self) -> Option<&Weapon> {
+        match &self.inventory[self.equipped_weapon?].0 {
+            Item::Weapon(w) => Some(w),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Gets the total [defense][Armor::defense] of the [`Player`]'s [equipped armor][Player::equipped_armor] and [off-hand item][Player::equipped_off_hand]
+    pub fn get_defense(&self) -> usize {
+        [ArmorSlot::Body, ArmorSlot::OffHand]
+            .into_iter()
+            .filter_map(|slot| self.get_equipped_armor(slot))
+            .map(|a| a.defense.as_usize())
+            .sum::<usize>()
+            + if self.fresh_turns > 0 { config::FRESH_DEFENSE_BONUS } else { 0 }
+    }
+
+    /// Deals a hit of the given damage to the [`Player`] in battle, reduced by their [defense][Player::get_defense].
+    /// Returns how much damage they actually took.
+    pub fn take_hit(&mut self, damage: Damage) -> Damage {
+        let damage = Damage::new(damage.as_usize().saturating_sub(self.get_defense()));
+
+        let damage = self.health.damage(damage);
+        self.stats.record_damage_taken(damage);
+        damage
+    }
+
+    /// Shows a [`Screen`] describing the [`Item`] at the given index into the [player's inventory][Player::inventory]
+    fn examine_item(&self, menu: &mut impl Menu, i: usize) {
+        let (item, count) = &self.inventory[i];
+
+        let rarity = match item.get_rarity() {
+            Rarity::Common => String::new(),
+            rarity => format!("\nIt's {}, so it's {}% better than usual.", rarity.get_name().to_lowercase(), rarity.get_stat_percent() - 100),
+        };
+
+        let details = match item {
+            Item::Food(f) => format!("\nIt heals for {} HP and fills you up by {}.", f.get_heals_for(), f.fills_for),
+            Item::Explosive => format!("\nIt deals {} damage to the enemy you throw it at, and {} to you if they're up close when it goes off.", config::EXPLOSIVE_DAMAGE, config::EXPLOSIVE_SELF_DAMAGE),
+            Item::Bandage => format!("\nIt heals for {} HP a turn for {} turns.", config::BANDAGE_HEAL_PER_TURN, config::BANDAGE_DURATION_TURNS),
+            Item::Weapon(w) => format!(
+                "\nIt deals {} {} damage straight on, {} if they dodge into it, and {} if thrown. It has a reach of {}, and {}/{} durability left.",
+                self.get_attack_damage(w.straight_damage),
+                w.damage_type.get_name(),
+                self.get_attack_damage(w.dodge_damage),
+                self.get_attack_damage(w.throw_damage),
+                w.reach,
+                w.durability,
+                w.max_durability
+            ),
+            _ => String::new(),
+        };
+
+        menu.show_screen(Screen {
+            title: &format!("You examine your {}{}{}", item.get_name(), item.get_rarity_label(), item.get_spoiled_label()),
+            content: &format!(
+                "{}{}{}\nYou have {} of them, weighing {} kg each.",
+                item.get_description(),
+                rarity,
+                details,
+                count,
+                item.get_weight()
+            ),
+            title_color: item.get_rarity().get_color(),
+            art: None,
+        });
+    }
+
+    /// Asks the user which [`Item`] to combine with the one at the given index into the [player's inventory][Player::inventory].
+    /// Returns the index of the other item, or [`None`] if the user goes back.
+    fn choose_item_to_combine_with(&self, menu: &mut impl Menu, first: usize) -> Option<usize> {
+        // An item can't be combined with itself, so leave it out of the list
+        let others = (0..self.inventory.len()).filter(|i| *i != first).collect::<Vec<_>>();
+        let others_str = others.iter().map(|i| self.inventory[*i].0.get_name().to_string()).collect::<Vec<_>>();
+
+        let prompt = format!("What do you combine your {} with?", self.inventory[first].0.get_name());
+        let list = OptionList::new(&others_str, &prompt).with_cancel_text("Back");
+        let second = menu.show_option_list_cancellable(list)?;
+
+        Some(others[second])
+    }
+
+    /// Gets how much health the [`Player`] recovers each turn outside of combat.
+    /// The [`Player`] can only rest in rooms without an enemy or a [`Hazard`][crate::rooms::Hazard], and can't recover at all while starving or suffocating.
+    fn get_regen_rate(&self) -> Damage {
+        let room_state = self.get_room_state();
+        if room_state.enemy.is_some() || room_state.hazard.is_some() || self.is_starving() || self.is_suffocating() {
+            return Damage::new(0);
+        }
+
+        config::REGEN_PER_TURN
+    }
+
+    /// Ages all of the [`Food`] in the game by a turn, both in the [player's inventory][Player::inventory] and lying around in every room
+    fn age_food(&mut self) {
+        items::age_food(self.inventory.iter_mut().map(|(item, _)| item));
+
+        for room_state in self.room_graph.rooms.values_mut() {
+            items::age_food(&mut room_state.items);
+            items::age_food(&mut room_state.hidden_items);
+        }
+    }
+
+    /// Gets whether the [`Player`] is hungry enough to be losing health each turn
+    const fn is_starving(&self) -> bool {
+        self.hunger >= config::HUNGER_STARVING
+    }
+
+    /// Gets a short description of how hungry the [`Player`] is
+    const fn get_hunger_description(&self) -> &'static str {
+        if self.is_starving() {
+            "starving"
+        } else if self.hunger >= config::HUNGER_WARNING {
+            "hungry"
+        } else {
+            "fine for food"
+        }
+    }
+
+    /// Gets whether the [`Player`] has run out of oxygen, and is losing health each turn
+    const fn is_suffocating(&self) -> bool {
+        self.oxygen == 0
+    }
+
+    /// Applies the effects of a turn passing outside of combat.
+    /// This may kill the [`Player`], so their health should be checked afterwards.
+    ///
+    /// Every per-turn effect is applied here, in order: the countdowns tick down, then the [`Player`]'s needs grow, then they are warned about anything
+    /// that is getting close, then they take damage from any needs that have run out, and finally they regenerate if they still can.
+    fn pass_turn(&mut self, menu: &mut impl Menu) {
+        self.remaining_turns -= 1;
+        self.stats.turns_taken += 1;
+        self.tick_self_destruct();
+
+        self.hunger += 1;
+        self.oxygen = self.oxygen.saturating_sub(1);
+        self.regen_stamina();
+        self.age_food();
+
+        self.warn_self_destruct(menu);
+
+        if self.oxygen == config::OXYGEN_WARNING {
+            menu.show_screen(Screen {
+                title: "Your breathing gets heavy",
+                content: "The air on the ship is thin at the best of times, and you've been running around. You'll need to find more oxygen soon.",
+                title_color: Some(Color::Yellow),
+                art: None,
+            });
+        }
+
+        if self.hunger == config::HUNGER_WARNING {
+            menu.show_screen(Screen {
+                title: "Your stomach rumbles",
+                content: "You can't remember the last time you ate. If you don't find something soon, you'll be too weak to go on.",
+                title_color: Some(Color::Yellow),
+                art: None,
+            });
+        }
+
+        if self.is_starving() {
+            let damage = self.health.damage(config::STARVATION_DAMAGE);
+            self.stats.record_damage_taken(damage);
+
+            let screen = Screen {
+                title: "You're starving",
+                content: &format!(
+                    "Your head is spinning and your legs feel like lead. You lose {} HP.\nYou are now at {}.",
+                    damage,
+                    self.health.display_against(self.max_health)
+                ),
+                title_color: Some(Color::Red),
+                art: None,
+            };
+            menu.show_screen(screen);
+        }
+
+        if self.is_suffocating() {
+            let damage = self.health.damage(config::SUFFOCATION_DAMAGE);
+            self.stats.record_damage_taken(damage);
+
+            let screen = Screen {
+                title: "You're suffocating",
+                content: &format!(
+                    "You gasp for air, but there's nothing left to breathe. You lose {} HP.\nYou are now at {}.",
+                    damage,
+                    self.health.display_against(self.max_health)
+                ),
+                title_color: Some(Color::Red),
+                art: None,
+            };
+            menu.show_screen(screen);
+        }
+
+        if let Some(hazard) = self.get_room_state().hazard.clone() {
+            let damage = self.health.damage(hazard.damage);
+            self.stats.record_damage_taken(damage);
+
+            menu.show_screen(Screen {
+                title: hazard.name,
+                content: &format!(
+                    "{} You lose {} HP.\nYou are now at {}.",
+                    hazard.description,
+                    damage,
+                    self.health.display_against(self.max_health)
+                ),
+                title_color: Some(Color::Red),
+                art: None,
+            });
+        }
+
+        self.health.heal_to_max(self.get_regen_rate(), self.max_health);
+
+        if self.tick_bandage().is_some() && self.bandage_turns == 0 {
+            menu.show_screen(Screen {
+                title: "Your bandage comes loose",
+                content: &format!("The gauze has done all it can. You peel it off and toss it aside.\nYou are now at {}.", self.health.display_against(self.max_health)),
+                title_color: None,
+                art: None,
+            });
+        }
+
+        if self.room_graph.tick_distraction() {
+            menu.show_screen(Screen {
+                title: "The crowd noise dies down",
+                content: "Somewhere on the ship, the volleyball game goes quiet. The crew will be paying attention again.",
+                title_color: None,
+                art: None,
+            });
+        }
+
+        if self.fresh_turns > 0 {
+            self.fresh_turns -= 1;
+            if self.fresh_turns == 0 {
+                menu.show_screen(Screen {
+                    title: "You don't feel so fresh any more",
+                    content: "The glow from your shower has worn off. Running around a ship full of people trying to kill you will do that.",
+                    title_color: None,
+                    art: None,
+                });
+            }
+        }
+    }
+
+    /// Gets why the [`Player`] should stop [resting][Player::rest], or [`None`] if they can carry on
+    fn get_rest_interruption(&self) -> Option<&'static str> {
+        let turns_left = self.self_destruct_turns.map_or(self.remaining_turns, |t| t.min(self.remaining_turns));
+
+        if self.health.is_dead() {
+            Some("You never get up again.")
+        } else if self.get_room_state().enemy.is_some() {
+            Some("Someone's here. You're on your feet in an instant.")
+        } else if self.get_room_state().hazard.is_some() {
+            Some("It's far too dangerous to rest in here.")
+        } else if self.health >= self.max_health {
+            Some("You feel as good as new.")
+        } else if turns_left <= config::REST_STOP_TURNS_LEFT {
+            Some("You can't afford to wait around any longer.")
+        } else if self.hunger >= config::HUNGER_WARNING {
+            Some("Your stomach is growling too loudly to rest.")
+        } else if self.oxygen <= config::OXYGEN_WARNING {
+            Some("The air is getting too thin to sit still.")
+        } else {
+            None
+        }
+    }
+
+    /// Passes turns until the [`Player`] is at full health, or until they get hungry, run low on oxygen or time, or an enemy turns up.
+    /// Each turn is passed the same way as any other, so [random encounters][Player::roll_random_encounter] and [pursuing][Player::check_pursuit] enemies can interrupt them.
+    /// A [`Screen`] then sums up how long they rested for and why they stopped.
+    fn rest(&mut self, menu: &mut impl Menu) {
+        let start_health = self.health;
+        let mut turns = 1;
+
+        let reason = loop {
+            if let Some(reason) = self.get_rest_interruption() {
+                break reason;
+            }
+
+            self.pass_turn(menu);
+            self.roll_random_encounter(menu);
+            self.check_pursuit(menu);
+            turns += 1;
+        };
+
+        menu.show_screen(Screen {
+            title: &format!("You rest for {turns} turns"),
+            content: &format!(
+                "You sit down somewhere out of the way and catch your breath, recovering {} HP. {reason}\nYou are now at {}.",
+                self.health.as_usize().saturating_sub(start_health.as_usize()),
+                self.health.display_against(self.max_health)
+            ),
+            title_color: Some(Color::Green),
+            art: None,
+        });
+    }
+
+    /// Asks the user for any [`Room`] and moves the [`Player`] straight there, ignoring doors. Used in [developer mode][dev].
+    /// [`Room::Escape`] is included, to test the ending without flying the escape pod.
+    fn dev_teleport(&mut self, menu: &mut impl Menu) {
+        let mut rooms: Vec<Room> = self.room_graph.rooms.keys().copied().collect();
+        rooms.sort();
+
+        let rooms_str: Vec<_> = rooms.iter().map(|room| format!("{room:?}")).collect();
+        let Some(i) = menu.show_option_list_cancellable(OptionList::new(&rooms_str, "[DEV] Where do you teleport to?")) else {
+            return;
+        };
+
+        // The escape isn't a real room to walk into, so the player is put there directly, like when the escape pod takes off
+        if rooms[i] == Room::Escape {
+            self.room = Room::Escape;
+            return;
+        }
+
+        self.enter_room(rooms[i]);
+        self.print_room(menu);
+    }
+
+    /// Heals the [`Player`] to full health and stamina. Used in [developer mode][dev].
+    fn dev_heal(&mut self, menu: &mut impl Menu) {
+        self.health = self.max_health;
+        self.stamina = self.max_stamina;
+
+        menu.show_screen(Screen {
+            title: "[DEV] You are fully healed",
+            content: &format!("You are at {} and {}/{} stamina.", self.health.display_against(self.max_health), self.stamina, self.max_stamina),
+            title_color: Some(Color::Green),
+            art: None,
+        });
+    }
+
+    /// Asks the user for any [`Item`] from [`map::ITEMS`] and gives it to the [`Player`]. Used in [developer mode][dev].
+    fn dev_spawn_item(&mut self, menu: &mut impl Menu) {
+        let items_str: Vec<_> = map::ITEMS.iter().map(|(_, item)| item.get_name().to_string()).collect();
+
+        if let Some(i) = menu.show_option_list_cancellable(OptionList::new(&items_str, "[DEV] What do you spawn?")) {
+            self.pick_up_item(menu, map::ITEMS[i].1.clone());
+        }
+    }
+
+    /// Shows a [`Screen`] with the [map][RoomGraph::render_map] of the ship from the [deck plans][Item::ShipMap]
+    fn show_ship_map(&mut self, menu: &mut impl Menu) {
+        // The plans may have been carried over from before the loop reset, so the rooms are revealed again just in case
+        self.room_graph.reveal_all();
+
+        menu.show_screen(Screen {
+            title: "You unfold the deck plans",
+            content: &format!(
+                "You're in the {}. Solid lines are routes you've taken, and dashed lines are ones you've only seen on paper.\n\n{}",
+                self.room.get_name(),
+                self.room_graph.render_map()
+            ),
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Asks the user which [damaged][Weapon::is_damaged] weapon to fix with the [repair kit][Item::RepairKit] at the given index into the [player's inventory][Player::inventory].
+    /// The kit is used up and the weapon's durability restored by [`REPAIR_KIT_AMOUNT`][config::REPAIR_KIT_AMOUNT], up to its max.
+    /// If nothing needs repairing or the user goes back, the kit is kept.
+    fn use_repair_kit(&mut self, menu: &mut impl Menu, kit: usize) {
+        let damaged: Vec<usize> = (0..self.inventory.len())
+            .filter(|&i| matches!(&self.inventory[i].0, Item::Weapon(w) if w.is_damaged()))
+            .collect();
+
+        if damaged.is_empty() {
+            menu.show_screen(Screen {
+                title: "Nothing needs fixing",
+                content: "You look over your weapons, but none of them are worn enough to be worth patching up. You put the repair kit away for later.",
+                title_color: None,
+                art: None,
+            });
+            return;
+        }
+
+        let options_str: Vec<String> = damaged
+            .iter()
+            .map(|&i| {
+                let Item::Weapon(w) = &self.inventory[i].0 else {unreachable!()};
+                format!("{} ({}/{} durability)", w.name, w.durability, w.max_durability)
+            })
+            .collect();
+
+        let list = OptionList::new(&options_str, "Which weapon do you repair?").with_cancel_text("Back");
+        let Some(choice) = menu.show_option_list_cancellable(list) else {
+            return;
+        };
+
+        let Item::Weapon(w) = &mut self.inventory[damaged[choice]].0 else {unreachable!()};
+        let before = w.durability;
+        w.durability = (w.durability + config::REPAIR_KIT_AMOUNT).min(w.max_durability);
+        let screen_title = format!("You repair your {}", w.name);
+        let screen_content = format!(
+            "You tighten, glue and file down everything you can. It's not as good as new, but it's close.\nDurability: {before}/{max} -> {}/{max}",
+            w.durability,
+            max = w.max_durability
+        );
+
+        self.take_item(kit);
+
+        menu.show_screen(Screen {
+            title: &screen_title,
+            content: &screen_content,
+            title_color: Some(Color::Green),
+            art: None,
+        });
+    }
+
+    /// Puts on the [bandage][Item::Bandage] at the given index into the [player's inventory][Player::inventory] outside of a battle, and shows a [`Screen`] saying so
+    fn put_on_bandage(&mut self, menu: &mut impl Menu, i: usize) {
+        self.take_item(i);
+        self.apply_bandage();
+
+        menu.show_screen(Screen {
+            title: "You put on your Bandage",
+            content: &format!(
+                "You wrap the gauze tightly around your worst wound. It will heal you by {} HP per turn for the next {} turns.",
+                config::BANDAGE_HEAL_PER_TURN,
+                config::BANDAGE_DURATION_TURNS
+            ),
+            title_color: Some(Color::Green),
+            art: None,
+        });
+    }
+
+    /// Puts on a [bandage][Item::Bandage], which heals the [`Player`] over the next [`BANDAGE_DURATION_TURNS`][config::BANDAGE_DURATION_TURNS] turns.
+    /// Putting on a new bandage replaces the old one rather than adding to it.
+    pub fn apply_bandage(&mut self) {
+        self.bandage_turns = config::BANDAGE_DURATION_TURNS;
+    }
+
+    /// Heals the [`Player`] by a turn's worth of their [bandage][Item::Bandage], if they are wearing one.
+    /// Returns how much health they recovered, or [`None`] if they aren't wearing a bandage.
+    pub fn tick_bandage(&mut self) -> Option<Damage> {
+        if self.bandage_turns == 0 {
+            return None;
+        }
+
+        self.bandage_turns -= 1;
+        Some(self.health.heal_to_max(config::BANDAGE_HEAL_PER_TURN, self.max_health))
+    }
+
+    /// Gets a line describing the [`Player`]'s [bandage][Item::Bandage], or an empty string if they aren't wearing one
+    fn get_bandage_description(&self) -> String {
+        if self.bandage_turns == 0 {
+            return String::new();
+        }
+
+        format!("Your bandage is healing you by {} HP per turn for {} more turns\n", config::BANDAGE_HEAL_PER_TURN, self.bandage_turns)
+    }
+
+    /// Gets short labels for every timed effect on the [`Player`] in battle, with how many turns each has left, for the [status line][combat::describe_statuses]
+    pub fn get_status_effects(&self) -> Vec<String> {
+        [("bandage", self.bandage_turns), ("fresh", self.fresh_turns), ("no dodge", self.dodge_cooldown)]
+            .into_iter()
+            .filter(|(_, turns)| *turns > 0)
+            .map(|(name, turns)| format!("{name} {turns}t"))
+            .collect()
+    }
+
+    /// Gets a line describing how fresh the [`Player`] feels after a shower, or an empty string if they don't
+    fn get_fresh_description(&self) -> String {
+        if self.fresh_turns == 0 {
+            return String::new();
+        }
+
+        format!("You feel fresh from your shower, giving you {} extra defense for {} more turns\n", config::FRESH_DEFENSE_BONUS, self.fresh_turns)
+    }
+
+    /// Eats a [`Food`] item, healing the [`Player`] and reducing their hunger.
+    /// Returns how much health the [`Player`] recovered.
+    pub fn eat(&mut self, food: &Food) -> Damage {
+        self.hunger = self.hunger.saturating_sub(food.fills_for);
+        self.health.heal_to_max(food.get_heals_for(), self.max_health)
+    }
+
+    /// Gets a [`PassiveAction`] from the user and carries it out.
+    /// The [`Player`] may die while the turn passes, in which case no action is taken.
+    pub fn take_passive_action(&mut self, menu: &mut impl Menu) {
+        self.pass_turn(menu);
+
+        if self.health.is_dead() {
+            return;
+        }
+
+        let action = self.choose_passive_action(menu);
+
+        match action {
+            PassiveAction::CheckState => self.print_state(menu),
+            PassiveAction::GoToRoom(r) => {
+                self.print_room_transition(r, menu);
+                let to = r.to;
+                self.enter_room(to);
+                self.spring_trap(menu);
+                self.roll_random_encounter(menu);
+            }
+            PassiveAction::OpenDoor(room) => {
+                let locked = self.get_room_state().connections.iter().any(|c| c.to == room && c.door == DoorState::Locked);
+                self.open_door(menu, room);
+                if locked {
+                    self.tutorial.show(menu, TutorialPrompt::LockedDoor);
+                }
+            }
+            PassiveAction::CloseDoor(room) => self.close_door(menu, room),
+            PassiveAction::PickLock(room) => self.pick_lock(menu, room),
+            PassiveAction::GoBack(room) => {
+                menu.show_screen(Screen {
+                    title: &format!("You go back to the {}", room.get_name()),
+                    content: &format!("You retrace your steps. {}{}{}", room.get_description(), self.describe_hazard(room), self.get_room_screen_footer()),
+                    title_color: Some(self.get_room_screen_color()),
+                    art: None,
+                });
+                // Only the move is undone, and it can't be undone twice
+                self.enter_room(room);
+                self.previous_room = None;
+                self.spring_trap(menu);
+            }
+            PassiveAction::FastTravel(room, distance) => {
+                menu.show_screen(Screen {
+                    title: &format!("You make your way to the {}", room.get_name()),
+                    content: &format!("It takes you {distance} turns to cross the ship. {}{}{}", room.get_description(), self.describe_hazard(room), self.get_room_screen_footer()),
+                    title_color: Some(self.get_room_screen_color()),
+                    art: None,
+                });
+
+                // This turn has already passed, so only the rest of the journey needs to
+                for _ in 1..distance {
+                    self.pass_turn(menu);
+                }
+                // Entering the room counts the last move, so only the rooms on the way need adding
+                self.stats.distance_traveled += distance.saturating_sub(1);
+                self.enter_room(room);
+                self.spring_trap(menu);
+            }
+            PassiveAction::PickUpItem(i) => {
+                self.pick_up_item_from_room(menu, i);
+                self.tutorial.show(menu, TutorialPrompt::PickUp);
+            }
+            PassiveAction::TakeAll => self.take_all(menu),
+            PassiveAction::Search => self.search_room(menu),
+            PassiveAction::Rest => self.rest(menu),
+            PassiveAction::ViewAchievements => self.achievements.show(menu),
+            PassiveAction::ViewObjectives => self.objectives.show(menu),
+            PassiveAction::ViewJournal => self.journal.show(menu),
+            PassiveAction::ViewStats => self.stats.show(menu),
+            PassiveAction::ViewTimer => self.timer.show(menu),
+            PassiveAction::TalkTo => self.talk_to_npc(menu),
+            PassiveAction::OpenInventory => self.open_inventory(menu),
+            PassiveAction::Help => self.show_help(menu),
+            PassiveAction::SkipTutorial => self.skip_tutorial(menu),
+            PassiveAction::Sneak => self.sneak(menu),
+            PassiveAction::DevTeleport => self.dev_teleport(menu),
+            PassiveAction::DevHeal => self.dev_heal(menu),
+            PassiveAction::DevSpawnItem => self.dev_spawn_item(menu),
+            PassiveAction::InspectConnections => self.inspect_connections(menu),
+            // The settings were already changed while the action was being chosen
+            PassiveAction::Settings => (),
+            PassiveAction::QuitToMenu(quit) => self.quit = Some(quit),
+            PassiveAction::Fight => {
+                // Attacking gives the player away, so the battle starts straight away
+                if let Some(enemy) = &mut self.get_room_state_mut().enemy {
+                    enemy.alert = true;
+                }
+            }
+            PassiveAction::RoomAction(i) => {
+                let action = self.get_room_state_mut().actions.remove(i); // Take action out of vec to avoid multiple mutable references
+                let result = action.execute(self, menu);
+
+                if let Some(message) = result.message {
+                    menu.show_screen(message);
+                }
+
+                if let Some(item) = result.item {
+                    self.pick_up_item(menu, item);
+                    self.tutorial.show(menu, TutorialPrompt::PickUp);
+                }
+
+                if result.show_again {
+                    self.get_room_state_mut().actions.insert(i, action); // Put action back if needed
+                }
+            }
+        }
+
+        self.check_pursuit(menu);
+        self.check_objectives(menu);
+        self.check_achievements(menu);
+    }
+
+    /// Gives any [alert][crate::combat::Enemy::alert] enemies in the rooms next to the [`Player`] a chance of following them in through an open door.
+    /// Closed doors stop enemies from following, and only one enemy can follow into a room at a time.
+    pub fn check_pursuit(&mut self, menu: &mut impl Menu) {
+        if self.get_room_state().enemy.is_some() {
+            return;
+        }
+
+        let neighbours: Vec<_> = self
+            .get_room_state()
+            .connections
+            .iter()
+            .filter(|c| c.door == DoorState::Open)
+            .map(|c| c.to)
+            .collect();
+
+        for room in neighbours {
+            // Distracted enemies are too busy to notice the player going past
+            let is_alert = self.room_graph.get_state(room).enemy.as_ref().is_some_and(|e| e.alert) && !self.room_graph.is_distracted(room);
+
+            if is_alert && self.rng.below(100) < config::PURSUIT_CHANCE_PERCENT {
+                let enemy = self.room_graph.get_state_mut(room).enemy.take();
+                let Some(enemy) = enemy else {unreachable!()};
+
+                menu.show_screen(Screen {
+                    title: &format!("The {} followed you", enemy.name),
+                    content: &format!("You hear footsteps behind you. The {} has chased you in from the {}.", enemy.name, room.get_name()),
+                    title_color: Some(Color::Red),
+                    art: None,
+                });
+
+                self.get_room_state_mut().enemy = Some(enemy);
+                return;
+            }
+        }
+    }
+
+    /// Gets the percentage chance that the [`Player`] sneaks past the given enemy without being noticed.
+    /// This goes up as the [`Player`] levels up, and down for more [aware][combat::Enemy::awareness] enemies.
+    /// It is also easier while the enemy is [distracted][crate::rooms::Distraction], or in the dark while the [ship's power][RoomGraph::power_on] is off.
+    pub fn get_sneak_chance_percent(&self, enemy: &combat::Enemy) -> usize {
+        let distraction = if self.room_graph.is_distracted(self.room) { config::DISTRACTION_SNEAK_BONUS_PERCENT } else { 0 };
+        let dark = if self.room_graph.power_on { 0 } else { config::DARK_SNEAK_BONUS_PERCENT };
+
+        (config::SNEAK_BASE_CHANCE_PERCENT + (self.level - 1) * config::SNEAK_CHANCE_PER_LEVEL_PERCENT + distraction + dark)
+            .saturating_sub(enemy.awareness)
+            .min(100)
+    }
+
+    /// Tries to sneak past the enemy in the current room.
+    /// If it works, the [`Player`] is [hidden][Player::hidden] and can carry on as normal until they leave the room.
+    /// If it doesn't, the enemy gets a free hit in, becomes [alert][combat::Enemy::alert] so the battle starts, and raises the alarm.
+    pub fn sneak(&mut self, menu: &mut impl Menu) {
+        let Some(enemy) = self.get_room_state().enemy.as_ref() else {
+            return;
+        };
+        let name = enemy.name;
+        let chance = self.get_sneak_chance_percent(enemy);
+        let first_hit = enemy.inventory.iter().find_map(|item| match item {
+            Item::Weapon(w) => Some((w.name, w.straight_damage)),
+            _ => None,
+        });
+
+        if self.rng.below(100) < chance {
+            self.hidden = true;
+            self.log(format!("Snuck past the {name}"));
+
+            menu.show_screen(Screen {
+                title: &format!("You slip past the {name}"),
+                content: &format!("Keeping low and moving slowly, you creep past the {name}. They don't so much as look up. As long as you keep quiet, you can go about your business."),
+                title_color: Some(Color::Green),
+                art: None,
+            });
+            return;
+        }
+
+        let hit_text = match first_hit {
+            Some((weapon, damage)) => {
+                let damage = self.take_hit(damage);
+                format!("They catch you with their {weapon} before you can react, dealing {damage} damage.\nYou are now at {}.", self.health.display_against(self.max_health))
+            }
+            None => "Luckily, they don't have a weapon to hand.".to_string(),
+        };
+
+        menu.show_screen(Screen {
+            title: &format!("The {name} spots you"),
+            content: &format!("Your foot catches on something and the {name} spins round. {hit_text}"),
+            title_color: Some(Color::Red),
+            art: None,
+        });
+
+        if let Some(enemy) = &mut self.get_room_state_mut().enemy {
+            enemy.alert = true;
+        }
+        self.raise_alarm(menu, &format!("The {name} shouted for help when they spotted you."));
+    }
+
+    /// Gets the [`Room`]s the [`Player`] could [flee][combat::Action::Flee] into from a battle, through the open doors out of the current room
+    pub fn get_flee_destinations(&self) -> Vec<Room> {
+        self.get_room_state()
+            .connections
+            .iter()
+            .filter(|c| c.door == DoorState::Open && c.to != Room::Escape)
+            .map(|c| c.to)
+            .collect()
+    }
+
+    /// Completes any [`Objectives`] whose conditions have been met
+    fn check_objectives(&mut self, menu: &mut impl Menu) {
+        let has = |f: fn(&Item) -> bool| self.inventory.iter().any(|(item, _)| f(item));
+
+        if has(|i| matches!(i, Item::EscapePodKeys)) {
+            self.objectives.complete(menu, Objective::FindKeyCard);
+        }
+        if has(|i| matches!(i, Item::Maps)) {
+            self.objectives.complete(menu, Objective::FindMaps);
+        }
+
+        match self.room {
+            Room::EscapePod => self.objectives.complete(menu, Objective::ReachEscapePod),
+            Room::Escape => self.objectives.complete(menu, Objective::Escape),
+            _ => (),
+        }
+    }
+
+    /// Talks to the [`Npc`] in the current room. If they start a fight, they are replaced by the [`Enemy`][crate::combat::Enemy] they become,
+    /// and if they join the [`Player`], they become one of their [companions][Player::companions].
+    fn talk_to_npc(&mut self, menu: &mut impl Menu) {
+        // Take the npc out of the room to avoid multiple mutable references
+        let mut npc = self.get_room_state_mut().npc.take().unwrap();
 
-        let room_state = self.get_room_state();
+        match npc.talk(self, menu) {
+            ConversationResult::Ended => self.get_room_state_mut().npc = Some(npc),
+            ConversationResult::Fight(enemy) => {
+                let mut enemy = scale_enemy(*enemy, self.level, self.balance, self.new_game_plus_cycle);
+                enemy.alert = true;
+                self.get_room_state_mut().enemy = Some(enemy);
+            }
+            ConversationResult::Joined => self.companions.push(npc),
+        }
+    }
 
-        for connection in &room_state.connections {
-            options.push(PassiveAction::GoToRoom(connection));
-            options_str.push(format!(
-                "Go to the {}",
-                connection.prompt_text.unwrap_or_else(||connection.to.get_name())
-            ));
+    /// Makes the [`Player`] choose which of their [companions][Player::companions] come with them in the [escape pod][Room::EscapePod],
+    /// if there are more of them than there are [seats][config::ESCAPE_POD_SEATS] left. Anyone who isn't chosen is [left behind][Player::left_behind].
+    ///
+    /// ### Returns:
+    /// `false` if the [`Player`] backed out of choosing, in which case nobody is left behind
+    pub fn choose_pod_passengers(&mut self, menu: &mut impl Menu) -> bool {
+        let seats = config::ESCAPE_POD_SEATS - 1;
+        if self.companions.len() <= seats {
+            return true;
         }
 
-        for (i, item) in room_state.items.iter().enumerate() {
-            options.push(PassiveAction::PickUpItem(i));
-            options_str.push(format!(
-                "Pick up the {} - {}",
-                item.get_name(),
-                item.get_description()
-            ));
+        let mut passengers = Vec::new();
+        while passengers.len() < seats {
+            let options: Vec<_> = self.companions.iter().map(|npc| npc.name.to_string()).collect();
+            let prompt = format!("The pod only has room for {seats} of them. Who comes with you?");
+            let list = OptionList::new(&options, &prompt).with_cancel_text("Not yet");
+
+            let Some(choice) = menu.show_option_list_cancellable(list) else {
+                self.companions.append(&mut passengers);
+                return false;
+            };
+            passengers.push(self.companions.remove(choice));
         }
 
-        for (i, action) in room_state.actions.iter().enumerate() {
-            options.push(PassiveAction::RoomAction(i));
-            options_str.push(action.get_description().to_string());
+        self.left_behind = self.companions.drain(..).map(|npc| npc.name).collect();
+        self.companions = passengers;
+        true
+    }
+
+    /// Unlocks any [`Achievements`] which depend on where the [`Player`] is or what they are carrying
+    fn check_achievements(&mut self, menu: &mut impl Menu) {
+        match self.room {
+            Room::EscapePod => self.achievements.unlock(menu, Achievement::ReachedEscapePod),
+            Room::Escape => self.achievements.unlock(menu, Achievement::Escaped),
+            _ => (),
         }
 
-        for (i, item) in self.inventory.iter().enumerate() {
-            match item {
-                Item::Food(f) => {
-                    options.push(PassiveAction::UseItem(i));
-                    options_str.push(format!("Eat your {}", f.name));
-                }
-                Item::CaptainsDiary(_) => {
-                    options.push(PassiveAction::UseItem(i));
-                    options_str.push("Read the captain's diary".to_string());
-                }
-                _ => ()
-            }
+        let has = |f: fn(&Item) -> bool| self.inventory.iter().any(|(item, _)| f(item));
+        if has(|i| matches!(i, Item::Maps)) && has(|i| matches!(i, Item::EscapePodKeys)) && has(|i| matches!(i, Item::CaptainsDiary(_))) {
+            self.achievements.unlock(menu, Achievement::Collector);
         }
+    }
 
-        let prompt = format!("{} - What do you do?", self.get_remaining_time());
-        let option_list = OptionList::new(&options_str, &prompt);
+    /// Shows a [`Screen`] listing where each of the current room's connections leads, whether its door is open, closed or locked, and whether the [`Player`] has been there.
+    /// Only what the [`Player`] could know is shown - a room they haven't been to is called by its [prompt text][RoomTransition::prompt_text] if it has one,
+    /// and a [`Hazard`][crate::rooms::Hazard] is only mentioned if they have been there or can see it through an open door.
+    fn inspect_connections(&self, menu: &mut impl Menu) {
+        let connections = self.get_room_state().connections.iter().fold(String::new(), |mut list, c| {
+            let state = self.room_graph.get_state(c.to);
+            let name = c.prompt_text.filter(|_| !state.visited).unwrap_or_else(|| c.to.get_name());
+            let door = match c.door {
+                DoorState::Open => "the door is open",
+                DoorState::Closed => "the door is closed",
+                DoorState::Locked => "the door is locked",
+            };
+            let visited = if state.visited { "you've been there before" } else { "you haven't been there yet" };
+            let hazard = if state.hazard.is_some() && (state.visited || c.door == DoorState::Open) { " It isn't safe in there." } else { "" };
 
-        let choice = menu.show_option_list(option_list);
+            // Writing to a String can't fail
+            let _ = writeln!(list, "• {} to the {name}: {door}, and {visited}.{hazard}", c.direction.get_name());
+            list
+        });
 
-        options.swap_remove(choice)
+        menu.show_screen(Screen {
+            title: &format!("The ways out of the {}", self.room.get_name()),
+            content: connections.trim_end(),
+            title_color: None,
+            art: None,
+        });
     }
 
-    /// Gets a [`PassiveAction`] from the user and carries it out
-    pub fn take_passive_action(&mut self, menu: &mut impl Menu) {
-        self.remaining_turns -= 1;
-        
-        let action = self.choose_passive_action(menu);
+    /// Turns off the [`Tutorial`], and tells the [`Player`] they're on their own
+    fn skip_tutorial(&mut self, menu: &mut impl Menu) {
+        self.tutorial.enabled = false;
+        menu.show_screen(Screen {
+            title: "Tutorial turned off",
+            content: "You're on your own from here. You can still work out what you can do from the list of actions.",
+            title_color: None,
+            art: None,
+        });
+    }
 
-        match action {
-            PassiveAction::CheckState => self.print_state(menu),
-            PassiveAction::GoToRoom(r) => {
-                print_room_transition(r, menu);
-                self.room = r.to;
-            }
-            PassiveAction::UseItem(i) => self.use_item(menu, i),
-            PassiveAction::PickUpItem(i) => self.pick_up_item_from_room(i),
-            PassiveAction::RoomAction(i) => {
-                let action = self.get_room_state_mut().actions.remove(i); // Take action out of vec to avoid multiple mutable references
-                let result = action.execute(self);
+    /// Shows a [`Screen`] explaining how to explore the ship, based on where the [`Player`] is and what they are carrying
+    fn show_help(&self, menu: &mut impl Menu) {
+        let connections = self.get_room_state().connections.iter().fold(String::new(), |mut list, c| {
+            let door = match c.door {
+                DoorState::Open => "",
+                DoorState::Closed => " (the door is closed, so open it first)",
+                DoorState::Locked => " (the door is locked, so you'll need to find a way to open it)",
+            };
+            // Writing to a String can't fail
+            let _ = writeln!(list, "• {} to the {}{door}", c.direction.get_name(), c.to.get_name());
+            list
+        });
 
-                if let Some(message) = result.message {
-                    menu.show_screen(message);
-                }
+        let mut items = String::new();
+        let has = |f: fn(&Item) -> bool| self.inventory.iter().any(|(item, _)| f(item));
+        if has(|i| matches!(i, Item::Food(_))) {
+            items += "• Food can be eaten from your inventory or during a fight to heal you, and stops you going hungry\n";
+        }
+        if has(|i| matches!(i, Item::Weapon(_))) {
+            items += "• Weapons can be equipped from your inventory, and are used to attack in fights. They wear down with each attack, and break when they run out of durability\n";
+        }
+        if has(|i| matches!(i, Item::Armor(_))) {
+            items += "• Armor can be equipped from your inventory to reduce the damage you take\n";
+        }
+        if has(|i| matches!(i, Item::Bandage)) {
+            items += "• Bandages can be put on from your inventory or during a fight to heal you a little each turn\n";
+        }
+        if has(|i| matches!(i, Item::RepairKit)) {
+            items += "• Repair kits can be used from your inventory to restore a worn weapon's durability\n";
+        }
+        if has(|i| matches!(i, Item::ShipMap)) {
+            items += "• The deck plans can be looked at from your inventory to see the whole ship\n";
+        }
+        if has(|i| matches!(i, Item::Explosive)) {
+            items += "• Explosives can only be thrown during a fight, at the enemy you're fighting\n";
+        }
+        if has(|i| matches!(i, Item::FireExtinguisher)) {
+            items += "• A fire extinguisher can put out a fire, but it's used up afterwards\n";
+        }
+        if has(|i| matches!(i, Item::Lockpick)) {
+            items += "• A lockpick can be used on some locked doors instead of their key\n";
+        }
+        if has(|i| matches!(i, Item::CaptainsDiary(_))) {
+            items += "• Some things, like the captain's diary, can be read from your inventory\n";
+        }
+        if has(|i| matches!(i, Item::Maps | Item::EscapePodKeys)) {
+            items += "• Some things are needed to escape the ship, so hold on to them\n";
+        }
+        if items.is_empty() {
+            items += "• You aren't carrying anything yet. Pick up items from the room you're in, or search it to find hidden ones\n";
+        }
 
-                if result.show_again {
-                    self.get_room_state_mut().actions.insert(i, action); // Put action back if needed
-                }
+        menu.show_screen(Screen {
+            title: "You take a moment to think about what you can do",
+            content: &format!(
+                "Moving:\nEach action takes a turn. From the {}, you can go:\n{connections}Once you've been somewhere, you can head straight back to it, but the journey takes a turn for every room on the way.\n\nEnemies:\nIf someone hasn't noticed you yet, you can try to sneak past them instead of fighting. If they spot you, they'll get the first hit in and raise the alarm.\n\nItems:\n{items}You can carry up to {} kg, and can combine items in your inventory to make new ones.\n\nTime:\nYou have {} until the ISPD agents arrive. If you die or run out of time, the loop resets and you start again from the {}.\n\nControls:\n{}",
+                self.room.get_name(),
+                self.max_weight,
+                self.get_remaining_time(),
+                STARTING_ROOM.get_name(),
+                menu.get_controls()
+            ),
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Shows a [`Screen`] explaining how fighting works, based on what the [`Player`] is carrying and how much stamina they have
+    fn show_combat_help(&self, menu: &mut impl Menu) {
+        let weapons = self.inventory.iter().fold(String::new(), |mut list, (item, _)| {
+            if let Item::Weapon(w) = item {
+                // Writing to a String can't fail
+                let _ = writeln!(
+                    list,
+                    "• Your {} does {} damage straight ahead, {} damage if you aim the way they dodge, and {} damage thrown. It has a reach of {}",
+                    w.name,
+                    self.get_attack_damage(w.straight_damage),
+                    self.get_attack_damage(w.dodge_damage),
+                    self.get_attack_damage(w.throw_damage),
+                    w.reach
+                );
             }
-        }
+            list
+        });
+        let weapons = if weapons.is_empty() { "• You don't have any weapons, so find one before your next fight\n".to_string() } else { weapons };
+
+        let food = self.inventory.iter().filter(|(item, _)| matches!(item, Item::Food(_))).map(|(_, count)| count).sum::<usize>();
+
+        let combos = config::COMBOS.iter().fold(String::new(), |mut list, combo| {
+            // Writing to a String can't fail
+            let _ = writeln!(list, "• {}: {} ({} extra damage)", combo.name, combo.describe_steps(), combo.bonus_damage);
+            list
+        });
+
+        menu.show_screen(Screen {
+            title: "You try to remember your combat training",
+            content: &format!(
+                "Each turn, you and your enemy both choose an action at the same time.\n\nAttacking:\nAttacks straight ahead hit unless your enemy dodges. Attacks to the left or right only hit if they dodge that way. A weapon with a longer reach can still catch a dodge.\n{weapons}\nDefending:\nDodging to the left or right avoids attacks straight ahead. Blocking lets some of the damage through. Both cost stamina, and you have {}/{} stamina. After dodging, you need {} turn(s) to catch your breath before you can dodge again.\n{}\n\nCombos:\nTake these actions on turns in a row, and if the last attack hits, you follow through for extra damage.\n{combos}\nFood:\nYou have {food} food to eat, which heals you but takes your turn.\n\nRunning:\nIf there's an open door, you can run for it. Your enemy may follow you, unless you close the door behind you.\n\nControls:\n{}",
+                self.stamina,
+                self.max_stamina,
+                config::DODGE_COOLDOWN_TURNS,
+                config::COMBAT_RESOLUTION.describe(),
+                menu.get_controls()
+            ),
+            title_color: None,
+            art: None,
+        });
     }
 
     /// Prints the [`Player`]'s room and health
@@ -172,223 +2238,833 @@ impl Player {
         let screen = Screen {
             title: "You take a moment to rest and check your body for injuries",
             content: &format!(
-                "You are in the {} - {}\nYou are at {}/{} HP\nYou have:\n{}• {} to get off the ship\n",
+                "You are in the {} - {}\nYou are at {}, and recovering {} HP per turn while you're out of combat\n{}{}You are level {} ({})\nYou have {} strength\nYou are wielding {}, wearing {} and holding {} in your off hand\nYou are {} (hunger {}/{})\nYou have {}/{} oxygen left\nYou are carrying {}/{} kg\nYou have {} gold\nYou have:\n{}• {} to get off the ship\n",
                 self.room.get_name(),
                 self.room.get_description(),
-                self.health,
-                self.max_health,
-                self.inventory
-                    .iter()
-                    .map(|item| format!("• {} - {}\n", item.get_name(), item.get_description()))
-                    .collect::<String>(),
+                self.health.display_against(self.max_health),
+                self.get_regen_rate(),
+                self.get_bandage_description(),
+                self.get_fresh_description(),
+                self.level,
+                self.get_xp_description(),
+                self.strength,
+                self.get_equipped_weapon().map_or("nothing in particular", |w| w.name),
+                self.get_equipped_armor(ArmorSlot::Body).map_or("no armor", |a| a.name),
+                self.get_equipped_armor(ArmorSlot::OffHand).map_or("nothing", |a| a.name),
+                self.get_hunger_description(),
+                self.hunger,
+                config::HUNGER_STARVING,
+                self.oxygen,
+                config::PLAYER_MAX_OXYGEN,
+                self.get_carried_weight(),
+                self.max_weight,
+                self.gold,
+                list_item_stacks(&self.inventory),
                 self.get_remaining_time()
             ),
+            title_color: None,
+            art: None,
         };
 
         menu.show_screen(screen);
     }
 
-    /// Uses the [`Item`] at the given index into the [`Player`]'s inventory
+    /// Uses the [`Item`] at the given index into the [`Player`]'s inventory.
+    /// Only items which [`choose_inventory_action`][Player::choose_inventory_action] offers to use can be passed in.
     fn use_item(&mut self, menu: &mut impl Menu, i: usize) {
-        match &mut self.inventory[i] {
-            Item::Food(f) => {
-                let prev_health = self.health;
-                self.health.heal_to_max(f.heals_for, self.max_health);
+        match &mut self.inventory[i].0 {
+            Item::Food(_) => {
+                let Item::Food(f) = self.take_item(i) else {unreachable!()};
+                let healed = self.eat(&f);
 
                 let screen = Screen {
                     title: &format!("You ate your {}", f.name),
                     content: &format!(
-                        "You are healed by {} HP.\nYou are now at {}/{} HP.",
-                        self.health - prev_health,
-                        self.health,
-                        self.max_health
+                        "You are healed by {} HP.\nYou are now at {}.\nYou are {} (hunger {}/{}).",
+                        healed,
+                        self.health.display_against(self.max_health),
+                        self.get_hunger_description(),
+                        self.hunger,
+                        config::HUNGER_STARVING
                     ),
+                    title_color: Some(Color::Green),
+                    art: None,
                 };
 
                 menu.show_screen(screen);
-
-                self.inventory.remove(i);
             }
+            Item::RepairKit => self.use_repair_kit(menu, i),
+            Item::ShipMap => self.show_ship_map(menu),
+            Item::Bandage => self.put_on_bandage(menu, i),
             Item::CaptainsDiary(ref mut page) => {
                 let screen;
                 (screen, *page) = match *page {
                     0 => (Screen {
                         title: "You read the last page: 15/08/2168 - Found someone in the cold",
                         content: "Found a body in the cold. Ship was all busted up so we thought we'd be holding a funeral but turns out they had a pulse. \
-They've been out for a few hours now, but still breathing. They look harmless but they're wearing an Arnithian military uniform so we're treating them like a prisoner."
+They've been out for a few hours now, but still breathing. They look harmless but they're wearing an Arnithian military uniform so we're treating them like a prisoner.",
+                        title_color: None,
+                        art: None,
                     }, 1),
                     
                     1 => (Screen {
                         title: "You start at the beginning: 01/01/2168 - Another revolution, another revelation",
                         content: "It occurred to me during the festivities that I didn't know why a revolution was called that. Turns out it's based on roughly the time Earth took to complete one orbit of its star. \
-Early humans called it a 'year' and it was an important unit of time due to the seasonal nature of their planet."
+Early humans called it a 'year' and it was an important unit of time due to the seasonal nature of their planet.",
+                        title_color: None,
+                        art: None,
                     }, 2),
 
                     2 => (Screen {
                         title: "03/02/2168 - Big setback today, the Arnithians seem to have ultra-powerful fighter jet engines",
-                        content: "They can go from 0-60 in half a second - the engineers reckon that needs 200kN of thrust but the engines don't look much different. Can't imagine what that would do to a pilot's neck"
+                        content: "They can go from 0-60 in half a second - the engineers reckon that needs 200kN of thrust but the engines don't look much different. Can't imagine what that would do to a pilot's neck",
+                        title_color: None,
+                        art: None,
                     }, 3),
 
                     3 => (Screen {
                         title: "17/02/2168 - More bad news about the new jets",
-                        content: "They seem to have some automated dodging capabilities. We can't hit them with anything. Their comms seems to be on all different frequencies and the patterns are all over the place - much faster than normal."
+                        content: "They seem to have some automated dodging capabilities. We can't hit them with anything. Their comms seems to be on all different frequencies and the patterns are all over the place - much faster than normal.",
+                        title_color: None,
+                        art: None,
                     }, 4),
 
                     4 => (Screen {
                         title: "05/04/2168 - Home is in sight",
-                        content: "The commander says I can have a few scores off after we pick up the new recruits. Sounds good to me."
+                        content: "The commander says I can have a few scores off after we pick up the new recruits. Sounds good to me.",
+                        title_color: None,
+                        art: None,
                     }, 5),
 
                     5 => (Screen {
                         title: "01/06/2168 - Something about the new jets seems off",
                         content: "They're messing up all our instruments - according to my scanner, the pilot of one of them was 90 degrees! I'm really not sure what's going on - maybe they're all remote and it's to mess with our heads.\
-In any case, whatever they're doing is working. We need to step up our game before they start deploying these everywhere."
+In any case, whatever they're doing is working. We need to step up our game before they start deploying these everywhere.",
+                        title_color: None,
+                        art: None,
                     }, 6),
 
                     6 => (Screen {
                         title: "20/06/2168 - Going home sooner than I thought",
-                        content: "The commander moved forward picking up the recruits because we need more officers on the front lines. I'll be leaving in a few cycles, but it'll only be three of us"
+                        content: "The commander moved forward picking up the recruits because we need more officers on the front lines. I'll be leaving in a few cycles, but it'll only be three of us",
+                        title_color: None,
+                        art: None,
                     }, 7),
 
                     7 => (Screen {
                         title: "10/07/2168 - Had to confiscate the darts",
                         content: "Juuran keeps throwing them at the windows. I know they're double reinforced but from my point of view there's no point in testing that. Besides, it makes a damn annoying noise. \
-I've not hidden them but Juuran knows there'll be trouble if they take them."
+I've not hidden them but Juuran knows there'll be trouble if they take them.",
+                        title_color: None,
+                        art: None,
                     }, 8),
 
                     p => (Screen {
                         title: "There's no more pages",
-                        content: "How disappointing."
+                        content: "How disappointing.",
+                        title_color: None,
+                        art: None,
                     }, p)
                 };
 
                 menu.show_screen(screen);
             }
-            _ => panic!("Only food items can be used outside of combat")
+            _ => unreachable!("Only food, bandages, repair kits, the ship map and the captain's diary can be used from the inventory")
+        }
+    }
+
+    /// Removes an [`Item`] from the current [`RoomState`] at the specified index and adds it to the [player's inventory][Player::inventory].
+    /// If the item is too heavy to carry, it is left where it was.
+    fn pick_up_item_from_room(&mut self, menu: &mut impl Menu, i: usize) {
+        let item = self.get_room_state_mut().items.remove(i);
+
+        if let Err(item) = self.try_pick_up_item(menu, item) {
+            self.get_room_state_mut().items.insert(i, item);
         }
     }
 
-    /// Removes an [`Item`] from the current [`RoomState`] at the specified index and adds it to the [player's inventory][Player::inventory]
-    fn pick_up_item_from_room(&mut self, i: usize) {
+    /// Moves any [hidden items][RoomState::hidden_items] in the current [`RoomState`] into its [`items`][RoomState::items] so the [`Player`] can pick them up.
+    /// Traps in the rooms next to the current one are also spotted and disarmed, so the [`Player`] can search before stepping in.
+    fn search_room(&mut self, menu: &mut impl Menu) {
+        self.disarm_nearby_traps(menu);
+
+        let room_name = self.room.get_name();
         let room_state = self.get_room_state_mut();
-        let item = room_state.items.remove(i);
-        self.pick_up_item(item);
+
+        if room_state.hidden_items.is_empty() {
+            let screen = Screen {
+                title: &format!("You search the {room_name}"),
+                content: "You look in every corner, but there's nothing more to find here.",
+                title_color: None,
+                art: None,
+            };
+            menu.show_screen(screen);
+            return;
+        }
+
+        let found = list_items(&room_state.hidden_items);
+
+        let hidden_items = std::mem::take(&mut room_state.hidden_items);
+        room_state.items.extend(hidden_items);
+
+        let screen = Screen {
+            title: &format!("You search the {room_name}"),
+            content: &format!("You look in every corner, and your patience pays off. You find:\n{found}"),
+            title_color: None,
+            art: None,
+        };
+        menu.show_screen(screen);
+    }
+
+    /// Disarms the [`Trap`][crate::rooms::Trap] in every [`Room`] connected to the current one, showing a [`Screen`] for each
+    fn disarm_nearby_traps(&mut self, menu: &mut impl Menu) {
+        let trapped_rooms = self
+            .get_room_state()
+            .connections
+            .iter()
+            .map(|c| c.to)
+            .filter(|room| self.room_graph.get_state(*room).trap.is_some())
+            .collect::<Vec<_>>();
+
+        for room in trapped_rooms {
+            self.room_graph.get_state_mut(room).trap = None;
+
+            menu.show_screen(Screen {
+                title: &format!("You spot a trap in the {}", room.get_name()),
+                content: "Peering through the doorway, you notice something waiting for whoever walks in next. You carefully disarm it.",
+                title_color: Some(Color::Yellow),
+                art: None,
+            });
+        }
+    }
+
+    /// Moves the [`Item`] at the given index into the [player's inventory][Player::inventory] to the current [`RoomState`]
+    fn drop_item(&mut self, menu: &mut impl Menu, i: usize) {
+        let quantity = self.choose_quantity(menu, i, "drop");
+        if quantity == 0 {
+            return;
+        }
+
+        let items: Vec<Item> = (0..quantity).map(|_| self.take_item(i)).collect();
+        let count = if quantity > 1 { format!(" (x{quantity})") } else { String::new() };
+
+        let screen = Screen {
+            title: &format!("You put down your {}{count}", items[0].get_name()),
+            content: &format!(
+                "You leave it in the {}. You're now carrying {}/{} kg.",
+                self.room.get_name(),
+                self.get_carried_weight(),
+                self.max_weight
+            ),
+            title_color: None,
+            art: None,
+        };
+        menu.show_screen(screen);
+
+        self.get_room_state_mut().items.extend(items);
+    }
+
+    /// Asks the user how many [`Item`]s to take from the stack at the given index into the [player's inventory][Player::inventory], for the given verb, e.g. 'drop'.
+    /// Stacks of one item aren't asked about. This may return 0 if the user changes their mind.
+    pub fn choose_quantity(&self, menu: &mut impl Menu, i: usize, verb: &str) -> usize {
+        let (item, count) = &self.inventory[i];
+        if *count == 1 {
+            return 1;
+        }
+
+        menu.prompt_number(&format!("How many of your {} do you {verb}? You have {count}.", item.get_name()), *count)
+    }
+
+    /// Asks the user to pick any number of stacks from the [player's inventory][Player::inventory], and drops all of them in the current room.
+    /// `stacks` are the descriptions of each stack to show the user. Nothing happens if they don't pick anything.
+    fn drop_several_items(&mut self, menu: &mut impl Menu, stacks: &[String]) {
+        let list = OptionList::new(stacks, "What do you drop? Pick each thing to mark it.").with_cancel_text("Drop them");
+        let chosen = menu.show_multi_select(list);
+
+        if chosen.is_empty() {
+            return;
+        }
+
+        // Stacks are taken from the back, so taking one doesn't change the indices of the others
+        let mut dropped = Vec::new();
+        for &i in chosen.iter().rev() {
+            for _ in 0..self.inventory[i].1 {
+                dropped.push(self.take_item(i));
+            }
+        }
+        dropped.reverse();
+
+        menu.show_screen(Screen {
+            title: "You empty your pockets",
+            content: &format!(
+                "You leave these in the {}:\n{}You're now carrying {}/{} kg.",
+                self.room.get_name(),
+                list_items(&dropped),
+                self.get_carried_weight(),
+                self.max_weight
+            ),
+            title_color: None,
+            art: None,
+        });
+
+        self.get_room_state_mut().items.extend(dropped);
+    }
+
+    /// Combines the [`Item`]s at the two given indices into the [player's inventory][Player::inventory], if there is a [recipe][items::combine] for them.
+    /// The inputs are used up, and the new item is picked up, or left in the room if it is too heavy.
+    fn combine_items(&mut self, menu: &mut impl Menu, a: usize, b: usize) {
+        let Some(output) = items::combine(&self.inventory[a].0, &self.inventory[b].0) else {
+            let screen = Screen {
+                title: "Nothing happens",
+                content: &format!(
+                    "You try combining your {} with your {}, but nothing useful comes of it.",
+                    self.inventory[a].0.get_name(),
+                    self.inventory[b].0.get_name()
+                ),
+                title_color: None,
+                art: None,
+            };
+            menu.show_screen(screen);
+            return;
+        };
+
+        // Take from the later stack first, so that the earlier one's index doesn't change if the later stack empties
+        let later = self.take_item(a.max(b));
+        let earlier = self.take_item(a.min(b));
+
+        let screen = Screen {
+            title: &format!("You made a {}", output.get_name()),
+            content: &format!(
+                "You combine your {} and your {}. {}",
+                earlier.get_name(),
+                later.get_name(),
+                output.get_description()
+            ),
+            title_color: Some(Color::Green),
+            art: None,
+        };
+        menu.show_screen(screen);
+
+        self.pick_up_item(menu, output);
+    }
+
+    /// Removes one [`Item`] from the stack at the given index into the [player's inventory][Player::inventory] and returns it.
+    /// The stack is only removed once its last item is taken.
+    pub fn take_item(&mut self, i: usize) -> Item {
+        let (item, count) = &mut self.inventory[i];
+
+        if *count > 1 {
+            *count -= 1;
+            return item.clone();
+        }
+
+        shift_equipped_index(&mut self.equipped_weapon, i);
+        shift_equipped_index(&mut self.equipped_armor, i);
+        shift_equipped_index(&mut self.equipped_off_hand, i);
+        self.inventory.remove(i).0
+    }
+
+    /// Gets the total [weight][Item::get_weight] of the items in the [player's inventory][Player::inventory], in kg
+    pub fn get_carried_weight(&self) -> usize {
+        self.inventory.iter().map(|(item, count)| item.get_weight() * count).sum()
+    }
+
+    /// Add an item to the [player's inventory][Player::inventory].
+    /// If the item would take the [`Player`] over their [`max_weight`][Player::max_weight], a screen is shown and the item is left in the current room instead.
+    pub fn pick_up_item(&mut self, menu: &mut impl Menu, item: Item) {
+        if let Err(item) = self.try_pick_up_item(menu, item) {
+            self.get_room_state_mut().items.push(item);
+        }
+    }
+
+    /// Checks whether the door to the [escape pod][Room::EscapePod] has been unlocked
+    fn is_escape_pod_unlocked(&self) -> bool {
+        self.room_graph
+            .get_state(Room::CrewArea)
+            .connections
+            .iter()
+            .any(|c| c.to == Room::EscapePod && c.door != DoorState::Locked)
+    }
+
+    /// Add an item to the [player's inventory][Player::inventory] if it isn't too heavy to carry.
+    /// If it is, a screen is shown and the item is given back as an [`Err`] value.
+    /// [`Currency`][Item::Currency] is added to the [player's gold][Player::gold] instead.
+    pub fn try_pick_up_item(&mut self, menu: &mut impl Menu, item: Item) -> Result<(), Item> {
+        let carried_weight = self.get_carried_weight();
+
+        let Err(item) = self.add_to_inventory(item) else {
+            return Ok(());
+        };
+
+        let screen = Screen {
+            title: &format!("You can't carry the {}", item.get_name()),
+            content: &format!(
+                "It weighs {} kg, and you're already carrying {}/{} kg. You leave it in the {}. You'll have to drop something if you want to take it.",
+                item.get_weight(),
+                carried_weight,
+                self.max_weight,
+                self.room.get_name()
+            ),
+            title_color: None,
+            art: None,
+        };
+        menu.show_screen(screen);
+
+        Err(item)
+    }
+
+    /// Picks up every [`Item`] in the current room which the [`Player`] can carry, in the [configured order][config::TAKE_ALL_ORDER],
+    /// then shows a [`Screen`] listing what they took and what they had to leave behind
+    pub fn take_all(&mut self, menu: &mut impl Menu) {
+        let mut items = std::mem::take(&mut self.get_room_state_mut().items);
+        items.sort_by(|a, b| config::TAKE_ALL_ORDER.compare(a, b));
+
+        let mut taken = Vec::new();
+        for item in items {
+            match self.add_to_inventory(item.clone()) {
+                Ok(()) => taken.push(item),
+                Err(item) => self.get_room_state_mut().items.push(item),
+            }
+        }
+
+        let left = &self.get_room_state().items;
+        let title = match (taken.is_empty(), left.is_empty()) {
+            (_, true) => "You take everything",
+            (true, false) => "You can't carry any of it",
+            (false, false) => "You take what you can carry",
+        };
+
+        // Writing to a String can't fail
+        let mut content = String::new();
+        if !taken.is_empty() {
+            let _ = write!(content, "You take:\n{}", list_items(&taken));
+        }
+        if !left.is_empty() {
+            let _ = write!(content, "You don't have room for these, so you leave them in the {}:\n{}", self.room.get_name(), list_items(left));
+        }
+        let _ = write!(content, "You are carrying {}/{} kg.", self.get_carried_weight(), self.max_weight);
+
+        menu.show_screen(Screen {
+            title,
+            content: &content,
+            title_color: None,
+            art: None,
+        });
+
+        if !taken.is_empty() {
+            self.tutorial.show(menu, TutorialPrompt::PickUp);
+        }
     }
 
-    /// Add an item to the [player's inventory][Player::inventory]
-    pub fn pick_up_item(&mut self, item: Item) {
-        // TODO: max inventory size
-        self.inventory.push(item);
+    /// Adds an [`Item`] to the [player's inventory][Player::inventory], stacking it with the same item if they have one, as long as it isn't too heavy to carry.
+    /// Gold is added to the [`Player`]'s [gold][Player::gold] instead.
+    ///
+    /// ### Returns:
+    /// The item back again if it was too heavy
+    fn add_to_inventory(&mut self, item: Item) -> Result<(), Item> {
+        if let Item::Currency(gold) = item {
+            self.gold += gold;
+            return Ok(());
+        }
+
+        if self.get_carried_weight() + item.get_weight() <= self.max_weight {
+            self.log(format!("Picked up the {}", item.get_name()));
+            self.stats.items_collected += 1;
+
+            // The escape pod door recognises the key card wherever it was found
+            if matches!(item, Item::EscapePodKeys) && !self.is_escape_pod_unlocked() {
+                self.room_graph.set_door(Room::CrewArea, Room::EscapePod, DoorState::Closed);
+            }
+
+            // A glance at the deck plans is enough to know where everything is
+            if matches!(item, Item::ShipMap) {
+                self.room_graph.reveal_all();
+            }
+
+            match self.inventory.iter_mut().find(|(stacked, _)| stacked.can_stack_with(&item)) {
+                Some((_, count)) => *count += 1,
+                None => self.inventory.push((item, 1)),
+            }
+            return Ok(());
+        }
+
+        Err(item)
     }
 
     /// Get the user to choose a [combat action][combat::Action] to perform
     pub fn choose_combat_action(&self, menu: &mut impl Menu) -> combat::Action {
         // Init lists of options and their string representations
-        let mut options = vec![
-            combat::Action::Nothing,
-            combat::Action::DodgeLeft,
-            combat::Action::DodgeRight,
-        ];
-        let mut options_str = vec![
-            "Do nothing".to_string(),
-            "Dodge to the left".to_string(),
-            "Dodge to the right".to_string(),
+        let mut options = vec![combat::Action::Nothing];
+        let mut options_str = vec!["Do nothing".to_string()];
+
+        // Defensive actions are only shown if the player has enough stamina for them, and dodges only once they've caught their breath from the last one
+        let defensive_actions = [
+            (combat::Action::DodgeLeft, "Dodge to the left"),
+            (combat::Action::DodgeRight, "Dodge to the right"),
+            (combat::Action::Block, "Block"),
         ];
+        for (action, text) in defensive_actions {
+            if self.can_take_combat_action(action) {
+                options.push(action);
+                options_str.push(format!("{text} ({} stamina)", action.get_stamina_cost()));
+            }
+        }
 
         // Add actions for items
-        for (i, item) in self.inventory.iter().enumerate() {
+        for (i, (item, count)) in self.inventory.iter().enumerate() {
             match item {
                 Item::Food(f) => {
                     options.push(combat::Action::EatFood(i));
-                    options_str.push(format!("Eat your {}", f.name));
+                    options_str.push(format!("Eat your {}{}{} ({count} left)", f.name, item.get_rarity_label(), item.get_spoiled_label()));
+                }
+                Item::Explosive => {
+                    options.push(combat::Action::Explode(i));
+                    options_str.push(format!(
+                        "Throw your {} ({} damage to the enemy, but {} to you if they're up close, {count} left)",
+                        item.get_name(),
+                        config::EXPLOSIVE_DAMAGE,
+                        config::EXPLOSIVE_SELF_DAMAGE
+                    ));
                 }
-                Item::Weapon(w) => {
+                Item::Bandage => {
+                    options.push(combat::Action::UseBandage(i));
+                    options_str.push(format!(
+                        "Put on a Bandage ({} HP a turn for {} turns, {count} left)",
+                        config::BANDAGE_HEAL_PER_TURN,
+                        config::BANDAGE_DURATION_TURNS
+                    ));
+                }
+                // Two-handed weapons can't be used with something in the off hand
+                Item::Weapon(w) if self.can_wield(w) => {
                     options.push(combat::Action::AttackStraight(i));
-                    options_str.push(format!("Attack with your {}", w.name));
+                    options_str.push(format!(
+                        "Attack with your {}{} ({} damage, reach {}, {}/{} durability)",
+                        w.name,
+                        self.get_equipped_label(i),
+                        self.get_attack_damage(w.straight_damage),
+                        w.reach,
+                        w.durability,
+                        w.max_durability
+                    ));
+                    options.push(combat::Action::Throw(i));
+                    options_str.push(format!("Throw your {} ({} damage, but you can't get it back until the fight is over)", w.name, self.get_attack_damage(w.throw_damage)));
                 }
                 _ => (),
             }
         }
 
-        // Get the user to pick an option
-        let prompt = format!("{} - What do you do?", self.get_remaining_time());
-        let list = OptionList::new(&options_str, &prompt);
-        let choice = menu.show_option_list(list);
+        // The player can only flee through an open door
+        let flee_cost = combat::Action::Flee.get_stamina_cost();
+        if flee_cost <= self.stamina && !self.get_flee_destinations().is_empty() {
+            options.push(combat::Action::Flee);
+            options_str.push(format!("Run for it ({flee_cost} stamina, but they may follow you)"));
+        }
+
+        options.push(combat::Action::Examine);
+        options_str.push(if config::EXAMINE_TAKES_TURN { "Size up your opponent (takes your turn)" } else { "Size up your opponent" }.to_string());
 
-        // If the action was an attack, get the user to pick which direction to aim it
-        if let combat::Action::AttackStraight(i) = options[choice] {
-            let options = &[
-                "Attack Left".to_string(),
-                "Attack Straight".to_string(),
-                "Attack Right".to_string(),
-            ];
-            let list = OptionList::new(options, "Which way do you attack?");
+        // Help is shown from here rather than being a combat action, so it doesn't use up a turn
+        options_str.push("Remember how fighting works".to_string());
 
-            let direction = menu.show_option_list(list);
+        let prompt = format!("{} - {}, {}/{} stamina - What do you do?", self.get_remaining_time(), self.health.display_against(self.max_health), self.stamina, self.max_stamina);
 
-            match direction {
-                0 => combat::Action::AttackLeft(i),
-                1 => combat::Action::AttackStraight(i),
-                2 => combat::Action::AttackRight(i),
-                _ => unreachable!(),
+        // Loop until the user commits to an action, as they can go back from the direction submenu
+        loop {
+            // Get the user to pick an option
+            let list = OptionList::new(&options_str, &prompt);
+            let choice = menu.show_option_list(list);
+
+            if choice == options.len() {
+                self.show_combat_help(menu);
+                continue;
             }
-        } else {
-            options.swap_remove(choice)
+
+            // If the action was an attack, get the user to pick which direction to aim it
+            let combat::Action::AttackStraight(i) = options[choice] else {
+                return options.swap_remove(choice);
+            };
+
+            let Item::Weapon(w) = &self.inventory[i].0 else {unreachable!()};
+            let straight_damage = self.get_attack_damage(w.straight_damage);
+            let dodge_damage = self.get_attack_damage(w.dodge_damage);
+
+            let directions = &[
+                format!("Attack Left ({dodge_damage} damage if they dodge that way)"),
+                format!("Attack Straight ({straight_damage} damage if they don't dodge)"),
+                format!("Attack Right ({dodge_damage} damage if they dodge that way)"),
+            ];
+            let list = OptionList::new(directions, "Which way do you attack?").with_cancel_text("Back");
+
+            match menu.show_option_list_cancellable(list) {
+                // Going back shows the main list again
+                None => (),
+                Some(0) => return combat::Action::AttackLeft(i),
+                Some(1) => return combat::Action::AttackStraight(i),
+                Some(2) => return combat::Action::AttackRight(i),
+                Some(_) => unreachable!(),
+            }
+        }
+    }
+
+    /// Gets the XP the [`Player`] needs to reach their next level, or [`None`] if they are at the max level
+    fn get_next_level_xp(&self) -> Option<usize> {
+        config::LEVEL_XP_THRESHOLDS.get(self.level - 1).copied()
+    }
+
+    /// Gets a description of the [`Player`]'s XP and how much they need for their next level
+    fn get_xp_description(&self) -> String {
+        match self.get_next_level_xp() {
+            Some(next) => format!("{}/{next} XP to the next level", self.xp),
+            None => format!("{} XP, the max level", self.xp),
+        }
+    }
+
+    /// Increases the [`Player`]'s level by 1, along with their max health and strength
+    fn level_up(&mut self) {
+        self.level += 1;
+        self.max_health += config::LEVEL_UP_MAX_HEALTH;
+        self.health += config::LEVEL_UP_MAX_HEALTH;
+        self.strength += config::LEVEL_UP_STRENGTH;
+    }
+
+    /// Gives the [`Player`] XP, levelling them up as many times as their new total allows.
+    /// Each level increases their max health and strength, and shows a [`Screen`] to celebrate.
+    pub fn gain_xp(&mut self, menu: &mut impl Menu, xp: usize) {
+        self.xp += xp;
+
+        while self.get_next_level_xp().is_some_and(|next| self.xp >= next) {
+            self.level_up();
+            self.log(format!("Reached level {}", self.level));
+
+            let screen = Screen {
+                title: &format!("You reached level {}!", self.level),
+                content: &format!(
+                    "You feel tougher after that fight. Your max health is now {} HP, and your strength is now {}.",
+                    self.max_health,
+                    self.strength
+                ),
+                title_color: Some(Color::Green),
+                art: None,
+            };
+            menu.show_screen(screen);
         }
     }
 
+    /// Recovers [`STAMINA_REGEN_PER_TURN`][config::STAMINA_REGEN_PER_TURN] stamina, up to the [`Player`]'s max stamina
+    pub fn regen_stamina(&mut self) {
+        self.stamina = self.max_stamina.min(self.stamina + config::STAMINA_REGEN_PER_TURN);
+    }
+
+    /// Counts down the [`Player`]'s [dodge cooldown][Player::dodge_cooldown] at the end of a turn of battle,
+    /// or starts it again if they dodged this turn
+    pub fn tick_dodge_cooldown(&mut self, action: combat::Action) {
+        self.dodge_cooldown = if action.is_dodge() { config::DODGE_COOLDOWN_TURNS } else { self.dodge_cooldown.saturating_sub(1) };
+    }
+
+    /// Checks whether the [`Player`] can take a [combat action][combat::Action]: they need enough stamina for it,
+    /// and can't dodge until their [dodge cooldown][Player::dodge_cooldown] is over
+    pub const fn can_take_combat_action(&self, action: combat::Action) -> bool {
+        action.get_stamina_cost() <= self.stamina && !(action.is_dodge() && self.dodge_cooldown > 0)
+    }
+
+    /// Gets the damage the [`Player`] deals with an attack which has the given base damage, taking their [strength][Player::strength] into account
+    pub const fn get_attack_damage(&self, base_damage: Damage) -> Damage {
+        combat::attack_damage(base_damage, self.strength)
+    }
+
     /// Get a [`String`] describing the [`Player`] performing a [combat action][combat::Action]
     pub fn describe_combat_action(&self, action: combat::Action) -> String {
         use combat::Action::*;
 
         match action {
-            AttackLeft(w) => format!(
-                "You attack to the left with your {}",
-                self.inventory[w].get_name()
-            ),
-            AttackRight(w) => format!(
-                "You attack to the right with your {}",
-                self.inventory[w].get_name()
-            ),
-            AttackStraight(w) => format!(
-                "You attack in front of you with your {}",
-                self.inventory[w].get_name()
-            ),
-            EatFood(f) => format!("You attempt to eat your {}", self.inventory[f].get_name()),
+            AttackLeft(w) => self.describe_attack(w, AttackDirection::Left),
+            AttackRight(w) => self.describe_attack(w, AttackDirection::Right),
+            AttackStraight(w) => self.describe_attack(w, AttackDirection::Straight),
+            EatFood(f) => lang::fill("combat.player.eat", &[&self.inventory[f].0.get_name()]),
+            Throw(w) => lang::fill("combat.player.throw", &[&self.inventory[w].0.get_name()]),
 
-            DodgeLeft => "You dodge to the left".to_string(),
-            DodgeRight => "You dodge to the right".to_string(),
-            Nothing => "You do nothing".to_string(),
+            DodgeLeft => lang::get("combat.player.dodge_left").to_string(),
+            DodgeRight => lang::get("combat.player.dodge_right").to_string(),
+            Block => lang::get("combat.player.block").to_string(),
+            Nothing => lang::get("combat.player.nothing").to_string(),
+            Flee => lang::get("combat.player.flee").to_string(),
+            UseBandage(_) => lang::get("combat.player.bandage").to_string(),
+            Explode(_) => lang::get("combat.player.explode").to_string(),
+            Examine => lang::get("combat.player.examine").to_string(),
         }
     }
 
-    /// Shows the player a win screen
-    pub fn show_win_screen(&self, menu: &mut impl Menu) {
-        if self.inventory.iter().any(|item|matches!(item, Item::Food(_))) {
-            menu.show_screen(Screen {
-                title: "Freedom at long last",
-                content: "Or maybe not so long - it's only been a few minutes, after all. You buckle in for the long ride and allow yourself to relax, finally. You won't get back to New Arnith for a cycle and a half, but at least you brought some food."
-            });
+    /// Gets a string describing the [`Player`] attacking in the given direction with the weapon at the given index into their [inventory][Player::inventory],
+    /// in the weapon's own [style][Weapon::attack_style]
+    fn describe_attack(&self, w: usize, direction: AttackDirection) -> String {
+        let Item::Weapon(weapon) = &self.inventory[w].0 else {unreachable!()};
+        lang::fill(weapon.get_attack_key(direction, true), &[&weapon.name])
+    }
+
+    /// Shows the player the win screen for the given [`Ending`], with how long the run took
+    pub fn show_win_screen(&self, menu: &mut impl Menu, ending: Ending, finish: &Finish) {
+        let food = if self.inventory.iter().any(|(item, _)| matches!(item, Item::Food(_))) {
+            " You won't get back to New Arnith for a cycle and a half, but at least you brought some food."
         } else {
-            menu.show_screen(Screen {
-                title: "Freedom at long last",
-                content: "Or maybe not so long - it's only been a few minutes, after all. You buckle in for the long ride and allow yourself to relax, finally."
-            });
-        }
+            ""
+        };
+
+        let left_behind = if self.left_behind.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n\nAs the ship shrinks behind you, you think of who you left on board: {}. You try not to imagine what the crew will do when they find them.",
+                self.left_behind.join(", ")
+            )
+        };
+
+        let fallen = if self.fallen_companions.is_empty() {
+            String::new()
+        } else {
+            format!("\n\nThe seat beside you is emptier than it should be. The {} didn't make it this far.", self.fallen_companions.join(" and the "))
+        };
+
+        menu.show_screen(Screen {
+            title: ending.get_title(),
+            content: &format!(
+                "{}{food}{left_behind}{fallen}\n\n{}\n{}\n\nRun seed: {}",
+                ending.get_description(),
+                self.stats.describe(),
+                finish.describe(),
+                self.seed
+            ),
+            title_color: None,
+            art: None,
+        });
     }
 }
 
 impl Player {
-    /// Initialise a new [`Player`]
-    pub fn init() -> Self {
-        Self {
+    /// Initialise a new [`Player`].
+    /// The same seed will always produce the same random events, as long as the player makes the same choices.
+    #[cfg(test)]
+    pub fn init(seed: u64) -> Self {
+        Self::init_balanced(seed, DynamicBalance::default())
+    }
+
+    /// Initialise a new [`Player`], with the ship's enemies adjusted by the given [`DynamicBalance`] carried over from earlier loops
+    pub fn init_balanced(seed: u64, balance: DynamicBalance) -> Self {
+        let mut player = Self::init_unscaled(seed);
+        player.balance = balance;
+        player.scale_room_enemies();
+        player
+    }
+
+    /// Initialise a new [`Player`], leaving the enemies in the ship as they are defined in [`map`]
+    fn init_unscaled(seed: u64) -> Self {
+        let mut player = Self {
             room: STARTING_ROOM,
+            previous_room: None,
+            hidden: false,
             inventory: Vec::new(),
-            health: config::PLAYER_START_HEALTH,
+            equipped_weapon: None,
+            equipped_armor: None,
+            equipped_off_hand: None,
+            health: Health::clamped(config::PLAYER_START_HEALTH.as_usize(), config::PLAYER_START_MAX_HEALTH),
             max_health: config::PLAYER_START_MAX_HEALTH,
+            stamina: config::PLAYER_MAX_STAMINA,
+            max_stamina: config::PLAYER_MAX_STAMINA,
+            dodge_cooldown: 0,
+            strength: config::PLAYER_START_STRENGTH,
+            xp: 0,
+            level: 1,
+            max_weight: config::PLAYER_MAX_WEIGHT,
+            gold: config::PLAYER_START_GOLD,
             remaining_turns: config::MAX_TURNS,
+            self_destruct_turns: if config::SELF_DESTRUCT_MODE { settings::get().difficulty.get_self_destruct_turns() } else { None },
+            hunger: config::PLAYER_START_HUNGER,
+            oxygen: config::PLAYER_MAX_OXYGEN,
+            bandage_turns: 0,
+            fresh_turns: 0,
+            inventory_sort: None,
 
             room_graph: map::init(),
+            companions: Vec::new(),
+            left_behind: Vec::new(),
+            fallen_companions: Vec::new(),
+            defeated_enemies: Vec::new(),
+            objectives: Objectives::default(),
+            journal: Journal::default(),
+            stats: RunStats::default(),
+            timer: Timer::default(),
+            achievements: Achievements::default(),
+            tutorial: Tutorial::default(),
+            balance: DynamicBalance::default(),
+            quit: None,
+
+            new_game_plus_cycle: 0,
+
+            seed,
+            rng: Rng::new(seed),
+        };
+
+        player.get_room_state_mut().visited = true;
+        player.stats.rooms_visited = 1;
+        player
+    }
+
+    /// Takes everything out of the [`Player`]'s inventory as [`Remains`], to be left where they died.
+    /// Returns [`None`] if they weren't carrying anything.
+    pub fn drop_remains(&mut self) -> Option<Remains> {
+        self.equipped_weapon = None;
+        self.equipped_armor = None;
+        self.equipped_off_hand = None;
+
+        let items: Vec<_> = self
+            .inventory
+            .drain(..)
+            .flat_map(|(item, count)| std::iter::repeat_n(item, count))
+            .collect();
+
+        (!items.is_empty()).then_some(Remains { room: self.room, items })
+    }
+
+    /// Leaves the [`Remains`] from the last time round the loop in the room they were dropped in, and tells the [`Player`] where to find them
+    pub fn place_remains(&mut self, menu: &mut impl Menu, remains: Remains) {
+        menu.show_screen(Screen {
+            title: "You remember where you fell",
+            content: &format!(
+                "Last time, you didn't make it out of the {}. Whatever you were carrying should still be there:\n{}",
+                remains.room.get_name(),
+                list_items(&remains.items)
+            ),
+            title_color: None,
+            art: None,
+        });
+
+        self.room_graph.get_state_mut(remains.room).items.extend(remains.items);
+    }
+
+    /// Initialise a new [`Player`] for a [New Game Plus][NewGamePlus] run.
+    /// They start at the level their XP gets them to, with the item they chose, and every enemy on the ship is [tougher][crate::combat::Enemy::scale_for_cycle].
+    /// Like [`init_balanced`][Player::init_balanced], the enemies are adjusted by the given [`DynamicBalance`].
+    pub fn init_new_game_plus(seed: u64, new_game_plus: &NewGamePlus, balance: DynamicBalance) -> Self {
+        let mut player = Self::init_unscaled(seed);
+        player.balance = balance;
+        player.new_game_plus_cycle = new_game_plus.cycle;
+
+        player.xp = new_game_plus.xp;
+        while player.get_next_level_xp().is_some_and(|next| player.xp >= next) {
+            player.level_up();
+        }
+
+        if let Some(item) = &new_game_plus.item {
+            player.inventory.push((item.clone(), 1));
+        }
+
+        player.scale_room_enemies();
+        player
+    }
+
+    /// [Scales][scale_enemy] every enemy placed in the ship's rooms to suit the [`Player`]. This should only be done once, when the [`Player`] is created.
+    fn scale_room_enemies(&mut self) {
+        for room_state in self.room_graph.rooms.values_mut() {
+            room_state.enemy = room_state.enemy.take().map(|enemy| scale_enemy(enemy, self.level, self.balance, self.new_game_plus_cycle));
         }
     }
 }
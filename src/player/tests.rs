@@ -1,13 +1,13 @@
 #![cfg(test)]
 
-use crate::{items::Food, combat::Damage, menu::tests::MockMenu};
+use crate::{items::{DamageType, Food, Rarity, Weapon}, menu::tests::MockMenu};
 
 use super::*;
 
 /// Tests that the [`Player::get_remaining_time`] function returns correct results
 #[test]
 fn test_time_format() {
-    let mut player = Player::init();
+    let mut player = Player::init(0);
 
     player.remaining_turns = 0;
     assert_eq!(player.get_remaining_time(), "0:00");
@@ -29,15 +29,20 @@ fn test_time_format() {
 fn test_use_item() {
     // Eating food should heal by the right number of health
     {
-        let mut player = Player::init();
+        let mut player = Player::init(0);
         player.health = Health::new(5);
         player.max_health = Health::new(10);
 
-        player.inventory.push(Item::Food(Food {
+        player.inventory.push((Item::Food(Food {
         name: "",
             description: "",
             heals_for: Damage::new(3),
-        }));
+            fills_for: 0,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+            turns_until_spoiled: 1,
+        }), 1));
 
         player.use_item(&mut MockMenu::default(), 0);
         assert_eq!(player.health, Health::new(8));
@@ -45,17 +50,1444 @@ fn test_use_item() {
 
     // Eating food should not heal past the player's maximum health
     {
-        let mut player = Player::init();
+        let mut player = Player::init(0);
         player.health = Health::new(5);
         player.max_health = Health::new(10);
 
-        player.inventory.push(Item::Food(Food {
+        player.inventory.push((Item::Food(Food {
         name: "",
             description: "",
             heals_for: Damage::new(10),
-        }));
+            fills_for: 0,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+            turns_until_spoiled: 1,
+        }), 1));
 
         player.use_item(&mut MockMenu::new().unwrap(), 0);
         assert_eq!(player.health, Health::new(10));
     }
-}
\ No newline at end of file
+}
+
+/// Tests that the healing shown after eating is worked out from the change in health, so it's only what the player actually gained, even at or above their max health
+#[test]
+fn test_use_item_heal_delta() {
+    for (health, healed) in [(7, 3), (10, 0), (12, 0)] {
+        let mut player = Player::init(0);
+        player.health = Health::new(health);
+        player.max_health = Health::new(10);
+
+        player.inventory.push((Item::Food(Food {
+            name: "",
+            description: "",
+            heals_for: Damage::new(5),
+            fills_for: 0,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+            turns_until_spoiled: 1,
+        }), 1));
+
+        let mut menu = MockMenu::default();
+        player.use_item(&mut menu, 0);
+        assert_eq!(player.health, Health::new(health.max(10)));
+        assert!(menu.get_last_screen().unwrap().1.starts_with(&format!("You are healed by {healed} HP.")));
+    }
+}
+
+/// Tests that items which would take the player over their max weight are left in the room
+#[test]
+fn test_max_weight() {
+    let mut player = Player::init(0);
+    player.max_weight = 5;
+
+    let food = |weight| Item::Food(Food {
+        name: "",
+        description: "",
+        heals_for: Damage::new(0),
+        fills_for: 0,
+        weight,
+        value: 0,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    });
+
+    // Items which fit should be picked up
+    player.pick_up_item(&mut MockMenu::default(), food(3));
+    player.pick_up_item(&mut MockMenu::default(), food(2));
+    assert_eq!(player.inventory.len(), 2);
+    assert_eq!(player.get_carried_weight(), 5);
+
+    // Items which don't fit should be left in the room
+    let room_items = player.get_room_state().items.len();
+    player.pick_up_item(&mut MockMenu::default(), food(1));
+    assert_eq!(player.inventory.len(), 2);
+    assert_eq!(player.get_room_state().items.len(), room_items + 1);
+}
+
+/// Tests that the player regenerates health each turn, but not past their maximum health
+#[test]
+fn test_regen() {
+    let mut player = Player::init(0);
+    player.max_health = Health::new(10);
+    player.health = Health::new(5);
+
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.health, Health::new(5) + config::REGEN_PER_TURN);
+
+    player.health = player.max_health;
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.health, player.max_health);
+}
+
+/// Tests that starving players lose health each turn, and that eating stops them starving
+#[test]
+fn test_hunger() {
+    let mut player = Player::init(0);
+    player.health = Health::new(5);
+    player.hunger = config::HUNGER_STARVING - 2;
+
+    // Not starving yet, so the player should regenerate
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.hunger, config::HUNGER_STARVING - 1);
+    assert_eq!(player.health, Health::new(5) + config::REGEN_PER_TURN);
+
+    // Starving, so the player should lose health
+    player.health = Health::new(5);
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.health, Health::new(5) - config::STARVATION_DAMAGE);
+
+    // Eating should reduce hunger
+    player.eat(&Food {
+        name: "",
+        description: "",
+        heals_for: Damage::new(0),
+        fills_for: 5,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    });
+    assert_eq!(player.hunger, config::HUNGER_STARVING - 5);
+    assert!(!player.is_starving());
+}
+
+/// Tests that picking up gold adds to the player's gold rather than their inventory
+#[test]
+fn test_pick_up_gold() {
+    let mut player = Player::init(0);
+    player.gold = 3;
+
+    player.pick_up_item(&mut MockMenu::default(), Item::Currency(5));
+    assert_eq!(player.gold, 8);
+    assert!(player.inventory.is_empty());
+}
+
+/// Tests that taking everything picks up as much as the player can carry, lightest first, and leaves the rest in the room
+#[test]
+fn test_take_all() {
+    let mut player = Player::init(0);
+    player.gold = 0;
+    player.max_weight = Item::Bandage.get_weight();
+    player.get_room_state_mut().items = vec![wrench(), Item::Bandage, Item::Currency(5), Item::Dust];
+
+    let mut menu = MockMenu::default();
+    player.take_all(&mut menu);
+    assert_eq!(player.gold, 5);
+    assert_eq!(player.inventory, vec![(Item::Dust, 1), (Item::Bandage, 1)]);
+    assert_eq!(player.get_room_state().items, vec![wrench()]);
+    assert_eq!(menu.get_last_screen().unwrap().0, "You take what you can carry");
+
+    player.max_weight = config::PLAYER_MAX_WEIGHT;
+    player.take_all(&mut menu);
+    assert!(player.get_room_state().items.is_empty());
+    assert_eq!(menu.get_last_screen().unwrap().0, "You take everything");
+}
+
+/// Tests that searching a room reveals its hidden items, and that searching again finds nothing more
+#[test]
+fn test_search_room() {
+    let mut player = Player::init(0);
+    player.get_room_state_mut().hidden_items = vec![Item::Dust, Item::Shame];
+    let room_items = player.get_room_state().items.len();
+
+    player.search_room(&mut MockMenu::default());
+    assert!(player.get_room_state().hidden_items.is_empty());
+    assert_eq!(player.get_room_state().items.len(), room_items + 2);
+
+    player.search_room(&mut MockMenu::default());
+    assert_eq!(player.get_room_state().items.len(), room_items + 2);
+}
+
+/// Tests that going back from the attack direction submenu returns to the main action list
+#[test]
+fn test_combat_action_back() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Weapon(Weapon {
+        name: "",
+        description: "",
+        damage_type: DamageType::Blunt,
+        straight_damage: Damage::new(1),
+        dodge_damage: Damage::new(1),
+        speed: 1,
+        throw_damage: Damage::new(1),
+        two_handed: false,
+        accuracy: 100,
+        reach: 1,
+        durability: 20,
+        max_durability: 20,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        attack_style: None,
+    }), 1)];
+
+    // Pick the attack, go back, then dodge to the left instead
+    let mut menu = MockMenu::with_numbers([Some(4), None, Some(1)]);
+    assert!(matches!(player.choose_combat_action(&mut menu), combat::Action::DodgeLeft));
+
+    // Pick the attack, then aim it to the right
+    let mut menu = MockMenu::with_numbers([Some(4), Some(2)]);
+    assert!(matches!(player.choose_combat_action(&mut menu), combat::Action::AttackRight(0)));
+}
+
+/// Tests that the player levels up exactly when their XP reaches each threshold, and not past the max level
+#[test]
+fn test_level_up() {
+    let mut player = Player::init(0);
+    let first = config::LEVEL_XP_THRESHOLDS[0];
+    let start_max_health = player.max_health;
+    let start_strength = player.strength;
+
+    // One XP short of the first threshold shouldn't level up
+    player.gain_xp(&mut MockMenu::default(), first - 1);
+    assert_eq!(player.level, 1);
+
+    // Reaching the threshold should level up and increase stats
+    player.gain_xp(&mut MockMenu::default(), 1);
+    assert_eq!(player.level, 2);
+    assert_eq!(player.max_health, start_max_health + config::LEVEL_UP_MAX_HEALTH);
+    assert_eq!(player.strength, start_strength + config::LEVEL_UP_STRENGTH);
+
+    // A large amount of XP should level up several times, but stop at the max level
+    player.gain_xp(&mut MockMenu::default(), 1000);
+    assert_eq!(player.level, config::LEVEL_XP_THRESHOLDS.len() + 1);
+}
+
+/// Tests that carrying all of the key items unlocks the collector achievement
+#[test]
+fn test_collector_achievement() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Maps, 1), (Item::EscapePodKeys, 1)];
+
+    player.check_achievements(&mut MockMenu::default());
+    assert!(!player.achievements.is_unlocked(Achievement::Collector));
+
+    player.inventory.push((Item::CaptainsDiary(0), 1));
+    player.check_achievements(&mut MockMenu::default());
+    assert!(player.achievements.is_unlocked(Achievement::Collector));
+}
+
+/// Tests that defensive actions are hidden when the player doesn't have enough stamina for them
+#[test]
+fn test_stamina_hides_defence() {
+    let mut player = Player::init(0);
+    player.stamina = 0;
+
+    // With no stamina, the only options are to do nothing, examine the enemy or get help
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    assert!(matches!(player.choose_combat_action(&mut menu), combat::Action::Nothing));
+    assert_eq!(menu.get_last_list().unwrap().1.len(), 3);
+
+    let mut menu = MockMenu::with_numbers([Some(1)]);
+    assert!(matches!(player.choose_combat_action(&mut menu), combat::Action::Examine));
+}
+
+/// Tests that the player can't dodge twice in a row, and can dodge again once the cooldown is over
+#[test]
+fn test_dodge_cooldown() {
+    let mut player = Player::init(0);
+    player.stamina = player.max_stamina;
+    let can_dodge = |player: &Player| {
+        let mut menu = MockMenu::with_numbers([Some(0)]);
+        player.choose_combat_action(&mut menu);
+        menu.get_last_list().unwrap().1.iter().any(|option| option.starts_with("Dodge"))
+    };
+    assert!(can_dodge(&player));
+
+    player.tick_dodge_cooldown(combat::Action::DodgeLeft);
+    assert!(!player.can_take_combat_action(combat::Action::DodgeRight));
+    assert!(player.can_take_combat_action(combat::Action::Block));
+    assert!(!can_dodge(&player));
+
+    for _ in 0..config::DODGE_COOLDOWN_TURNS {
+        player.tick_dodge_cooldown(combat::Action::Nothing);
+    }
+    assert!(can_dodge(&player));
+}
+
+/// Tests that the help screens can be shown from both exploring and fighting, and that they reflect what the player is carrying
+#[test]
+fn test_help() {
+    let mut player = Player::init(0);
+    player.inventory.clear();
+
+    let mut menu = MockMenu::default();
+    player.show_help(&mut menu);
+    let (_, content) = menu.get_last_screen().unwrap();
+    assert!(content.contains("You aren't carrying anything yet"));
+    assert!(!content.contains("Food can be eaten"));
+
+    player.inventory.push((Item::Food(Food {
+        name: "",
+        description: "",
+        heals_for: Damage::new(3),
+        fills_for: 0,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    }), 1));
+    player.show_help(&mut menu);
+    assert!(menu.get_last_screen().unwrap().1.contains("Food can be eaten"));
+
+    // Getting help in a fight shows the list again afterwards, without using up the turn
+    player.inventory.clear();
+    player.stamina = 0;
+    let mut menu = MockMenu::with_numbers([Some(2), Some(0)]);
+    assert!(matches!(player.choose_combat_action(&mut menu), combat::Action::Nothing));
+    assert_eq!(menu.get_last_screen().unwrap().0, "You try to remember your combat training");
+}
+
+/// Tests that combining items with a recipe replaces them with the result, and that other items are left alone
+#[test]
+fn test_combine_items() {
+    let food = |name| Item::Food(Food {
+        name,
+        description: "",
+        heals_for: Damage::new(0),
+        fills_for: 0,
+        weight: 1,
+        value: 0,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    });
+
+    let mut player = Player::init(0);
+    player.inventory = vec![(food("Bar of Chocolate"), 1), (Item::Dust, 1), (food("Bread roll"), 1)];
+
+    // There's no recipe for these, so nothing should be used up
+    player.combine_items(&mut MockMenu::default(), 0, 1);
+    assert_eq!(player.inventory.len(), 3);
+
+    // The inputs should be replaced by the recipe's output, in either order
+    player.combine_items(&mut MockMenu::default(), 2, 0);
+    assert_eq!(player.inventory.len(), 2);
+    assert!(matches!(player.inventory[0].0, Item::Dust));
+    assert_eq!(player.inventory[1].0.get_name(), "Chocolate Sandwich");
+}
+
+/// Tests that food in the inventory and in rooms ages each turn, and that spoiled food heals less
+#[test]
+fn test_food_spoils() {
+    let food = || Item::Food(Food {
+        name: "",
+        description: "",
+        heals_for: Damage::new(4),
+        fills_for: 0,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    });
+
+    let mut player = Player::init(0);
+    player.inventory = vec![(food(), 1)];
+    player.get_room_state_mut().items.push(food());
+
+    player.pass_turn(&mut MockMenu::default());
+    assert!(player.inventory[0].0.get_spoiled_label().contains("spoiled"));
+    assert!(player.get_room_state().items.last().unwrap().get_spoiled_label().contains("spoiled"));
+
+    // Spoiled food only heals for part of its usual amount
+    let Item::Food(f) = player.take_item(0) else {unreachable!()};
+    player.health = Health::new(1);
+    player.eat(&f);
+    assert_eq!(player.health, Health::new(1) + Damage::new(4 * config::SPOILED_FOOD_HEAL_PERCENT / 100));
+}
+
+/// Tests that a rarer item has scaled stats and value, isn't scaled twice, and doesn't stack with the common version
+#[test]
+fn test_rarity() {
+    let common = Item::Food(Food {
+        name: "",
+        description: "",
+        heals_for: Damage::new(4),
+        fills_for: 0,
+        weight: 0,
+        value: 10,
+        rarity: Rarity::Common,
+        turns_until_spoiled: 1,
+    });
+
+    let rare = common.clone().with_rarity(Rarity::Rare);
+    let Item::Food(f) = &rare else {unreachable!()};
+    assert_eq!(f.heals_for, Damage::new(6));
+    assert_eq!(rare.get_value(), Some(20));
+    assert_eq!(rare.get_rarity_label(), " [Rare]");
+    assert!(!rare.can_stack_with(&common));
+
+    // Only common items are made rarer, so the stats aren't scaled again
+    assert_eq!(rare.clone().with_rarity(Rarity::Uncommon), rare);
+    assert_eq!(Item::Bandage.with_rarity(Rarity::Rare).get_rarity(), Rarity::Common);
+
+    let Item::Food(f) = common.with_rarity(Rarity::Uncommon) else {unreachable!()};
+    assert_eq!(f.heals_for, Damage::new(5));
+
+    let mut rng = Rng::new(0);
+    let rolls: Vec<_> = (0..500).map(|_| Rarity::roll(&mut rng)).collect();
+    assert!([Rarity::Common, Rarity::Uncommon, Rarity::Rare].iter().all(|r| rolls.contains(r)));
+}
+
+/// Tests that identical items stack, that weapons don't, and that using an item only takes one from its stack
+#[test]
+fn test_item_stacking() {
+    let weapon = || Item::Weapon(Weapon {
+        name: "",
+        description: "",
+        damage_type: DamageType::Blunt,
+        straight_damage: Damage::new(1),
+        dodge_damage: Damage::new(1),
+        speed: 1,
+        throw_damage: Damage::new(1),
+        two_handed: false,
+        accuracy: 100,
+        reach: 1,
+        durability: 20,
+        max_durability: 20,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        attack_style: None,
+    });
+
+    let mut player = Player::init(0);
+    player.pick_up_item(&mut MockMenu::default(), Item::Dust);
+    player.pick_up_item(&mut MockMenu::default(), Item::Dust);
+    player.pick_up_item(&mut MockMenu::default(), weapon());
+    player.pick_up_item(&mut MockMenu::default(), weapon());
+    assert_eq!(player.inventory.len(), 3);
+    assert_eq!(player.inventory[0].1, 2);
+
+    // Taking an item should leave the rest of the stack behind
+    assert!(matches!(player.take_item(0), Item::Dust));
+    assert_eq!(player.inventory[0].1, 1);
+    player.take_item(0);
+    assert_eq!(player.inventory.len(), 2);
+}
+
+/// Tests that items can be dropped from the inventory screen, and that it closes once the inventory is empty
+#[test]
+fn test_inventory_drop() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::CaptainsDiary(0), 1)];
+    let room_items = player.get_room_state().items.len();
+
+    // Pick the diary, then drop it, which comes after the options to examine and read it
+    let mut menu = MockMenu::with_numbers([Some(0), Some(2)]);
+    player.open_inventory(&mut menu);
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.get_room_state().items.len(), room_items + 1);
+}
+
+/// Tests that equipped armor reduces the damage of hits, and stays equipped when an earlier stack is removed
+#[test]
+fn test_equipped_armor() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.inventory = vec![
+        (Item::Dust, 1),
+        (Item::Armor(Armor {
+            name: "",
+            description: "",
+            defense: Damage::new(2),
+            slot: ArmorSlot::Body,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+        }), 1),
+    ];
+
+    // Without armor equipped, the full damage is taken
+    assert_eq!(player.take_hit(Damage::new(3)), Damage::new(3));
+
+    player.equip_item(&mut MockMenu::default(), 1);
+    assert_eq!(player.take_hit(Damage::new(3)), Damage::new(1));
+    assert_eq!(player.take_hit(Damage::new(1)), Damage::new(0));
+    assert_eq!(player.health, Health::new(6));
+
+    // Removing the dust moves the armor to the start of the inventory
+    player.take_item(0);
+    assert_eq!(player.equipped_armor, Some(0));
+
+    // Removing the armor itself unequips it
+    player.take_item(0);
+    assert_eq!(player.equipped_armor, None);
+}
+
+/// Tests that a two-handed weapon and an off-hand item can't be equipped at the same time, in either order
+#[test]
+fn test_two_handed_weapon() {
+    let mut player = Player::init(0);
+    player.inventory = vec![
+        (Item::Weapon(Weapon {
+            name: "",
+            description: "",
+            damage_type: DamageType::Blunt,
+            straight_damage: Damage::new(1),
+            dodge_damage: Damage::new(1),
+            speed: 1,
+            throw_damage: Damage::new(1),
+            two_handed: true,
+            accuracy: 100,
+            reach: 1,
+            durability: 20,
+            max_durability: 20,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+            attack_style: None,
+        }), 1),
+        (Item::Armor(Armor {
+            name: "",
+            description: "",
+            defense: Damage::new(1),
+            slot: ArmorSlot::OffHand,
+            weight: 0,
+            value: 0,
+            rarity: Rarity::Common,
+        }), 1),
+    ];
+
+    // With the weapon equipped, the off-hand item should be rejected
+    player.equip_item(&mut MockMenu::default(), 0);
+    player.equip_item(&mut MockMenu::default(), 1);
+    assert_eq!(player.equipped_weapon, Some(0));
+    assert_eq!(player.equipped_off_hand, None);
+
+    // With the off-hand item equipped, the weapon should be rejected, and can't be attacked with
+    player.unequip_item(&mut MockMenu::default(), 0);
+    player.equip_item(&mut MockMenu::default(), 1);
+    player.equip_item(&mut MockMenu::default(), 0);
+    assert_eq!(player.equipped_weapon, None);
+    assert_eq!(player.equipped_off_hand, Some(1));
+
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    player.choose_combat_action(&mut menu);
+    assert!(!menu.get_last_list().unwrap().1.iter().any(|o| o.starts_with("Attack")));
+}
+
+/// Tests that locked doors can't be opened until the escape pod keys are taken, and that open doors can be closed again
+#[test]
+fn test_doors() {
+    let mut player = Player::init(0);
+    player.room = Room::CrewArea;
+
+    player.open_door(&mut MockMenu::default(), Room::EscapePod);
+    assert!(!player.is_door_open(Room::EscapePod));
+
+    player.room_graph.set_door(Room::CrewArea, Room::EscapePod, DoorState::Closed);
+    player.open_door(&mut MockMenu::default(), Room::EscapePod);
+    assert!(player.is_door_open(Room::EscapePod));
+
+    player.close_door(&mut MockMenu::default(), Room::EscapePod);
+    assert!(!player.is_door_open(Room::EscapePod));
+}
+
+/// Tests that picking every pin of a lock unlocks the door, and that giving up leaves it locked
+#[test]
+fn test_pick_lock() {
+    const NUMBERS: [&str; config::LOCK_PIN_POSITIONS] = ["1", "2", "3", "4", "5"];
+
+    let mut player = Player::init(0);
+    player.room = Room::CrewArea;
+    player.inventory.push((Item::Lockpick, 1));
+
+    player.pick_lock(&mut MockMenu::default().with_typed_numbers(["0"]), Room::EscapePod);
+    assert!(player.get_room_state().connections.iter().any(|c| c.to == Room::EscapePod && c.door == DoorState::Locked));
+
+    // Guessing every pin right first time doesn't roll for the lockpick breaking, so the pin positions can be worked out in advance
+    let pins = player.get_room_state().connections.iter().find(|c| c.to == Room::EscapePod).unwrap().lock_pins;
+    let mut rng = player.rng.clone();
+    let guesses: Vec<_> = (0..pins).map(|_| NUMBERS[rng.below(config::LOCK_PIN_POSITIONS)]).collect();
+
+    player.pick_lock(&mut MockMenu::default().with_typed_numbers(guesses), Room::EscapePod);
+    assert!(!player.is_door_open(Room::EscapePod));
+    player.open_door(&mut MockMenu::default(), Room::EscapePod);
+    assert!(player.is_door_open(Room::EscapePod));
+    assert!(!player.room_graph.alarm.is_active());
+}
+
+/// Tests that the lock's tamper alarm goes off on exactly the last of the mistakes the player is told they have left
+#[test]
+fn test_pick_lock_mistakes() {
+    const NUMBERS: [&str; config::LOCK_PIN_POSITIONS] = ["1", "2", "3", "4", "5"];
+
+    let init = |seed| {
+        let mut player = Player::init(0);
+        player.room = Room::CrewArea;
+        player.inventory.push((Item::Lockpick, 1));
+        player.rng = Rng::new(seed);
+        player
+    };
+    let pins = init(0).get_room_state().connections.iter().find(|c| c.to == Room::EscapePod).unwrap().lock_pins;
+    let mistakes = pins * config::LOCKPICK_MISTAKES_PER_PIN;
+
+    // Use a seed where the lockpick survives every wrong guess, so only the tamper alarm can cut the attempt short
+    let seed = (0..1000)
+        .find(|&seed| {
+            let mut rng = Rng::new(seed);
+            rng.below(config::LOCK_PIN_POSITIONS);
+            (0..mistakes).all(|_| rng.below(100) >= config::LOCKPICK_BREAK_PERCENT)
+        })
+        .unwrap();
+    let wrong = NUMBERS[(Rng::new(seed).below(config::LOCK_PIN_POSITIONS) + 1) % config::LOCK_PIN_POSITIONS];
+
+    // One mistake short of the limit, the player can still give up quietly
+    let mut player = init(seed);
+    player.pick_lock(&mut MockMenu::default().with_typed_numbers(vec![wrong; mistakes - 1].into_iter().chain(["0"])), Room::EscapePod);
+    assert!(!player.room_graph.alarm.is_active());
+
+    // The last mistake sets the alarm off straight away, without asking for another guess
+    let mut player = init(seed);
+    player.pick_lock(&mut MockMenu::default().with_typed_numbers(vec![wrong; mistakes]), Room::EscapePod);
+    assert!(player.room_graph.alarm.is_active());
+    assert!(player.inventory.iter().any(|(item, _)| matches!(item, Item::Lockpick)));
+    assert!(player.get_room_state().connections.iter().any(|c| c.to == Room::EscapePod && c.door == DoorState::Locked));
+}
+
+/// Tests that one-shot traps only go off once, and that searching next door disarms them
+#[test]
+fn test_traps() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.room = Room::StoreRoom;
+    let damage = player.get_room_state().trap.as_ref().unwrap().damage;
+
+    player.spring_trap(&mut MockMenu::default());
+    player.spring_trap(&mut MockMenu::default());
+    assert_eq!(player.health, Health::new(10) - damage);
+
+    // The screen shows how much health was actually lost, not how hard the trap hits
+    let mut player = Player::init(0);
+    player.health = Health::new(1);
+    player.room = Room::StoreRoom;
+    let mut menu = MockMenu::default();
+    player.spring_trap(&mut menu);
+    assert!(menu.get_last_screen().unwrap().1.contains("You lose 1 HP."));
+
+    let mut player = Player::init(0);
+    player.room = Room::CrewArea;
+    player.search_room(&mut MockMenu::default());
+    assert!(player.room_graph.get_state(Room::StoreRoom).trap.is_none());
+}
+
+/// Tests that the self-destruct counts down each turn, and that room screens get more urgent as it nears
+#[test]
+fn test_self_destruct() {
+    let mut player = Player::init(0);
+    assert_eq!(player.get_self_destruct_line(), "");
+
+    player.self_destruct_turns = Some(config::SELF_DESTRUCT_WARNING_TURNS + 1);
+    assert!(matches!(player.get_room_screen_color(), Color::Cyan));
+
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.self_destruct_turns, Some(config::SELF_DESTRUCT_WARNING_TURNS));
+    assert!(matches!(player.get_room_screen_color(), Color::Yellow));
+
+    player.self_destruct_turns = Some(1);
+    player.pass_turn(&mut MockMenu::default());
+    assert!(player.has_self_destructed());
+    assert!(matches!(player.get_room_screen_color(), Color::Red));
+}
+
+/// Tests that oxygen runs out over time, that running out hurts the player, and that it can be refilled in the engine room
+#[test]
+fn test_oxygen() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.oxygen = 1;
+
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.oxygen, 0);
+    assert_eq!(player.health, Health::new(10) - config::SUFFOCATION_DAMAGE);
+
+    // The screen shows how much health was actually lost, which can be less than a full turn's worth
+    player.health = Health::new(1);
+    let mut menu = MockMenu::default();
+    player.pass_turn(&mut menu);
+    assert!(menu.get_screens().iter().any(|(title, content)| title == "You're suffocating" && content.contains("You lose 1 HP.")));
+
+    map::RoomAction::EngineRoomRefillOxygen.execute(&mut player, &mut MockMenu::default());
+    assert_eq!(player.oxygen, config::PLAYER_MAX_OXYGEN);
+}
+
+/// Tests that the kitchen fire hurts the player each turn until it is put out with an extinguisher, which reveals what it was hiding
+#[test]
+fn test_hazards() {
+    let mut player = Player::init(0);
+    player.health = Health::new(10);
+    player.room = Room::Kitchen;
+    let damage = player.get_room_state().hazard.as_ref().unwrap().damage;
+    let items = player.get_room_state().items.len();
+
+    player.pass_turn(&mut MockMenu::default());
+    assert_eq!(player.health, Health::new(10) - damage);
+
+    let result = map::RoomAction::KitchenPutOutFire.execute(&mut player, &mut MockMenu::default());
+    assert!(result.show_again);
+    assert!(player.get_room_state().hazard.is_some());
+
+    player.inventory.push((Item::FireExtinguisher, 1));
+    let result = map::RoomAction::KitchenPutOutFire.execute(&mut player, &mut MockMenu::default());
+    assert!(!result.show_again);
+    assert!(player.get_room_state().hazard.is_none());
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.get_room_state().items.len(), items + 1);
+
+    // Once the fire is out, the player can recover in the kitchen like anywhere else
+    player.pass_turn(&mut MockMenu::default());
+    assert!(player.health > Health::new(10) - damage);
+}
+
+/// Tests that cooking in the kitchen turns a raw ingredient into better food, and does nothing without one
+#[test]
+fn test_cooking() {
+    let mut player = Player::init(0);
+    player.room = Room::Kitchen;
+
+    let result = map::RoomAction::KitchenCook.execute(&mut player, &mut MockMenu::default());
+    assert!(result.show_again);
+    assert!(result.item.is_none());
+
+    let bread_roll = player.get_room_state().items[0].clone();
+    let Item::Food(raw) = bread_roll.clone() else { panic!("the kitchen should start with a bread roll") };
+    player.inventory = vec![(Item::Dust, 1), (bread_roll, 2)];
+
+    // Backing out shouldn't use anything up
+    let result = map::RoomAction::KitchenCook.execute(&mut player, &mut MockMenu::with_numbers([None]));
+    assert!(result.item.is_none());
+    assert_eq!(player.inventory[1].1, 2);
+
+    let result = map::RoomAction::KitchenCook.execute(&mut player, &mut MockMenu::with_numbers([Some(0)]));
+    assert_eq!(player.inventory[1].1, 1);
+    let Some(Item::Food(cooked)) = result.item else { panic!("cooking should make food") };
+    assert!(cooked.heals_for > raw.heals_for);
+    assert!(cooked.turns_until_spoiled > raw.turns_until_spoiled);
+
+    // Cooked food keeps the rarity of its ingredient
+    let rare = items::cook(&Item::Food(raw).with_rarity(Rarity::Rare)).unwrap();
+    assert!(matches!(rare.get_rarity(), Rarity::Rare));
+}
+
+/// Tests that a shower in the wash room gives the player extra defense for a while, and can't be taken again until it wears off
+#[test]
+fn test_shower() {
+    let mut player = Player::init(0);
+    player.room = Room::WashRoom;
+    let defense = player.get_defense();
+
+    map::RoomAction::WashRoomShower.execute(&mut player, &mut MockMenu::default());
+    assert_eq!(player.fresh_turns, config::FRESH_DURATION_TURNS);
+    assert_eq!(player.get_defense(), defense + config::FRESH_DEFENSE_BONUS);
+
+    // Showering again straight away doesn't top the buff up
+    player.pass_turn(&mut MockMenu::default());
+    map::RoomAction::WashRoomShower.execute(&mut player, &mut MockMenu::default());
+    assert_eq!(player.fresh_turns, config::FRESH_DURATION_TURNS - 1);
+
+    for _ in 1..config::FRESH_DURATION_TURNS {
+        player.pass_turn(&mut MockMenu::default());
+    }
+    assert_eq!(player.fresh_turns, 0);
+    assert_eq!(player.get_defense(), defense);
+}
+
+/// Tests that turning up the holo-screen in the mess hall stops nearby enemies from pursuing the player and makes them easier to sneak past, until it wears off
+#[test]
+fn test_distraction() {
+    let mut player = Player::init(0);
+    player.room = Room::MessHall;
+    let enemy = player.get_room_state_mut().enemy.take().unwrap();
+    let sneak_chance = player.get_sneak_chance_percent(&enemy);
+
+    map::RoomAction::MessHallTurnUpTheGame.execute(&mut player, &mut MockMenu::default());
+    assert!(player.room_graph.is_distracted(Room::MessHall));
+    assert!(player.room_graph.is_distracted(Room::Kitchen));
+    assert!(!player.room_graph.is_distracted(Room::EngineRoom));
+    assert!(player.get_sneak_chance_percent(&enemy) > sneak_chance);
+
+    // An alert enemy next door is too busy watching the game to follow the player in
+    player.room_graph.get_state_mut(Room::Kitchen).enemy = Some(Enemy { alert: true, ..enemy });
+    for _ in 0..20 {
+        player.check_pursuit(&mut MockMenu::default());
+        assert!(player.get_room_state().enemy.is_none());
+    }
+
+    for _ in 0..config::DISTRACTION_DURATION_TURNS {
+        assert!(player.room_graph.distraction.is_some());
+        player.room_graph.tick_distraction();
+    }
+    assert!(!player.room_graph.is_distracted(Room::MessHall));
+}
+
+/// Tests that cutting the power at the breakers darkens the ship, makes sneaking easier and stops the doors from moving, until it is switched back on
+#[test]
+fn test_power() {
+    let mut player = Player::init(0);
+    player.room = Room::EngineRoom;
+    let enemy = map::patrolling_guard();
+    let sneak_chance = player.get_sneak_chance_percent(&enemy);
+    let door = |player: &Player| player.get_room_state().connections[0].door;
+    let open = door(&player);
+
+    map::RoomAction::EngineRoomFlipBreakers.execute(&mut player, &mut MockMenu::default());
+    assert!(!player.room_graph.power_on);
+    assert_eq!(player.get_sneak_chance_percent(&enemy), sneak_chance + config::DARK_SNEAK_BONUS_PERCENT);
+
+    let mut menu = MockMenu::default();
+    player.print_room(&mut menu);
+    assert!(menu.get_last_screen().unwrap().1.contains("The lights are out"));
+
+    player.close_door(&mut MockMenu::default(), Room::LowerCorridor);
+    assert_eq!(door(&player), open);
+
+    map::RoomAction::EngineRoomFlipBreakers.execute(&mut player, &mut MockMenu::default());
+    assert!(player.room_graph.power_on);
+    assert_eq!(player.get_sneak_chance_percent(&enemy), sneak_chance);
+
+    player.close_door(&mut MockMenu::default(), Room::LowerCorridor);
+    assert_eq!(door(&player), DoorState::Closed);
+}
+
+/// Tests that wandering enemies ambush the player some of the time, but never in the escape pod or on top of another enemy
+#[test]
+fn test_random_encounters() {
+    let mut player = Player::init(0);
+
+    player.room = Room::EscapePod;
+    for _ in 0..100 {
+        player.roll_random_encounter(&mut MockMenu::default());
+    }
+    assert!(player.get_room_state().enemy.is_none());
+
+    player.room = Room::Kitchen;
+    for _ in 0..100 {
+        player.roll_random_encounter(&mut MockMenu::default());
+    }
+    assert!(player.get_room_state().enemy.is_some());
+
+    // The mess hall's cook shouldn't be replaced
+    player.room = Room::MessHall;
+    for _ in 0..100 {
+        player.roll_random_encounter(&mut MockMenu::default());
+    }
+    assert_eq!(player.get_room_state().enemy.as_ref().unwrap().name, "Cook");
+}
+
+/// Tests that talking to the crew member in the bunks can reveal a room and give an item, or start a fight
+#[test]
+fn test_npc_dialogue() {
+    let mut player = Player::init(0);
+    player.room = Room::Bunks;
+
+    player.talk_to_npc(&mut MockMenu::with_numbers([Some(0), Some(0), Some(0)]));
+    assert!(player.room_graph.get_state(Room::EngineRoom).visited);
+    assert_eq!(player.inventory.len(), 1);
+    assert_eq!(player.get_room_state().npc.as_ref().unwrap().start_node, 4);
+
+    let mut player = Player::init(0);
+    player.room = Room::Bunks;
+
+    player.talk_to_npc(&mut MockMenu::with_numbers([Some(1), Some(0)]));
+    assert!(player.get_room_state().npc.is_none());
+    assert!(player.get_room_state().enemy.is_some());
+}
+
+/// Tests that objectives are completed as their conditions are met, and that the active one moves on
+#[test]
+fn test_objectives() {
+    let mut player = Player::init(0);
+    assert_eq!(player.objectives.get_active(), Some(Objective::FindKeyCard));
+
+    player.inventory.push((Item::Maps, 1));
+    player.check_objectives(&mut MockMenu::default());
+    assert!(player.objectives.is_complete(Objective::FindMaps));
+    assert_eq!(player.objectives.get_active(), Some(Objective::FindKeyCard));
+
+    player.inventory.push((Item::EscapePodKeys, 1));
+    player.room = Room::EscapePod;
+    player.check_objectives(&mut MockMenu::default());
+    assert_eq!(player.objectives.get_active(), Some(Objective::Escape));
+    assert!(player.get_room_screen_footer().contains(Objective::Escape.get_description()));
+}
+
+/// Tests that notable events are logged to the journal with the turn they happened on, and that the oldest are forgotten once it's full
+#[test]
+fn test_journal() {
+    let mut player = Player::init(0);
+    player.remaining_turns -= 2;
+    player.enter_room(Room::UpperCorridor);
+    player.pick_up_item(&mut MockMenu::default(), Item::Maps);
+
+    // Going back to a room which has already been visited isn't notable
+    player.enter_room(Room::Cells);
+
+    assert_eq!(player.journal.entries.len(), 2);
+    assert_eq!(player.journal.entries[0], (2, "Found your way into the Upper Corridor".to_string()));
+
+    for _ in 0..config::JOURNAL_MAX_ENTRIES {
+        player.log(String::new());
+    }
+    assert_eq!(player.journal.entries.len(), config::JOURNAL_MAX_ENTRIES);
+    assert!(player.journal.entries.iter().all(|(_, event)| event.is_empty()));
+}
+
+/// Tests that each tutorial prompt is only shown once while the tutorial is on, and that it can be turned off from the list of actions
+#[test]
+fn test_tutorial() {
+    let mut menu = MockMenu::default();
+    let mut tutorial = Tutorial::default();
+    tutorial.show(&mut menu, TutorialPrompt::Combat);
+    assert!(menu.get_last_screen().is_none());
+    assert!(!tutorial.has_seen(TutorialPrompt::Combat));
+
+    let mut tutorial = Tutorial::new(true);
+    tutorial.show(&mut menu, TutorialPrompt::Combat);
+    tutorial.show(&mut menu, TutorialPrompt::Combat);
+    assert_eq!(menu.get_screens().len(), 1);
+    assert_eq!(menu.get_last_screen().unwrap().0, "Tutorial: Fighting");
+    assert!(tutorial.has_seen(TutorialPrompt::Combat));
+    assert!(!tutorial.has_seen(TutorialPrompt::PickUp));
+
+    let mut player = Player::init(0);
+    player.tutorial = tutorial;
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    player.choose_passive_action(&mut menu);
+    let skip = menu.get_last_list().unwrap().1.iter().position(|option| option == "Turn off the tutorial").unwrap();
+
+    let mut menu = MockMenu::with_numbers([Some(skip)]);
+    player.take_passive_action(&mut menu);
+    assert!(!player.tutorial.enabled);
+    assert_eq!(menu.get_last_screen().unwrap().0, "Tutorial turned off");
+}
+
+/// Tests that the ending depends on how the player escaped, with a companion taking priority over defeating the captain
+#[test]
+fn test_endings() {
+    let mut player = Player::init(0);
+    assert_eq!(Ending::from_player(&player), Ending::Alone);
+
+    player.defeated_enemies.push("Skipper");
+    assert_eq!(Ending::from_player(&player), Ending::CaptainDefeated);
+
+    // The crew member joins after being helped, asked to come along, and agreeing
+    player.room = Room::Bunks;
+    player.talk_to_npc(&mut MockMenu::with_numbers([Some(0), Some(0), Some(0)]));
+    player.talk_to_npc(&mut MockMenu::with_numbers([Some(0), Some(0)]));
+    assert!(player.get_room_state().npc.is_none());
+    assert_eq!(Ending::from_player(&player), Ending::WithCompanion);
+}
+
+/// Tests that the escape pod only takes as many companions as it has seats for, and that anyone left behind is mentioned when the player wins
+#[test]
+fn test_escape_pod_seats() {
+    let npc = |name| Npc::new(name, &[]);
+    let mut player = Player::init(0);
+    player.room = Room::EscapePod;
+    player.inventory.push((Item::Maps, 1));
+    player.companions = vec![npc("Technician"), npc("Stowaway")];
+
+    // Backing out of the choice doesn't take off or leave anyone behind
+    map::RoomAction::EscapePodTakeOff.execute(&mut player, &mut MockMenu::with_numbers([None]));
+    assert!(!player.has_escaped());
+    assert_eq!(player.companions.len(), 2);
+    assert!(player.left_behind.is_empty());
+
+    map::RoomAction::EscapePodTakeOff.execute(&mut player, &mut MockMenu::with_numbers([Some(1)]));
+    assert!(player.has_escaped());
+    assert_eq!(player.companions.len(), config::ESCAPE_POD_SEATS - 1);
+    assert_eq!(player.companions[0].name, "Stowaway");
+    assert_eq!(player.left_behind, vec!["Technician"]);
+    assert_eq!(Ending::from_player(&player), Ending::WithCompanion);
+
+    let mut menu = MockMenu::default();
+    player.show_win_screen(&mut menu, Ending::from_player(&player), &Timer::start(true).finish());
+    assert!(menu.get_last_screen().unwrap().1.contains("Technician"));
+}
+
+/// Tests that New Game Plus keeps the player's level and chosen item, and makes enemies tougher
+#[test]
+fn test_new_game_plus() {
+    let new_game_plus = NewGamePlus { cycle: 1, xp: config::LEVEL_XP_THRESHOLDS[1], item: Some(Item::Maps) };
+    let normal = Player::init(0);
+    let player = Player::init_new_game_plus(0, &new_game_plus, DynamicBalance::default());
+
+    assert_eq!(player.level, 3);
+    assert!(player.max_health > normal.max_health);
+    assert!(player.inventory.iter().any(|(item, _)| matches!(item, Item::Maps)));
+
+    let enemy_health = |player: &Player| player.room_graph.get_state(Room::MessHall).enemy.as_ref().unwrap().max_health;
+    // Enemies are scaled to the player's level as well as the cycle
+    let level_health = Damage::new(2 * config::ENEMY_HEALTH_PER_PLAYER_LEVEL);
+    assert_eq!(enemy_health(&player), enemy_health(&normal) + config::NEW_GAME_PLUS_ENEMY_HEALTH + level_health);
+}
+
+/// Tests that dropping remains empties the inventory, and that placing them in the next loop leaves every item where the player died
+#[test]
+fn test_remains() {
+    let mut player = Player::init(0);
+    assert!(player.drop_remains().is_none());
+
+    player.room = Room::Kitchen;
+    player.inventory = vec![(Item::Dust, 2), (Item::Maps, 1)];
+    player.equipped_weapon = Some(1);
+    let remains = player.drop_remains().unwrap();
+
+    assert!(player.inventory.is_empty());
+    assert!(player.equipped_weapon.is_none());
+    assert_eq!(remains.room, Room::Kitchen);
+    assert_eq!(remains.items.len(), 3);
+
+    let mut next = Player::init(0);
+    let kitchen_items = next.room_graph.get_state(Room::Kitchen).items.len();
+    next.place_remains(&mut MockMenu::default(), remains);
+    assert_eq!(next.room_graph.get_state(Room::Kitchen).items.len(), kitchen_items + 3);
+}
+
+/// Tests that alert enemies follow the player through open doors, but not through closed ones or when they aren't alert
+#[test]
+fn test_pursuit() {
+    let guard = |alert| {
+        let mut enemy = map::patrolling_guard();
+        enemy.alert = alert;
+        enemy
+    };
+
+    // An enemy who hasn't seen the player stays where they are
+    let mut player = Player::init(0);
+    player.room_graph.get_state_mut(Room::UpperCorridor).enemy = Some(guard(false));
+    for _ in 0..50 {
+        player.check_pursuit(&mut MockMenu::default());
+    }
+    assert!(player.get_room_state().enemy.is_none());
+
+    // A closed door stops an alert enemy from following
+    player.room_graph.get_state_mut(Room::UpperCorridor).enemy = Some(guard(true));
+    player.room_graph.set_door(Room::Cells, Room::UpperCorridor, DoorState::Closed);
+    for _ in 0..50 {
+        player.check_pursuit(&mut MockMenu::default());
+    }
+    assert!(player.get_room_state().enemy.is_none());
+
+    // With the door open, they catch up sooner or later
+    player.room_graph.set_door(Room::Cells, Room::UpperCorridor, DoorState::Open);
+    for _ in 0..50 {
+        player.check_pursuit(&mut MockMenu::default());
+    }
+    assert!(player.get_room_state().enemy.is_some());
+    assert!(player.room_graph.get_state(Room::UpperCorridor).enemy.is_none());
+}
+
+/// Tests that raising the alarm alerts every enemy and stops at the max level, and that it can be silenced from the bridge
+#[test]
+fn test_alarm() {
+    let mut player = Player::init(0);
+    assert!(!player.room_graph.alarm.is_active());
+
+    player.raise_alarm(&mut MockMenu::default(), "");
+    assert_eq!(player.room_graph.alarm.get_level(), 1);
+    assert!(player.room_graph.rooms.values().filter_map(|r| r.enemy.as_ref()).all(|e| e.alert));
+    assert!(player.get_room_screen_footer().contains("alarm"));
+
+    for _ in 0..config::ALARM_MAX_LEVEL + 2 {
+        player.raise_alarm(&mut MockMenu::default(), "");
+    }
+    assert_eq!(player.room_graph.alarm.get_level(), config::ALARM_MAX_LEVEL);
+
+    let result = map::RoomAction::BridgeSilenceAlarm.execute(&mut player, &mut MockMenu::default());
+    assert!(result.show_again);
+    assert!(!player.room_graph.alarm.is_active());
+    assert!(!player.get_room_screen_footer().contains("alarm"));
+}
+
+/// Tests that the player can only fight or sneak past an enemy who hasn't seen them, and that sneaking either hides them or starts the fight
+#[test]
+fn test_sneak() {
+    let mut player = Player::init(0);
+    let guard = |awareness| {
+        let mut enemy = map::patrolling_guard();
+        enemy.alert = false;
+        enemy.awareness = awareness;
+        enemy
+    };
+    player.get_room_state_mut().enemy = Some(guard(0));
+
+    // Only fighting, sneaking and a few things that don't take a turn are on offer
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    assert!(matches!(player.choose_passive_action(&mut menu), PassiveAction::Fight));
+    assert_eq!(menu.get_last_list().unwrap().1.len(), 8);
+    assert!(menu.get_last_list().unwrap().1.contains(&lang::get("player.action.inspect_connections").to_string()));
+    let settings = menu.get_last_list().unwrap().1.iter().position(|o| *o == lang::get("player.action.settings")).unwrap();
+
+    // Changing the settings goes back to the same choice
+    let mut menu = MockMenu::with_numbers([Some(settings), None, Some(0)]);
+    assert!(matches!(player.choose_passive_action(&mut menu), PassiveAction::Fight));
+
+    // A careful enough player always gets past
+    player.level = 9;
+    assert_eq!(player.get_sneak_chance_percent(&guard(0)), 100);
+    player.sneak(&mut MockMenu::default());
+    assert!(player.hidden);
+    assert!(!player.get_room_state().enemy.as_ref().unwrap().alert);
+
+    // Leaving the room means sneaking past whoever is in the next one
+    player.enter_room(Room::UpperCorridor);
+    assert!(!player.hidden);
+
+    // A very aware enemy always spots the player, hits them first and raises the alarm
+    player.get_room_state_mut().enemy = Some(guard(100));
+    assert_eq!(player.get_sneak_chance_percent(&guard(100)), 0);
+    let health = player.health;
+    player.sneak(&mut MockMenu::default());
+    assert!(!player.hidden);
+    assert!(player.get_room_state().enemy.as_ref().unwrap().alert);
+    assert!(player.health < health);
+    assert!(player.room_graph.alarm.is_active());
+}
+
+/// Tests that a bandage heals a little each turn until it runs out, and shows up in the player's state while it's on
+#[test]
+fn test_bandage() {
+    let mut player = Player::init(0);
+    player.health = Health::new(1);
+    player.max_health = Health::new(100);
+    player.inventory.push((Item::Bandage, 1));
+
+    player.use_item(&mut MockMenu::default(), 0);
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.bandage_turns, config::BANDAGE_DURATION_TURNS);
+
+    let mut menu = MockMenu::default();
+    player.print_state(&mut menu);
+    assert!(menu.get_last_screen().unwrap().1.contains("bandage"));
+
+    for _ in 0..config::BANDAGE_DURATION_TURNS {
+        assert_eq!(player.tick_bandage(), Some(config::BANDAGE_HEAL_PER_TURN));
+    }
+    assert_eq!(player.health, Health::new(1 + config::BANDAGE_HEAL_PER_TURN.as_usize() * config::BANDAGE_DURATION_TURNS));
+    assert_eq!(player.tick_bandage(), None);
+}
+
+/// Tests that a repair kit restores a worn weapon's durability up to its max and is used up, but is kept if nothing needs repairing
+#[test]
+fn test_repair_kit() {
+    let mut player = Player::init(0);
+    player.inventory.push((Item::RepairKit, 2));
+
+    // Nothing to repair, so the kit is kept
+    player.use_item(&mut MockMenu::default(), 0);
+    assert_eq!(player.inventory[0].1, 2);
+
+    player.inventory.push((Item::Weapon(Weapon {
+        name: "",
+        description: "",
+        damage_type: DamageType::Blunt,
+        straight_damage: Damage::new(1),
+        dodge_damage: Damage::new(1),
+        speed: 1,
+        throw_damage: Damage::new(1),
+        two_handed: false,
+        accuracy: 100,
+        reach: 1,
+        durability: 1,
+        max_durability: config::REPAIR_KIT_AMOUNT + 5,
+        weight: 0,
+        value: 0,
+        rarity: Rarity::Common,
+        attack_style: None,
+    }), 1));
+    let durability = |player: &Player| match &player.inventory.last().unwrap().0 {
+        Item::Weapon(w) => w.durability,
+        _ => unreachable!(),
+    };
+
+    player.use_item(&mut MockMenu::with_numbers([Some(0)]), 0);
+    assert_eq!(durability(&player), 1 + config::REPAIR_KIT_AMOUNT);
+    assert_eq!(player.inventory[0].1, 1);
+
+    // Repairs don't go past the weapon's max durability
+    player.use_item(&mut MockMenu::with_numbers([Some(0)]), 0);
+    assert_eq!(durability(&player), config::REPAIR_KIT_AMOUNT + 5);
+    assert_eq!(player.inventory.len(), 1);
+}
+
+/// Tests that picking several stacks from the inventory toggles them on and off, and drops every item in the ones left picked
+#[test]
+fn test_drop_several_items() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Bandage, 2), (Item::Dust, 1), (Item::RepairKit, 1)];
+    let room_items = player.get_room_state().items.len();
+
+    // Pick the bandages, the dust and the repair kit, then change your mind about the dust
+    let mut menu = MockMenu::with_numbers([Some(3), Some(0), Some(1), Some(2), Some(1), None, None]);
+    player.open_inventory(&mut menu);
+
+    assert_eq!(player.inventory, vec![(Item::Dust, 1)]);
+    assert_eq!(player.get_room_state().items.len(), room_items + 3);
+}
+
+/// Tests that the player is asked how many items to drop from a stack, and that dropping none leaves the stack alone
+#[test]
+fn test_drop_quantity() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Bandage, 5)];
+    let room_items = player.get_room_state().items.len();
+
+    let mut menu = MockMenu::default().with_typed_numbers(["some", "0"]);
+    player.drop_item(&mut menu, 0);
+    assert_eq!(player.inventory, vec![(Item::Bandage, 5)]);
+
+    let mut menu = MockMenu::default().with_typed_numbers(["3"]);
+    player.drop_item(&mut menu, 0);
+    assert_eq!(player.inventory, vec![(Item::Bandage, 2)]);
+    assert_eq!(player.get_room_state().items.len(), room_items + 3);
+
+    let mut menu = MockMenu::default().with_typed_numbers(["2"]);
+    player.drop_item(&mut menu, 0);
+    assert!(player.inventory.is_empty());
+}
+
+/// Tests that developer mode can teleport anywhere, including straight to the escape, and heal the player fully
+#[test]
+fn test_dev_actions() {
+    let mut player = Player::init(0);
+
+    // Rooms are listed in order, followed by the escape
+    player.dev_teleport(&mut MockMenu::with_numbers([Some(12)]));
+    assert_eq!(player.room, Room::EngineRoom);
+    assert!(player.get_room_state().visited);
+
+    player.dev_teleport(&mut MockMenu::with_numbers([None]));
+    assert_eq!(player.room, Room::EngineRoom);
+
+    player.dev_teleport(&mut MockMenu::with_numbers([Some(14)]));
+    assert_eq!(player.room, Room::Escape);
+
+    player.health = Health::new(1);
+    player.dev_heal(&mut MockMenu::default());
+    assert_eq!(player.health, player.max_health);
+}
+
+/// Tests that moving around and picking things up is counted in the player's stats
+#[test]
+fn test_run_stats() {
+    let mut player = Player::init(0);
+    assert_eq!(player.stats.rooms_visited, 1);
+
+    player.enter_room(Room::UpperCorridor);
+    player.enter_room(Room::Cells);
+    assert_eq!(player.stats.rooms_visited, 2);
+    assert_eq!(player.stats.distance_traveled, 2);
+
+    player.pick_up_item(&mut MockMenu::default(), Item::Bandage);
+    player.pick_up_item(&mut MockMenu::default(), Item::Currency(5));
+    assert_eq!(player.stats.items_collected, 1);
+
+    player.take_hit(Damage::new(3));
+    assert_eq!(player.stats.damage_taken, 3);
+}
+
+/// Gets the wrench from the [items which can be spawned][map::ITEMS], as a weapon for testing
+fn wrench() -> Item {
+    map::ITEMS.iter().find(|(name, _)| *name == "wrench").unwrap().1.clone()
+}
+
+/// Tests that part of a stack can be split off into a new stack after it, and that weapons can't be split
+#[test]
+fn test_split_stack() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Bandage, 5), (wrench(), 1)];
+    player.equipped_weapon = Some(1);
+
+    player.split_stack(&mut MockMenu::default().with_typed_numbers(["0"]), 0);
+    assert_eq!(player.inventory[0], (Item::Bandage, 5));
+
+    // The whole stack can't be split off
+    player.split_stack(&mut MockMenu::default().with_typed_numbers(["5", "3"]), 0);
+    assert_eq!(player.inventory[..2], [(Item::Bandage, 2), (Item::Bandage, 3)]);
+    assert_eq!(player.equipped_weapon, Some(2));
+
+    player.split_stack(&mut MockMenu::default().with_typed_numbers(["1"]), 2);
+    assert_eq!(player.inventory.len(), 3);
+}
+
+/// Tests that moving a stack around the inventory keeps the right items equipped
+#[test]
+fn test_move_stack() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Bandage, 2), (wrench(), 1), (Item::Dust, 1)];
+    player.equipped_weapon = Some(1);
+
+    player.move_stack(0, 2);
+    assert_eq!(player.inventory[2], (Item::Bandage, 2));
+    assert_eq!(player.equipped_weapon, Some(0));
+
+    player.move_stack(0, 1);
+    assert_eq!(player.inventory[0], (Item::Dust, 1));
+    assert_eq!(player.equipped_weapon, Some(1));
+
+    // Moving it from the inventory screen
+    let mut menu = MockMenu::with_numbers([Some(1), Some(4), Some(0), None]);
+    player.open_inventory(&mut menu);
+    assert!(matches!(player.inventory[0].0, Item::Weapon(_)));
+    assert_eq!(player.equipped_weapon, Some(0));
+}
+
+/// Tests that sorting the inventory keeps the right items equipped, stays sorted as things are picked up, and stops once something is moved by hand
+#[test]
+fn test_sort_inventory() {
+    let mut player = Player::init(0);
+    player.inventory = vec![(Item::Dust, 1), (Item::Bandage, 2), (wrench(), 1)];
+    player.equipped_weapon = Some(2);
+
+    // Sorting by type from the inventory screen, after the items, dropping several and sorting
+    player.open_inventory(&mut MockMenu::with_numbers([Some(4), Some(0), None]));
+    assert_eq!(player.inventory_sort, Some(InventorySort::Type));
+    assert!(matches!(player.inventory[0].0, Item::Weapon(_)));
+    assert_eq!(player.inventory[1], (Item::Bandage, 2));
+    assert_eq!(player.inventory[2], (Item::Dust, 1));
+    assert_eq!(player.equipped_weapon, Some(0));
+
+    // New items are put in order the next time the inventory is opened
+    player.inventory.push((Item::Explosive, 1));
+    player.open_inventory(&mut MockMenu::with_numbers([None]));
+    assert_eq!(player.inventory[2], (Item::Explosive, 1));
+
+    player.sort_inventory(InventorySort::Name);
+    assert_eq!(player.inventory[0], (Item::Dust, 1));
+    assert_eq!(player.inventory[1], (Item::Bandage, 2));
+    assert!(matches!(player.inventory[3].0, Item::Weapon(_)));
+    assert_eq!(player.equipped_weapon, Some(3));
+
+    // Moving the wrench to the start by hand stops the inventory from being sorted back by name
+    player.inventory_sort = Some(InventorySort::Name);
+    player.open_inventory(&mut MockMenu::with_numbers([Some(3), Some(4), Some(0), None]));
+    assert_eq!(player.inventory_sort, None);
+    assert!(matches!(player.inventory[0].0, Item::Weapon(_)));
+    assert_eq!(player.equipped_weapon, Some(0));
+}
+
+/// Tests that resting passes turns until the player is healed, and stops early when they get hungry
+#[test]
+fn test_rest() {
+    let mut player = Player::init(0);
+    // Nothing can ambush the player in the escape pod
+    player.room = Room::EscapePod;
+    player.health = Health::new(4);
+    player.hunger = 0;
+
+    let mut menu = MockMenu::default();
+    player.rest(&mut menu);
+    assert_eq!(player.health, player.max_health);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 6);
+    assert_eq!(menu.get_last_screen().unwrap().0, "You rest for 7 turns");
+
+    player.health = Health::new(4);
+    player.hunger = config::HUNGER_WARNING - 2;
+    player.rest(&mut menu);
+    assert_eq!(player.health, Health::new(6));
+    assert_eq!(player.hunger, config::HUNGER_WARNING);
+}
+
+/// Tests that taking off escapes the ship, and that the end of the turn can still be checked even though the escape isn't a real room
+#[test]
+fn test_take_off() {
+    let mut player = Player::init(0);
+    player.room = Room::EscapePod;
+    player.inventory.push((Item::Maps, 1));
+
+    let mut menu = MockMenu::default();
+    map::RoomAction::EscapePodTakeOff.execute(&mut player, &mut menu);
+    assert_eq!(player.room, Room::Escape);
+    assert!(player.has_escaped());
+
+    // The win screen should come first, but the escape still reads properly if it's ever shown
+    player.print_room(&mut menu);
+    assert_eq!(menu.get_last_screen().unwrap().0, format!("You are in the {}.", Room::Escape.get_name()));
+    assert!(!Room::Escape.get_name().is_empty() && !Room::Escape.get_description().is_empty());
+
+    player.check_pursuit(&mut menu);
+    player.check_objectives(&mut menu);
+    player.check_achievements(&mut menu);
+    assert!(player.get_room_state().enemy.is_none());
+}
+
+/// Tests that quitting is offered, remembering whether to keep the save once it is confirmed
+#[test]
+fn test_quit_to_menu() {
+    let player = Player::init(0);
+
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    let _ = player.choose_passive_action(&mut menu);
+    let quit = menu.get_last_list().unwrap().1.iter().position(|o| *o == lang::get("player.action.quit")).unwrap();
+
+    let mut menu = MockMenu::with_numbers([Some(quit)]).with_confirmations([true, true]);
+    assert!(matches!(player.choose_passive_action(&mut menu), PassiveAction::QuitToMenu(Quit::Save)));
+
+    let mut menu = MockMenu::with_numbers([Some(quit)]).with_confirmations([true, false]);
+    assert!(matches!(player.choose_passive_action(&mut menu), PassiveAction::QuitToMenu(Quit::Discard)));
+}
+
+/// Tests that changing the settings or backing out of quitting doesn't use up a turn
+#[test]
+fn test_meta_actions_keep_turn() {
+    let mut player = Player::init(0);
+    let mut menu = MockMenu::with_numbers([Some(0)]);
+    let _ = player.choose_passive_action(&mut menu);
+    let options = &menu.get_last_list().unwrap().1;
+    let quit = options.iter().position(|o| *o == lang::get("player.action.quit")).unwrap();
+    let settings = options.iter().position(|o| *o == lang::get("player.action.settings")).unwrap();
+    let check_state = 0;
+
+    let turns = player.remaining_turns;
+    let mut menu = MockMenu::with_numbers([Some(settings), None, Some(quit), Some(check_state)]).with_confirmations([false]);
+    player.take_passive_action(&mut menu);
+    // Only the one turn for checking their state has passed
+    assert_eq!(player.remaining_turns, turns - 1);
+    assert_eq!(player.quit, None);
+}
+
+/// Tests that inspecting the room's connections shows their doors and whether they've been visited, without giving away unseen hazards
+#[test]
+fn test_inspect_connections() {
+    let mut player = Player::init(0);
+    player.room = Room::MessHall;
+    player.get_room_state_mut().connections.iter_mut().for_each(|c| c.door = DoorState::Closed);
+
+    let mut menu = MockMenu::default();
+    player.inspect_connections(&mut menu);
+    let content = &menu.get_last_screen().unwrap().1;
+    assert_eq!(content.lines().count(), player.get_room_state().connections.len());
+    assert!(content.contains(Room::Kitchen.get_name()));
+    assert!(content.contains("the door is closed, and you haven't been there yet"));
+    assert!(!content.contains("It isn't safe"));
+
+    // Once the player has seen the fire in the kitchen, they remember it
+    player.room_graph.get_state_mut(Room::Kitchen).visited = true;
+    player.inspect_connections(&mut menu);
+    let content = &menu.get_last_screen().unwrap().1;
+    assert!(content.contains("you've been there before"));
+    assert!(content.contains("It isn't safe"));
+}
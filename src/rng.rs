@@ -0,0 +1,48 @@
+//! A small seedable random number generator.
+//! All random decisions in the game should draw from the [`Rng`] owned by the [`Player`][crate::player::Player],
+//! so that a run can be reproduced exactly from its seed.
+
+mod tests;
+
+/// A pseudorandom number generator using the `SplitMix64` algorithm.
+/// This is not cryptographically secure, but it is fast and the same seed will always produce the same numbers.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    /// The internal state, which is advanced each time a number is generated
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a new [`Rng`] from a seed
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Gets a seed based on the current time, for when the player doesn't choose one
+    pub fn seed_from_time() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() ^ u64::from(d.subsec_nanos()))
+    }
+
+    /// Generates a pseudorandom [`u64`]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a pseudorandom number from 0 up to but not including `max`
+    ///
+    /// ### Panics
+    /// * If `max` is 0
+    pub fn below(&mut self, max: usize) -> usize {
+        assert!(max != 0, "Can't generate a number below 0");
+
+        let n = self.next_u64() % max as u64;
+        usize::try_from(n).expect("Value should be less than max, which is a usize")
+    }
+}
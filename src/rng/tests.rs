@@ -0,0 +1,29 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that two [`Rng`]s with the same seed produce the same numbers
+#[test]
+fn test_same_seed() {
+    let mut a = Rng::new(2168);
+    let mut b = Rng::new(2168);
+
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+}
+
+/// Tests that [`Rng::below`] stays within its range and produces every value in it
+#[test]
+fn test_below() {
+    let mut rng = Rng::new(0);
+    let mut seen = [false; 8];
+
+    for _ in 0..1000 {
+        let n = rng.below(8);
+        assert!(n < 8);
+        seen[n] = true;
+    }
+
+    assert!(seen.iter().all(|s| *s));
+}
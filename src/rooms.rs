@@ -1,13 +1,18 @@
 //! Functionality related to rooms
 
-use std::collections::HashMap;
+mod tests;
 
-use crate::{combat::Enemy, items::Item, map::RoomAction};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Write};
+
+use serde::Deserialize;
+
+use crate::{alarm::Alarm, combat::{Damage, Enemy}, items::Item, lang, map::RoomAction, npc::Npc, shop::Shop};
 
 /// One of the game's rooms.
 /// This does not store the room's state, and is only an identifier.
 /// For the state of a room, use [`RoomState`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
 pub enum Room {
     /// The bridge
     Bridge,
@@ -39,77 +44,211 @@ pub enum Room {
 
     /// The escape pod
     EscapePod,
-    /// The room which triggers winning the game
+    /// The room which triggers winning the game. It isn't walked into like the others:
+    /// [taking off][crate::map::RoomAction::EscapePodTakeOff] puts the [`Player`][crate::player::Player] here directly,
+    /// and once they [have escaped][crate::player::Player::has_escaped] the win screen is shown before another turn starts.
     Escape,
 }
 
 impl Room {
-    /// Get the name of a room
-    pub const fn get_name(self) -> &'static str {
+    /// Every [`Room`], in order. Each one has a [`RoomState`] in the [`RoomGraph`] built by [`map::init`][crate::map::init].
+    pub const ALL: [Self; 15] = [
+        Self::Bridge,
+        Self::UpperCorridor,
+        Self::StrategyRoom,
+        Self::Cells,
+        Self::MessHall,
+        Self::Kitchen,
+        Self::Stairwell,
+        Self::CrewArea,
+        Self::StoreRoom,
+        Self::LowerCorridor,
+        Self::WashRoom,
+        Self::Bunks,
+        Self::EngineRoom,
+        Self::EscapePod,
+        Self::Escape,
+    ];
+
+    /// Gets where the [`Room`] comes in [`Room::ALL`].
+    /// The match has no wildcard, so a new [`Room`] can't be added without giving it a place, which is then checked against the list at compile time.
+    const fn index(self) -> usize {
         match self {
-            Self::Bridge => "Bridge",
-            Self::UpperCorridor => "Upper Corridor",
-            Self::StrategyRoom => "Strategy Room",
-            Self::Cells => "Cells",
-            Self::MessHall => "Mess Hall",
-            Self::Kitchen => "Kitchen",
-            Self::Stairwell => "Stairwell",
-
-            Self::CrewArea => "Crew Area",
-            Self::StoreRoom => "Store Room",
-            Self::LowerCorridor => "Lower Corridor",
-            Self::WashRoom => "Wash Room",
-            Self::Bunks => "Bunks",
-            Self::EngineRoom => "Engine Room",
-
-            Self::EscapePod => "Escape Pod",
-            Self::Escape => "",
+            Self::Bridge => 0,
+            Self::UpperCorridor => 1,
+            Self::StrategyRoom => 2,
+            Self::Cells => 3,
+            Self::MessHall => 4,
+            Self::Kitchen => 5,
+            Self::Stairwell => 6,
+            Self::CrewArea => 7,
+            Self::StoreRoom => 8,
+            Self::LowerCorridor => 9,
+            Self::WashRoom => 10,
+            Self::Bunks => 11,
+            Self::EngineRoom => 12,
+            Self::EscapePod => 13,
+            Self::Escape => 14,
         }
     }
 
-    /// Get a short description of a room
-    pub const fn get_description(self) -> &'static str {
+    /// Get the name of a room, in the active [`Language`][lang::Language]
+    pub fn get_name(self) -> &'static str {
         match self {
-            Self::Bridge => "The control centre of the ship. Through the front window you can see into the darkness of space.",
-            Self::UpperCorridor => "A corridor connecting the bridge to the rest of the ship.",
-            Self::StrategyRoom => "Where important tactical decisions are made. Before you arrived, the most important decision since since leaving the front lines had been what galactic time zone to use.",
-            Self::Cells => "Where they keep prisoners such as yourself. The ship is on a skeleton crew on its way to pick up troops and the security isn't up to scratch, so you managed to force open the door.",
-            Self::MessHall => "Where the crew eat their meals. A holo-screen in the corner is playing a game of half-G volleyball.",
-            Self::Kitchen => "An immaculately clean kitchen area. All the appliances are electric - no open flames are allowed on the ship.",
-            Self::Stairwell => "A stairwell. There's not much to do, but out the window you can see the ship's engines pushing you forward into your captors' grip.",
-            
-            Self::CrewArea => "Where the soldiers relax after a long cycle. If there were any, that is. There's a dart board on the wall, but no darts anywhere.",
-            Self::StoreRoom => "A small room with many shelves containing various things. The light is broken so you can only make out shapes close to the door.",
-            Self::LowerCorridor => "A corridor connecting the crew area to the engine room.",
-            Self::WashRoom => "A spotless wash room containing a few showers and a few toilets. This is a military vessel, so there's no need for privacy.",
-            Self::Bunks => "The soldiers will sleep here when they are on board",
-            Self::EngineRoom => "Where the ship's internals are serviced from. The actual engines are at the back of the ship, but this is where the boiler and the electrical breakers are.",
-
-            Self::EscapePod => "A pod big enough for only two people. It has enough fuel to get you to safety, but only just.",
-            Self::Escape => "",
+            Self::Bridge => lang::get("room.bridge.name"),
+            Self::UpperCorridor => lang::get("room.upper_corridor.name"),
+            Self::StrategyRoom => lang::get("room.strategy_room.name"),
+            Self::Cells => lang::get("room.cells.name"),
+            Self::MessHall => lang::get("room.mess_hall.name"),
+            Self::Kitchen => lang::get("room.kitchen.name"),
+            Self::Stairwell => lang::get("room.stairwell.name"),
+
+            Self::CrewArea => lang::get("room.crew_area.name"),
+            Self::StoreRoom => lang::get("room.store_room.name"),
+            Self::LowerCorridor => lang::get("room.lower_corridor.name"),
+            Self::WashRoom => lang::get("room.wash_room.name"),
+            Self::Bunks => lang::get("room.bunks.name"),
+            Self::EngineRoom => lang::get("room.engine_room.name"),
+            Self::EscapePod => lang::get("room.escape_pod.name"),
+            Self::Escape => lang::get("room.escape.name"),
+        }
+    }
+
+    /// Get a short description of a room, in the active [`Language`][lang::Language]
+    pub fn get_description(self) -> &'static str {
+        match self {
+            Self::Bridge => lang::get("room.bridge.description"),
+            Self::UpperCorridor => lang::get("room.upper_corridor.description"),
+            Self::StrategyRoom => lang::get("room.strategy_room.description"),
+            Self::Cells => lang::get("room.cells.description"),
+            Self::MessHall => lang::get("room.mess_hall.description"),
+            Self::Kitchen => lang::get("room.kitchen.description"),
+            Self::Stairwell => lang::get("room.stairwell.description"),
+
+            Self::CrewArea => lang::get("room.crew_area.description"),
+            Self::StoreRoom => lang::get("room.store_room.description"),
+            Self::LowerCorridor => lang::get("room.lower_corridor.description"),
+            Self::WashRoom => lang::get("room.wash_room.description"),
+            Self::Bunks => lang::get("room.bunks.description"),
+            Self::EngineRoom => lang::get("room.engine_room.description"),
+            Self::EscapePod => lang::get("room.escape_pod.description"),
+            Self::Escape => lang::get("room.escape.description"),
         }
     }
 }
 
+// Fails to compile unless every room in `Room::ALL` is at the index it should be
+const _: () = {
+    let mut i = 0;
+    while i < Room::ALL.len() {
+        assert!(Room::ALL[i].index() == i, "Room::ALL doesn't match Room::index");
+        i += 1;
+    }
+};
+
+/// The direction a [`RoomTransition`] leads in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Direction {
+    /// Towards the front of the ship
+    North,
+    /// Towards the back of the ship
+    South,
+    /// To starboard
+    East,
+    /// To port
+    West,
+    /// To the deck above
+    Up,
+    /// To the deck below
+    Down,
+}
+
+impl Direction {
+    /// Gets the name of the direction in the active [`Language`][lang::Language], as it would be written in the middle of a sentence
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::North => lang::get("direction.north"),
+            Self::South => lang::get("direction.south"),
+            Self::East => lang::get("direction.east"),
+            Self::West => lang::get("direction.west"),
+            Self::Up => lang::get("direction.up"),
+            Self::Down => lang::get("direction.down"),
+        }
+    }
+}
+
+/// The state of the door on a [`RoomTransition`].
+/// The [`Player`][crate::player::Player] can only move through open doors, and has to open closed ones first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum DoorState {
+    /// The door is open, so the [`Player`][crate::player::Player] can walk through it
+    #[default]
+    Open,
+    /// The door is closed, but can be opened
+    Closed,
+    /// The door can't be opened until something unlocks it, such as a [`RoomAction`]
+    Locked,
+}
+
 /// A transition between two [`Room`]s
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RoomTransition {
     /// A message to display when moving
     pub message: &'static str,
     /// Which [`Room`] to go to
     pub to: Room,
+    /// Which way the [`Room`] is from the one the transition starts in
+    pub direction: Direction,
     /// What option to show the player. If [`None`], it will default to the name of [Self::to]
     pub prompt_text: Option<&'static str>,
+    /// Whether the door is open, closed, or locked. The door is shared with the transition going back the other way, so this should be changed with [`RoomGraph::set_door`].
+    pub door: DoorState,
+    /// How many pins the door's lock has, which makes it harder to [pick][crate::items::Item::Lockpick] from this side while it is [locked][DoorState::Locked].
+    /// A lock with no pins can't be picked at all.
+    pub lock_pins: usize,
+}
+
+/// A trap which hurts the [`Player`][crate::player::Player] when they walk into a [`Room`]
+#[derive(Debug, Clone)]
+pub struct Trap {
+    /// The title of the [`Screen`][crate::menu::Screen] shown when the trap goes off
+    pub name: &'static str,
+    /// What happens when the trap goes off
+    pub description: &'static str,
+    /// How much damage the trap deals
+    pub damage: Damage,
+    /// Whether the trap disarms itself after going off. Traps which don't will go off every time the room is entered.
+    pub one_shot: bool,
+}
+
+/// An ongoing danger in a [`Room`], such as a fire, which hurts the [`Player`][crate::player::Player] for every turn they spend there until it is dealt with.
+/// While it lasts, the items in the room can't be reached, and the only [actions][RoomState::actions] offered are those which [deal with it][RoomAction::resolves_hazard].
+/// Once it has been dealt with it stays that way for the rest of the loop.
+#[derive(Debug, Clone)]
+pub struct Hazard {
+    /// The title of the [`Screen`][crate::menu::Screen] shown when the hazard hurts the player
+    pub name: &'static str,
+    /// A line added to the room's description while the hazard lasts
+    pub description: &'static str,
+    /// How much damage the hazard deals for each turn the player spends in the room
+    pub damage: Damage,
+    /// Items which the hazard was keeping out of reach, added to the room's [items][RoomState::items] once it is dealt with
+    pub reveals: &'static [Item],
 }
 
 /// The state of a room. 
 /// [`RoomState`]s can be constructed with [`new`][Self::new] and properties can be added using 
-/// [`add_item`][Self::add_item], [`add_action`][Self::add_action], and [`with_enemy`][Self::with_enemy]
+/// [`add_item`][Self::add_item], [`hide_item`][Self::hide_item], [`add_action`][Self::add_action], [`with_enemy`][Self::with_enemy], [`with_shop`][Self::with_shop], [`with_trap`][Self::with_trap], [`with_npc`][Self::with_npc], and [`with_hazard`][Self::with_hazard]
 /// ```
 /// let room_state = RoomState::new(Room::Bridge, vec![...])
 ///     .add_item(...)
+///     .hide_item(...)
 ///     .add_action(...)
-///     .with_enemy(...);
+///     .with_enemy(...)
+///     .with_shop(...)
+///     .with_trap(...)
+///     .with_npc(...)
+///     .with_hazard(...);
 /// ```
 #[derive(Debug)]
 pub struct RoomState {
@@ -117,24 +256,46 @@ pub struct RoomState {
     pub room: Room,
     /// What items are in the room for the [`Player`][crate::player::Player] to pick up
     pub items: Vec<Item>,
+    /// Items which will be moved into [`items`][Self::items] when the [`Player`][crate::player::Player] searches the room
+    pub hidden_items: Vec<Item>,
     /// An [`Enemy`], if there is one
     pub enemy: Option<Enemy>,
     /// Which other rooms the player can go to from this one
     pub connections: Vec<RoomTransition>,
     /// Which actions can be performed in this room
-    pub actions: Vec<RoomAction>
+    pub actions: Vec<RoomAction>,
+    /// A [`Shop`], if there is one
+    pub shop: Option<Shop>,
+    /// Whether the [`Player`][crate::player::Player] has been in this room during the current loop
+    pub visited: bool,
+    /// Whether the [`Player`][crate::player::Player] knows where this room is from the [deck plans][Item::ShipMap], even if they haven't been in it.
+    /// Unlike [`visited`][Self::visited] rooms, known rooms can't be fast travelled to.
+    pub known: bool,
+    /// A [`Trap`] which goes off when the [`Player`][crate::player::Player] walks in, if there is one
+    pub trap: Option<Trap>,
+    /// An [`Npc`] the [`Player`][crate::player::Player] can talk to, if there is one
+    pub npc: Option<Npc>,
+    /// A [`Hazard`] which hurts the [`Player`][crate::player::Player] while they stay in the room, if there is one
+    pub hazard: Option<Hazard>,
 }
 
 impl RoomState {
     /// Creates a new [`RoomState`] from a provided [`Room`] and connections.
-    /// [`items`][Self::items] and [`actions`][Self::actions] are set to empty [`Vec`]s and [`enemy`][Self::enemy] is set to [`None`]
+    /// [`items`][Self::items], [`hidden_items`][Self::hidden_items], and [`actions`][Self::actions] are set to empty [`Vec`]s and [`enemy`][Self::enemy], [`shop`][Self::shop], [`trap`][Self::trap], [`npc`][Self::npc] and [`hazard`][Self::hazard] are set to [`None`]
     pub fn new(room: Room, connections: Vec<RoomTransition>) -> Self {
         Self {
             room,
             items: Vec::new(),
+            hidden_items: Vec::new(),
             enemy: None,
             connections,
             actions: Vec::new(),
+            shop: None,
+            visited: false,
+            known: false,
+            trap: None,
+            npc: None,
+            hazard: None,
         }
     }
 
@@ -145,6 +306,13 @@ impl RoomState {
         self
     }
 
+    /// Takes a [`RoomState`] by value and returns a new one with the given [`Item`] added to [`hidden_items`][Self::hidden_items].
+    /// See [`RoomState`] docs for usage.
+    pub fn hide_item(mut self, item: Item) -> Self {
+        self.hidden_items.push(item);
+        self
+    }
+
     /// Takes a [`RoomState`] by value and returns a new one with the given [`RoomAction`] added to [`actions`][Self::actions].
     /// See [`RoomState`] docs for usage.
     pub fn add_action(mut self, action: RoomAction) -> Self {
@@ -162,6 +330,69 @@ impl RoomState {
         self.enemy = Some(enemy);
         self
     }
+
+    /// Takes a [`RoomState`] by value and returns a new one with [`shop`][Self::shop] set to the given [`Shop`].
+    /// See [`RoomState`] docs for usage.
+    /// 
+    /// ### Panics
+    /// * If [`shop`][Self::shop] is already [`Some`], most likely if this method was called twice
+    pub fn with_shop(mut self, shop: Shop) -> Self {
+        assert!(self.shop.is_none());
+        self.shop = Some(shop);
+        self
+    }
+
+    /// Takes a [`RoomState`] by value and returns a new one with [`trap`][Self::trap] set to the given [`Trap`].
+    /// See [`RoomState`] docs for usage.
+    /// 
+    /// ### Panics
+    /// * If [`trap`][Self::trap] is already [`Some`], most likely if this method was called twice
+    pub fn with_trap(mut self, trap: Trap) -> Self {
+        assert!(self.trap.is_none());
+        self.trap = Some(trap);
+        self
+    }
+
+    /// Takes a [`RoomState`] by value and returns a new one with [`npc`][Self::npc] set to the given [`Npc`].
+    /// See [`RoomState`] docs for usage.
+    /// 
+    /// ### Panics
+    /// * If [`npc`][Self::npc] is already [`Some`], most likely if this method was called twice
+    pub fn with_npc(mut self, npc: Npc) -> Self {
+        assert!(self.npc.is_none());
+        self.npc = Some(npc);
+        self
+    }
+
+    /// Takes a [`RoomState`] by value and returns a new one with [`hazard`][Self::hazard] set to the given [`Hazard`].
+    /// See [`RoomState`] docs for usage.
+    /// 
+    /// ### Panics
+    /// * If [`hazard`][Self::hazard] is already [`Some`], most likely if this method was called twice
+    pub fn with_hazard(mut self, hazard: Hazard) -> Self {
+        assert!(self.hazard.is_none());
+        self.hazard = Some(hazard);
+        self
+    }
+
+    /// Deals with the room's [`Hazard`], putting the items it [revealed][Hazard::reveals] in the room.
+    /// Returns the hazard, or [`None`] if there wasn't one.
+    pub fn resolve_hazard(&mut self) -> Option<Hazard> {
+        let hazard = self.hazard.take()?;
+        self.items.extend_from_slice(hazard.reveals);
+        Some(hazard)
+    }
+}
+
+/// A noise which draws the crew's attention for a while, such as the [holo-screen][RoomAction::MessHallTurnUpTheGame] in the [`MessHall`][Room::MessHall].
+/// Enemies in the noisy room and the rooms next to it are distracted: they don't [pursue][crate::player::Player::check_pursuit] the player,
+/// are easier to [sneak][crate::player::Player::sneak] past, and wandering patrols head for the noise instead of [ambushing][crate::player::Player::roll_random_encounter] the player elsewhere.
+#[derive(Debug, Clone, Copy)]
+pub struct Distraction {
+    /// The room the noise is coming from
+    pub room: Room,
+    /// How many more turns the noise lasts for
+    pub turns: usize,
 }
 
 /// The state of all rooms
@@ -169,16 +400,241 @@ impl RoomState {
 pub struct RoomGraph {
     /// A map from a [`Room`] to a [`RoomState`]
     pub rooms: HashMap<Room, RoomState>,
+    /// The ship-wide [`Alarm`], which affects every room
+    pub alarm: Alarm,
+    /// The [`Distraction`] currently drawing the crew's attention, if there is one
+    pub distraction: Option<Distraction>,
+    /// Whether the ship's power is on. It is switched at the [breakers][RoomAction::EngineRoomFlipBreakers] in the [`EngineRoom`][Room::EngineRoom].
+    /// Without power the rooms are dark, which makes sneaking easier, and the doors' buttons don't work, so every door is stuck as it is.
+    pub power_on: bool,
 }
 
 impl RoomGraph {
     /// Get a shared reference to the [`RoomState`] for a given [`Room`]
+    ///
+    /// ### Panics
+    /// * If the [`Room`] has no [`RoomState`], which can't happen in a graph built by [`map::init`][crate::map::init]
     pub fn get_state(&self, room: Room) -> &RoomState {
-        self.rooms.get(&room).unwrap()
+        self.rooms.get(&room).unwrap_or_else(|| panic!("The {room:?} has no RoomState in the room graph"))
     }
 
     /// Get a mutable reference to the [`RoomState`] for a given [`Room`]
+    ///
+    /// ### Panics
+    /// * If the [`Room`] has no [`RoomState`], which can't happen in a graph built by [`map::init`][crate::map::init]
     pub fn get_state_mut(&mut self, room: Room) -> &mut RoomState {
-        self.rooms.get_mut(&room).unwrap()
+        self.rooms.get_mut(&room).unwrap_or_else(|| panic!("The {room:?} has no RoomState in the room graph"))
+    }
+
+    /// Sets the state of the door between two [`Room`]s, on both sides of the door
+    pub fn set_door(&mut self, a: Room, b: Room, door: DoorState) {
+        for (from, to) in [(a, b), (b, a)] {
+            for transition in &mut self.get_state_mut(from).connections {
+                if transition.to == to {
+                    transition.door = door;
+                }
+            }
+        }
+    }
+
+    /// Checks whether enemies in the given [`Room`] are [distracted][Distraction], because it is the noisy room or next to it
+    pub fn is_distracted(&self, room: Room) -> bool {
+        self.distraction
+            .is_some_and(|d| d.room == room || self.get_state(d.room).connections.iter().any(|c| c.to == room))
+    }
+
+    /// Counts down the current [`Distraction`] by a turn.
+    /// Returns `true` if it has just worn off.
+    pub fn tick_distraction(&mut self) -> bool {
+        let Some(distraction) = &mut self.distraction else {
+            return false;
+        };
+
+        distraction.turns = distraction.turns.saturating_sub(1);
+        if distraction.turns > 0 {
+            return false;
+        }
+
+        self.distraction = None;
+        true
+    }
+
+    /// Marks every [`Room`] as [known][RoomState::known], so that they all show up on the [map][Self::render_map]
+    pub fn reveal_all(&mut self) {
+        for state in self.rooms.values_mut() {
+            state.known = true;
+        }
+    }
+
+    /// Draws a map of every [visited][RoomState::visited] or [known][RoomState::known] [`Room`] and where its connections lead.
+    /// Connections between two visited rooms are drawn with a solid line, and ones leading to a room that is only known with a dashed line.
+    /// Rooms the [`Player`][crate::player::Player] knows nothing about are shown as `???`.
+    pub fn render_map(&self) -> String {
+        // Rooms without any connections can't be reached, like the escape, so they aren't drawn
+        let mut rooms: Vec<&RoomState> = self.rooms.values().filter(|s| (s.visited || s.known) && !s.connections.is_empty()).collect();
+        rooms.sort_by_key(|s| s.room);
+
+        rooms.into_iter().fold(String::new(), |mut map, state| {
+            let unexplored = if state.visited { "" } else { " (unexplored)" };
+            // Writing to a String can't fail
+            let _ = writeln!(map, "{}{unexplored}", state.room.get_name());
+
+            for transition in &state.connections {
+                let to = self.get_state(transition.to);
+                let (line, name) = match (state.visited && to.visited, to.visited || to.known) {
+                    (true, _) => ("───", to.room.get_name()),
+                    (false, true) => ("- -", to.room.get_name()),
+                    (false, false) => ("- -", "???"),
+                };
+                let _ = writeln!(map, "    {} {line} {name}", transition.direction.get_name());
+            }
+            map
+        })
+    }
+
+    /// Gets how many moves it takes to get from one [`Room`] to another, only passing through [visited][RoomState::visited] rooms and open doors.
+    /// Returns [`None`] if there is no such route.
+    pub fn get_visited_distance(&self, from: Room, to: Room) -> Option<usize> {
+        let mut distances = HashMap::from([(from, 0)]);
+        let mut to_visit = VecDeque::from([from]);
+
+        while let Some(room) = to_visit.pop_front() {
+            let distance = distances[&room];
+            if room == to {
+                return Some(distance);
+            }
+
+            for transition in &self.get_state(room).connections {
+                if transition.door != DoorState::Open {
+                    continue;
+                }
+
+                if self.get_state(transition.to).visited && !distances.contains_key(&transition.to) {
+                    distances.insert(transition.to, distance + 1);
+                    to_visit.push_back(transition.to);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Gets every connection between two [`Room`]s, including the ones which [`RoomAction`]s can create.
+    /// Connections through [locked][DoorState::Locked] doors are only included if a [`RoomAction`] can unlock them.
+    fn get_all_connections(&self) -> HashSet<(Room, Room)> {
+        let mut connections = HashSet::new();
+
+        for (room, state) in &self.rooms {
+            for transition in state.connections.iter().filter(|t| t.door != DoorState::Locked) {
+                connections.insert((*room, transition.to));
+            }
+            for action in &state.actions {
+                if let Some(connection) = action.get_unlocked_connection() {
+                    connections.insert(connection);
+                }
+            }
+        }
+
+        connections
+    }
+
+    /// Gets every [`Room`] which the player could get to from the given [`Room`], including the [`Room`] itself
+    fn get_reachable_rooms(&self, from: Room) -> HashSet<Room> {
+        let connections = self.get_all_connections();
+
+        let mut reachable = HashSet::from([from]);
+        let mut to_visit = vec![from];
+
+        while let Some(room) = to_visit.pop() {
+            for (_, to) in connections.iter().filter(|(f, _)| *f == room) {
+                if reachable.insert(*to) {
+                    to_visit.push(*to);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Checks the [`RoomGraph`] for mistakes. Returns every [`ValidationProblem`] that was found, which will be empty if the graph is valid.
+    ///
+    /// The graph is checked for:
+    /// * Connections to [`Room`]s which aren't in [`rooms`][Self::rooms]
+    /// * Connections which can't be followed back the other way, either directly or once a [`RoomAction`] has unlocked them
+    /// * Reachable [`Room`]s which have no name or description
+    /// * [`Room::Escape`] not being reachable from [`Room::Bridge`]
+    pub fn validate(&self) -> Vec<ValidationProblem> {
+        let mut problems = Vec::new();
+        let connections = self.get_all_connections();
+
+        for (room, state) in &self.rooms {
+            for transition in &state.connections {
+                // Transitions which keep the player in the same room go nowhere, so don't need to be reciprocal
+                if transition.to == *room {
+                    continue;
+                }
+
+                if !self.rooms.contains_key(&transition.to) {
+                    problems.push(ValidationProblem::DanglingConnection { from: *room, to: transition.to });
+                } else if !connections.contains(&(transition.to, *room)) {
+                    problems.push(ValidationProblem::OneWayConnection { from: *room, to: transition.to });
+                }
+            }
+        }
+
+        let reachable = self.get_reachable_rooms(Room::Bridge);
+
+        for room in &reachable {
+            if room.get_name().is_empty() || room.get_description().is_empty() {
+                problems.push(ValidationProblem::MissingText(*room));
+            }
+        }
+
+        if !reachable.contains(&Room::Escape) {
+            problems.push(ValidationProblem::EscapeUnreachable);
+        }
+
+        problems
+    }
+}
+
+/// A mistake in a [`RoomGraph`], found by [`RoomGraph::validate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationProblem {
+    /// A connection goes to a [`Room`] which isn't in the graph, so the game would crash if the player followed it
+    DanglingConnection {
+        /// The [`Room`] the connection starts from
+        from: Room,
+        /// The [`Room`] which is missing from the graph
+        to: Room,
+    },
+    /// A connection can't be followed back the other way
+    OneWayConnection {
+        /// The [`Room`] the connection starts from
+        from: Room,
+        /// The [`Room`] which has no connection back
+        to: Room,
+    },
+    /// A reachable [`Room`] has an empty name or description
+    MissingText(Room),
+    /// [`Room::Escape`] can't be reached from [`Room::Bridge`], so the game can't be won
+    EscapeUnreachable,
+}
+
+impl ValidationProblem {
+    /// Whether this problem would break the game. Other problems are probably mistakes, but the game can still be played with them.
+    pub const fn is_fatal(self) -> bool {
+        matches!(self, Self::DanglingConnection { .. } | Self::EscapeUnreachable)
+    }
+}
+
+impl Display for ValidationProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DanglingConnection { from, to } => write!(f, "the {from:?} connects to the {to:?}, which isn't in the room graph"),
+            Self::OneWayConnection { from, to } => write!(f, "the {from:?} connects to the {to:?}, but there's no way back"),
+            Self::MissingText(room) => write!(f, "the {room:?} has an empty name or description"),
+            Self::EscapeUnreachable => write!(f, "the Escape can't be reached from the Bridge"),
+        }
     }
 }
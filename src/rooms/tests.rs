@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use crate::map;
+
+use super::*;
+
+/// Creates a [`RoomTransition`] to the given [`Room`] for testing
+const fn test_transition(to: Room) -> RoomTransition {
+    RoomTransition {
+        message: "",
+        to,
+        direction: Direction::North,
+        prompt_text: None,
+        door: DoorState::Open,
+        lock_pins: 0,
+    }
+}
+
+/// Tests that the map which the game uses has no problems which would break the game
+#[test]
+fn test_map_is_valid() {
+    let problems = map::init().validate();
+    assert!(problems.iter().all(|p| !p.is_fatal()), "{problems:?}");
+    assert!(!problems.iter().any(|p| matches!(p, ValidationProblem::MissingText(_))), "{problems:?}");
+}
+
+/// Tests that every [`Room`] has a [`RoomState`] in the map the game uses, so that looking one up can't panic
+#[test]
+fn test_every_room_has_state() {
+    let graph = map::init();
+
+    assert_eq!(Room::ALL.len(), 15);
+    for room in Room::ALL {
+        assert_eq!(graph.get_state(room).room, room);
+    }
+    assert_eq!(graph.rooms.len(), Room::ALL.len());
+}
+
+/// Tests that connections to missing rooms, one-way connections and an unreachable escape are all found
+#[test]
+fn test_invalid_graph() {
+    let graph = RoomGraph {
+        rooms: HashMap::from([
+            (Room::Bridge, RoomState::new(Room::Bridge, vec![test_transition(Room::UpperCorridor), test_transition(Room::Cells)])),
+            (Room::UpperCorridor, RoomState::new(Room::UpperCorridor, vec![test_transition(Room::Bridge)])),
+            (Room::Cells, RoomState::new(Room::Cells, Vec::new())),
+        ]),
+        alarm: Alarm::default(),
+        distraction: None,
+        power_on: true,
+    };
+
+    let problems = graph.validate();
+
+    assert!(problems.contains(&ValidationProblem::OneWayConnection { from: Room::Bridge, to: Room::Cells }));
+    assert!(problems.contains(&ValidationProblem::EscapeUnreachable));
+    assert!(!problems.contains(&ValidationProblem::OneWayConnection { from: Room::Bridge, to: Room::UpperCorridor }));
+
+    let graph = RoomGraph {
+        rooms: HashMap::from([(Room::Bridge, RoomState::new(Room::Bridge, vec![test_transition(Room::MessHall)]))]),
+        alarm: Alarm::default(),
+        distraction: None,
+        power_on: true,
+    };
+    assert!(graph.validate().contains(&ValidationProblem::DanglingConnection { from: Room::Bridge, to: Room::MessHall }));
+}
+
+/// Tests that fast travel distances are the shortest route, and only go through visited rooms
+#[test]
+fn test_visited_distance() {
+    let mut graph = map::init();
+    for room in [Room::Cells, Room::UpperCorridor, Room::MessHall, Room::Kitchen] {
+        graph.get_state_mut(room).visited = true;
+    }
+
+    assert_eq!(graph.get_visited_distance(Room::Cells, Room::Cells), Some(0));
+    assert_eq!(graph.get_visited_distance(Room::Cells, Room::Kitchen), Some(3));
+
+    // The stairwell hasn't been visited, so the crew area can't be reached through it
+    graph.get_state_mut(Room::CrewArea).visited = true;
+    assert_eq!(graph.get_visited_distance(Room::Cells, Room::CrewArea), None);
+}
+
+/// Tests that doors are changed on both sides, and that fast travel can't go through closed doors
+#[test]
+fn test_set_door() {
+    let mut graph = map::init();
+    for room in [Room::Cells, Room::UpperCorridor, Room::MessHall] {
+        graph.get_state_mut(room).visited = true;
+    }
+    assert_eq!(graph.get_visited_distance(Room::Cells, Room::MessHall), Some(2));
+
+    graph.set_door(Room::MessHall, Room::UpperCorridor, DoorState::Closed);
+
+    let door_to = |graph: &RoomGraph, from, to| graph.get_state(from).connections.iter().find(|t| t.to == to).unwrap().door;
+    assert_eq!(door_to(&graph, Room::UpperCorridor, Room::MessHall), DoorState::Closed);
+    assert_eq!(door_to(&graph, Room::MessHall, Room::UpperCorridor), DoorState::Closed);
+    assert_eq!(graph.get_visited_distance(Room::Cells, Room::MessHall), None);
+}
+
+/// Tests that the map only shows rooms the player has been in or knows about, and draws connections to known rooms differently to explored ones
+#[test]
+fn test_render_map() {
+    let mut graph = map::init();
+    assert!(graph.render_map().is_empty());
+
+    graph.get_state_mut(Room::Cells).visited = true;
+    graph.get_state_mut(Room::UpperCorridor).visited = true;
+    let map = graph.render_map();
+    assert!(map.contains(&format!("─── {}", Room::UpperCorridor.get_name())));
+    assert!(map.contains("- - ???"));
+    assert!(!map.contains("(unexplored)"));
+
+    graph.reveal_all();
+    let map = graph.render_map();
+    assert!(map.contains(&format!("{} (unexplored)", Room::Bridge.get_name())));
+    assert!(map.contains(&format!("- - {}", Room::Bridge.get_name())));
+    assert!(!map.contains("???"));
+}
@@ -0,0 +1,205 @@
+//! Autosaving, so that a run isn't lost if the game is closed.
+//!
+//! Everything in the game is decided by the run's seed and the player's answers to the [`Menu`], so rather than writing out the whole state,
+//! a save stores the seed and every answer the player has given. A [`RecordingMenu`] keeps track of the answers,
+//! and resuming a save replays them into a fresh game until it reaches the point where the player left off.
+
+mod tests;
+
+use std::collections::VecDeque;
+use std::fs;
+
+use crate::config;
+use crate::menu::{Error, Menu, OptionList, Screen};
+
+/// An answer the player gave to a [`Menu`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// The player chose the option at the given index from an [`OptionList`]
+    Choice(usize),
+    /// The player cancelled an [`OptionList`]
+    Cancel,
+    /// The player answered a [confirmation][Menu::confirm]
+    Confirm(bool),
+    /// The player typed in a number for a [number prompt][Menu::prompt_number]
+    Number(usize),
+}
+
+impl Answer {
+    /// Converts the [`Answer`] into the line it is saved as
+    fn to_line(self) -> String {
+        match self {
+            Self::Choice(i) => i.to_string(),
+            Self::Cancel => "-".to_string(),
+            Self::Confirm(true) => "y".to_string(),
+            Self::Confirm(false) => "n".to_string(),
+            Self::Number(n) => format!("#{n}"),
+        }
+    }
+
+    /// Parses a line written by [`to_line`][Answer::to_line], or returns [`None`] if it isn't valid
+    fn from_line(line: &str) -> Option<Self> {
+        match line {
+            "-" => Some(Self::Cancel),
+            "y" => Some(Self::Confirm(true)),
+            "n" => Some(Self::Confirm(false)),
+            n if n.starts_with('#') => n[1..].parse().ok().map(Self::Number),
+            i => i.parse().ok().map(Self::Choice),
+        }
+    }
+}
+
+/// Converts a seed and a list of [`Answer`]s into the contents of a save file.
+/// The seed goes on the first line, followed by one [`Answer`] per line.
+fn serialize(seed: u64, answers: &[Answer]) -> String {
+    let mut contents = format!("{seed}\n");
+    for answer in answers {
+        contents += &answer.to_line();
+        contents.push('\n');
+    }
+    contents
+}
+
+/// Parses the contents of a save file written by [`serialize`], or returns [`None`] if it is corrupt
+fn deserialize(contents: &str) -> Option<(u64, Vec<Answer>)> {
+    let mut lines = contents.lines();
+    let seed = lines.next()?.parse().ok()?;
+    let answers = lines.map(Answer::from_line).collect::<Option<_>>()?;
+
+    Some((seed, answers))
+}
+
+/// Loads the seed and [`Answer`]s from the [autosave][config::AUTOSAVE_PATH], or returns [`None`] if there isn't a valid one
+pub fn load_autosave() -> Option<(u64, Vec<Answer>)> {
+    let contents = fs::read_to_string(config::AUTOSAVE_PATH).ok()?;
+    deserialize(&contents)
+}
+
+/// Writes the seed and [`Answer`]s to the [autosave][config::AUTOSAVE_PATH].
+/// The save is written to a temporary file which is then renamed over the old one,
+/// so the autosave is never left half-written if the game is killed part way through.
+pub fn write_autosave(seed: u64, answers: &[Answer]) -> Result<(), std::io::Error> {
+    let temp_path = format!("{}.tmp", config::AUTOSAVE_PATH);
+
+    fs::write(&temp_path, serialize(seed, answers))?;
+    fs::rename(temp_path, config::AUTOSAVE_PATH)
+}
+
+/// Deletes the [autosave][config::AUTOSAVE_PATH], for when a run has finished and can't be resumed
+pub fn delete_autosave() {
+    // There may not be an autosave to delete, and a leftover one is harmless, so errors are ignored
+    let _ = fs::remove_file(config::AUTOSAVE_PATH);
+}
+
+/// A [`Menu`] which keeps track of every [`Answer`] the player gives to another [`Menu`], so that they can be saved.
+/// It can also be given [`Answer`]s to replay, which it will answer with instead of asking the player until they run out.
+#[derive(Debug)]
+pub struct RecordingMenu<M: Menu> {
+    /// The [`Menu`] the player is actually shown
+    inner: M,
+    /// The [`Answer`]s still to be replayed. Screens aren't shown while these are being replayed.
+    replay: VecDeque<Answer>,
+    /// Every [`Answer`] given so far, including replayed ones
+    answers: Vec<Answer>,
+}
+
+impl<M: Menu> RecordingMenu<M> {
+    /// Creates a [`RecordingMenu`] which will replay the given [`Answer`]s before showing anything from `inner`
+    pub fn new(inner: M, replay: Vec<Answer>) -> Self {
+        Self {
+            inner,
+            replay: replay.into(),
+            answers: Vec::new(),
+        }
+    }
+
+    /// Stops recording, giving back the [`Menu`] the player is shown
+    pub fn into_inner(self) -> M {
+        self.inner
+    }
+
+    /// Gets every [`Answer`] given so far
+    pub fn get_answers(&self) -> &[Answer] {
+        &self.answers
+    }
+
+    /// Gets the next [`Answer`] to replay, if it passes the given check.
+    /// If it doesn't, the save must be from a different version of the game, so the rest of the replay is abandoned.
+    fn next_replayed(&mut self, is_valid: impl FnOnce(Answer) -> bool) -> Option<Answer> {
+        let answer = self.replay.pop_front()?;
+
+        if is_valid(answer) {
+            return Some(answer);
+        }
+
+        self.replay.clear();
+        None
+    }
+
+    /// Whether the [`RecordingMenu`] is still replaying [`Answer`]s
+    fn is_replaying(&self) -> bool {
+        !self.replay.is_empty()
+    }
+}
+
+impl<M: Menu> Menu for RecordingMenu<M> {
+    fn new() -> Result<Self, std::io::Error> {
+        Ok(Self::new(M::new()?, Vec::new()))
+    }
+
+    fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
+        let num_options = list.options.len();
+        let choice = match self.next_replayed(|a| matches!(a, Answer::Choice(i) if i < num_options)) {
+            Some(Answer::Choice(i)) => i,
+            _ => self.inner.try_show_option_list(list)?,
+        };
+
+        self.answers.push(Answer::Choice(choice));
+        Ok(choice)
+    }
+
+    fn try_show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, Error> {
+        let num_options = list.options.len();
+        let choice = match self.next_replayed(|a| matches!(a, Answer::Cancel) || matches!(a, Answer::Choice(i) if i < num_options)) {
+            Some(Answer::Choice(i)) => Some(i),
+            Some(_) => None,
+            None => self.inner.try_show_option_list_cancellable(list)?,
+        };
+
+        self.answers.push(choice.map_or(Answer::Cancel, Answer::Choice));
+        Ok(choice)
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error> {
+        // The player has already seen the screens which are being replayed
+        if self.is_replaying() {
+            return Ok(());
+        }
+
+        self.inner.try_show_screen(screen)
+    }
+
+    fn get_controls(&self) -> &'static str {
+        self.inner.get_controls()
+    }
+
+    fn try_prompt_number(&mut self, prompt: &str, max: usize) -> Result<usize, Error> {
+        let number = match self.next_replayed(|a| matches!(a, Answer::Number(n) if n <= max)) {
+            Some(Answer::Number(n)) => n,
+            _ => self.inner.try_prompt_number(prompt, max)?,
+        };
+
+        self.answers.push(Answer::Number(number));
+        Ok(number)
+    }
+
+    fn try_confirm(&mut self, prompt: &str) -> Result<bool, Error> {
+        let answer = match self.next_replayed(|a| matches!(a, Answer::Confirm(_))) {
+            Some(Answer::Confirm(answer)) => answer,
+            _ => self.inner.try_confirm(prompt)?,
+        };
+
+        self.answers.push(Answer::Confirm(answer));
+        Ok(answer)
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(test)]
+
+use crate::menu::tests::MockMenu;
+
+use super::*;
+
+/// Creates a list of options with the given length for testing
+fn test_options(num_options: usize) -> Vec<String> {
+    (0..num_options).map(|i| i.to_string()).collect()
+}
+
+/// Tests that a save can be read back exactly as it was written, and that corrupt saves are rejected
+#[test]
+fn test_serialize() {
+    let answers = [Answer::Choice(3), Answer::Cancel, Answer::Confirm(true), Answer::Confirm(false), Answer::Number(12)];
+
+    let contents = serialize(2168, &answers);
+    assert_eq!(deserialize(&contents), Some((2168, answers.to_vec())));
+
+    assert_eq!(deserialize(""), None);
+    assert_eq!(deserialize("2168\n3\nnot an answer\n"), None);
+}
+
+/// Tests that replayed answers are used before the player is asked, and that all answers are recorded
+#[test]
+fn test_replay() {
+    let options = test_options(3);
+    let inner = MockMenu::with_numbers([Some(2)]);
+    let mut menu = RecordingMenu::new(inner, vec![Answer::Choice(1), Answer::Cancel]);
+
+    assert_eq!(menu.show_option_list(OptionList::new(&options, "")), 1);
+    assert_eq!(menu.show_option_list_cancellable(OptionList::new(&options, "")), None);
+    // The replay has run out, so this comes from the inner menu
+    assert_eq!(menu.show_option_list(OptionList::new(&options, "")), 2);
+
+    assert_eq!(menu.get_answers(), [Answer::Choice(1), Answer::Cancel, Answer::Choice(2)]);
+}
+
+/// Tests that a replay which doesn't match the game is abandoned
+#[test]
+fn test_mismatched_replay() {
+    let options = test_options(3);
+    let inner = MockMenu::with_numbers([Some(0)]);
+    let mut menu = RecordingMenu::new(inner, vec![Answer::Choice(5), Answer::Choice(1)]);
+
+    // The replayed choice is out of range, so the player is asked instead
+    assert_eq!(menu.show_option_list(OptionList::new(&options, "")), 0);
+    assert!(!menu.is_replaying());
+}
@@ -0,0 +1,146 @@
+//! Contains the [`Settings`] type, which holds the options the player can change while the game is running.
+//! They start out as the values in [`config`], and any changes are kept in the file at [`SETTINGS_PATH`][config::SETTINGS_PATH] for next time.
+
+mod tests;
+
+use std::fs;
+use std::sync::{PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::combat::HealthDisplay;
+use crate::config;
+use crate::difficulty::Difficulty;
+use crate::menu::{Menu, OptionList};
+
+/// The settings currently in use. These start as [`Settings::DEFAULT`] until [`init`] loads the settings file.
+static SETTINGS: RwLock<Settings> = RwLock::new(Settings::DEFAULT);
+
+/// Options the player can change from the [settings menu][show], which would otherwise be fixed by [`config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Whether to show coloured text. Defaults to [`config::COLORS_ENABLED`].
+    pub colors: bool,
+    /// Whether the terminal menu reveals text a character at a time. Defaults to [`config::TYPEWRITER`].
+    pub typewriter: bool,
+    /// How health is shown. Defaults to [`config::HEALTH_DISPLAY`].
+    pub health_display: HealthDisplay,
+    /// How forgiving the game is. Defaults to [`config::DIFFICULTY`].
+    pub difficulty: Difficulty,
+    /// Whether enemies are adjusted to suit how the player is doing. Defaults to [`config::DYNAMIC_BALANCE`].
+    pub dynamic_balance: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// One of the [`Settings`], as it is listed in the [settings menu][show]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    /// [`Settings::colors`]
+    Colors,
+    /// [`Settings::typewriter`]
+    Typewriter,
+    /// [`Settings::health_display`]
+    HealthDisplay,
+    /// [`Settings::difficulty`]
+    Difficulty,
+    /// [`Settings::dynamic_balance`]
+    DynamicBalance,
+}
+
+impl Setting {
+    /// Every [`Setting`], in the order they are listed in the [settings menu][show]
+    pub const ALL: [Self; 5] = [Self::Colors, Self::Typewriter, Self::HealthDisplay, Self::Difficulty, Self::DynamicBalance];
+
+    /// Gets the name of the setting, as it is shown in the [settings menu][show]
+    const fn get_name(self) -> &'static str {
+        match self {
+            Self::Colors => "Colours",
+            Self::Typewriter => "Typewriter effect",
+            Self::HealthDisplay => "Show health as",
+            Self::Difficulty => "Difficulty",
+            Self::DynamicBalance => "Dynamic balance",
+        }
+    }
+}
+
+/// Gets how an on or off setting is shown in the [settings menu][show]
+const fn on_off(on: bool) -> &'static str {
+    if on { "On" } else { "Off" }
+}
+
+impl Settings {
+    /// The settings before the player has changed anything, as they are set in [`config`]
+    pub const DEFAULT: Self = Self {
+        colors: config::COLORS_ENABLED,
+        typewriter: config::TYPEWRITER,
+        health_display: config::HEALTH_DISPLAY,
+        difficulty: config::DIFFICULTY,
+        dynamic_balance: config::DYNAMIC_BALANCE,
+    };
+
+    /// Gets the current value of the given [`Setting`], as it is shown in the [settings menu][show]
+    pub const fn describe(self, setting: Setting) -> &'static str {
+        match setting {
+            Setting::Colors => on_off(self.colors),
+            Setting::Typewriter => on_off(self.typewriter),
+            Setting::HealthDisplay => self.health_display.get_name(),
+            Setting::Difficulty => self.difficulty.get_name(),
+            Setting::DynamicBalance => on_off(self.dynamic_balance),
+        }
+    }
+
+    /// Changes the given [`Setting`] to its next value, turning it on or off or moving on to the next choice
+    pub const fn toggle(&mut self, setting: Setting) {
+        match setting {
+            Setting::Colors => self.colors = !self.colors,
+            Setting::Typewriter => self.typewriter = !self.typewriter,
+            Setting::HealthDisplay => self.health_display = self.health_display.next(),
+            Setting::Difficulty => self.difficulty = self.difficulty.next(),
+            Setting::DynamicBalance => self.dynamic_balance = !self.dynamic_balance,
+        }
+    }
+}
+
+/// Gets the [`Settings`] currently in use
+pub fn get() -> Settings {
+    *SETTINGS.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Loads the [`Settings`] from [`SETTINGS_PATH`][config::SETTINGS_PATH], if the player has saved any.
+/// This should be called once, before the game starts. A missing or broken file leaves the [defaults][Settings::DEFAULT] in place.
+pub fn init() {
+    let loaded = fs::read_to_string(config::SETTINGS_PATH).ok().and_then(|text| serde_json::from_str(&text).ok());
+
+    if let Some(settings) = loaded {
+        *SETTINGS.write().unwrap_or_else(PoisonError::into_inner) = settings;
+    }
+}
+
+/// Shows the settings menu, where each [`Setting`] can be changed by choosing it until the player goes back.
+/// Every change takes effect straight away and is saved to [`SETTINGS_PATH`][config::SETTINGS_PATH].
+pub fn show(menu: &mut impl Menu) {
+    loop {
+        let settings = get();
+        let options: Vec<_> = Setting::ALL.iter().map(|s| format!("{}: {}", s.get_name(), settings.describe(*s))).collect();
+        let list = OptionList::new(&options, "Settings - choose one to change it").with_cancel_text("Back");
+
+        let Some(choice) = menu.show_option_list_cancellable(list) else {
+            return;
+        };
+
+        let mut settings = settings;
+        settings.toggle(Setting::ALL[choice]);
+        *SETTINGS.write().unwrap_or_else(PoisonError::into_inner) = settings;
+
+        // Failing to save only means the change is forgotten next time, so the game carries on
+        if let Ok(text) = serde_json::to_string_pretty(&settings) {
+            let _ = fs::write(config::SETTINGS_PATH, text);
+        }
+    }
+}
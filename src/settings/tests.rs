@@ -0,0 +1,35 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that toggling a setting moves it to its next value and eventually back round to where it started
+#[test]
+fn test_toggle() {
+    for setting in Setting::ALL {
+        let mut settings = Settings::DEFAULT;
+        let start = settings.describe(setting);
+
+        settings.toggle(setting);
+        assert_ne!(settings.describe(setting), start, "{setting:?}");
+
+        for _ in 0..2 {
+            if settings.describe(setting) != start {
+                settings.toggle(setting);
+            }
+        }
+        assert_eq!(settings, Settings::DEFAULT, "{setting:?}");
+    }
+}
+
+/// Tests that settings can be written out and read back in, and that settings missing from the file are left as their defaults
+#[test]
+fn test_serialize() {
+    let mut settings = Settings::DEFAULT;
+    settings.toggle(Setting::Difficulty);
+    settings.toggle(Setting::Colors);
+
+    let json = serde_json::to_string(&settings).unwrap();
+    assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+
+    assert_eq!(serde_json::from_str::<Settings>(r#"{ "typewriter": false }"#).unwrap(), Settings { typewriter: false, ..Settings::DEFAULT });
+}
@@ -0,0 +1,156 @@
+//! Functionality related to [`Shop`]s, where the [`Player`] can trade items for gold
+
+use crate::items::Item;
+use crate::menu::{Menu, OptionList, Screen};
+use crate::player::Player;
+
+/// A shop which the [`Player`] can buy items from and sell items to.
+/// Shops are stored on their [`RoomState`][crate::rooms::RoomState], so their stock persists when the player leaves.
+#[derive(Debug)]
+pub struct Shop {
+    /// The name of the shop, shown when trading
+    pub name: &'static str,
+    /// The items which the [`Player`] can buy. Items the [`Player`] sells are added here, so they can be bought back.
+    pub stock: Vec<Item>,
+}
+
+/// A trade the [`Player`] can make with a [`Shop`]
+#[derive(Debug)]
+enum Trade {
+    /// Buy the [`Item`] at the given index into the [shop's stock][Shop::stock]
+    Buy(usize),
+    /// Sell the [`Item`] at the given index into the [player's inventory][Player::inventory]
+    Sell(usize),
+}
+
+impl Shop {
+    /// Creates a new [`Shop`] with the given name and stock
+    pub const fn new(name: &'static str, stock: Vec<Item>) -> Self {
+        Self { name, stock }
+    }
+
+    /// Gets how much gold the [`Shop`] will sell an [`Item`] for, or [`None`] if it can't be traded
+    const fn get_buy_price(item: &Item) -> Option<u32> {
+        item.get_value()
+    }
+
+    /// Gets how much gold the [`Shop`] will pay for an [`Item`], or [`None`] if it can't be traded.
+    /// This is half of what the [`Shop`] sells it for, rounded up.
+    const fn get_sell_price(item: &Item) -> Option<u32> {
+        match item.get_value() {
+            Some(value) => Some(value.div_ceil(2)),
+            None => None,
+        }
+    }
+
+    /// Lets the [`Player`] buy and sell items until they choose to leave
+    pub fn trade(&mut self, player: &mut Player, menu: &mut impl Menu) {
+        loop {
+            // Init lists of options and their string representations
+            let mut options = Vec::new();
+            let mut options_str = Vec::new();
+
+            for (i, item) in self.stock.iter().enumerate() {
+                if let Some(price) = Self::get_buy_price(item) {
+                    options.push(Trade::Buy(i));
+                    options_str.push(format!(
+                        "Buy the {}{} for {} gold - {}",
+                        item.get_name(),
+                        item.get_rarity_label(),
+                        price,
+                        item.get_description()
+                    ));
+                }
+            }
+
+            for (i, (item, _)) in player.inventory.iter().enumerate() {
+                if let Some(price) = Self::get_sell_price(item) {
+                    options.push(Trade::Sell(i));
+                    options_str.push(format!("Sell your {}{} for {} gold", item.get_name(), item.get_rarity_label(), price));
+                }
+            }
+
+            if options.is_empty() {
+                menu.show_screen(Screen {
+                    title: &format!("The {} has nothing to offer", self.name),
+                    content: "There's nothing to buy, and you have nothing worth selling.",
+                    title_color: None,
+                    art: None,
+                });
+                return;
+            }
+
+            let prompt = format!("{} - You have {} gold", self.name, player.gold);
+            let list = OptionList::new(&options_str, &prompt);
+
+            // Cancelling leaves the shop
+            let Some(choice) = menu.show_option_list_cancellable(list) else {
+                return;
+            };
+
+            match options.swap_remove(choice) {
+                Trade::Buy(i) => self.buy(player, menu, i),
+                Trade::Sell(i) => self.sell(player, menu, i),
+            }
+        }
+    }
+
+    /// Sells the [`Item`] at the given index into the [shop's stock][Shop::stock] to the [`Player`], if they can afford and carry it
+    fn buy(&mut self, player: &mut Player, menu: &mut impl Menu, i: usize) {
+        let Some(price) = Self::get_buy_price(&self.stock[i]) else {unreachable!()};
+
+        if price > player.gold {
+            menu.show_screen(Screen {
+                title: &format!("You can't afford the {}", self.stock[i].get_name()),
+                content: &format!("It costs {price} gold, but you only have {}.", player.gold),
+                title_color: None,
+                art: None,
+            });
+            return;
+        }
+
+        let item = self.stock.remove(i);
+        let name = item.get_name();
+
+        // The item goes back on the shelf if it's too heavy to carry
+        if let Err(item) = player.try_pick_up_item(menu, item) {
+            self.stock.insert(i, item);
+            return;
+        }
+
+        player.gold -= price;
+
+        menu.show_screen(Screen {
+            title: &format!("You bought the {name}"),
+            content: &format!("You paid {price} gold. You have {} gold left.", player.gold),
+            title_color: None,
+            art: None,
+        });
+    }
+
+    /// Buys the [`Item`]s at the given index into the [player's inventory][Player::inventory] from the [`Player`].
+    /// If they have a stack of them, they are [asked how many][Player::choose_quantity] to sell.
+    fn sell(&mut self, player: &mut Player, menu: &mut impl Menu, i: usize) {
+        let quantity = player.choose_quantity(menu, i, "sell");
+        if quantity == 0 {
+            return;
+        }
+
+        let name = player.inventory[i].0.get_name();
+        let Some(price) = Self::get_sell_price(&player.inventory[i].0) else {unreachable!()};
+        let total = price * u32::try_from(quantity).unwrap_or(u32::MAX);
+
+        for _ in 0..quantity {
+            self.stock.push(player.take_item(i));
+        }
+        player.gold += total;
+
+        let count = if quantity > 1 { format!(" (x{quantity})") } else { String::new() };
+        menu.show_screen(Screen {
+            title: &format!("You sold your {name}{count}"),
+            content: &format!("You got {total} gold. You now have {} gold.", player.gold),
+            title_color: None,
+            art: None,
+        });
+    }
+}
@@ -0,0 +1,143 @@
+//! A speedrun timer, which measures the real time taken to escape and keeps the fastest time at [`BEST_TIME_PATH`][config::BEST_TIME_PATH]
+
+mod tests;
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::menu::{Menu, OptionList, Screen};
+
+/// Measures the real time taken by a run, from the intro being dismissed until the [`Player`][crate::player::Player] escapes.
+/// Like [`Achievements`][crate::achievements::Achievements], it keeps going when the time loop resets, and only starts again with a new run.
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    /// When the run started
+    start: Instant,
+    /// Whether the run was resumed from a save. Time spent before the game was closed isn't counted, so these runs can't set a record.
+    resumed: bool,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::start(false)
+    }
+}
+
+/// How a finished run's time compares to the fastest time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Record {
+    /// The run was resumed from a save, so its time wasn't compared
+    Resumed,
+    /// The run beat the fastest time, or there wasn't one yet
+    NewBest,
+    /// The run was slower than the fastest time, which is given
+    Slower(Duration),
+}
+
+/// The time a run took to finish, and how it compares to the fastest time
+#[derive(Debug, Clone, Copy)]
+pub struct Finish {
+    /// How long the run took
+    pub time: Duration,
+    /// How the time compares to the fastest time
+    pub record: Record,
+}
+
+impl Timer {
+    /// Starts timing a run from now
+    pub fn start(resumed: bool) -> Self {
+        Self { start: Instant::now(), resumed }
+    }
+
+    /// Gets how long the run has taken so far
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Stops timing the run, and compares its time to the fastest time.
+    /// Nothing is written to disk - a new fastest time is only kept once it is [saved][Finish::save_best].
+    pub fn finish(&self) -> Finish {
+        let time = self.elapsed();
+
+        let record = if self.resumed {
+            Record::Resumed
+        } else {
+            match load_best() {
+                Some(best) if best <= time => Record::Slower(best),
+                _ => Record::NewBest,
+            }
+        };
+
+        Finish { time, record }
+    }
+
+    /// Shows the time so far and the fastest time, and lets the player reset the fastest time
+    pub fn show(&self, menu: &mut impl Menu) {
+        let best = load_best().map_or_else(|| "none yet".to_string(), format_time);
+        let resumed = if self.resumed { " (resumed from a save, so this run can't set a record)" } else { "" };
+        let prompt = format!("Time so far: {}{resumed}. Fastest time: {best}.", format_time(self.elapsed()));
+
+        let options = ["Reset your fastest time".to_string()];
+        let list = OptionList::new(&options, &prompt).with_cancel_text("Back");
+        if menu.show_option_list_cancellable(list).is_none() || !menu.confirm("Forget your fastest time? This can't be undone.") {
+            return;
+        }
+
+        reset_best();
+        menu.show_screen(Screen {
+            title: "Fastest time reset",
+            content: "The next run you finish will set a new fastest time.",
+            title_color: None,
+            art: None,
+        });
+    }
+}
+
+impl Finish {
+    /// Writes the time to [`BEST_TIME_PATH`][config::BEST_TIME_PATH] if it is a [new fastest time][Record::NewBest]
+    pub fn save_best(&self) {
+        if self.record == Record::NewBest {
+            // A record that can't be written is only lost, so it shouldn't stop the game
+            let _ = fs::write(config::BEST_TIME_PATH, serialize(self.time));
+        }
+    }
+
+    /// Gets a line describing the time, in the same style as [`RunStats::describe`][crate::stats::RunStats::describe]
+    pub fn describe(&self) -> String {
+        let comparison = match self.record {
+            Record::Resumed => "resumed from a save, so it can't set a record".to_string(),
+            Record::NewBest => "a new fastest time!".to_string(),
+            Record::Slower(best) => format!("fastest: {}", format_time(best)),
+        };
+
+        format!("• Real time: {} ({comparison})", format_time(self.time))
+    }
+}
+
+/// Formats a time as minutes, seconds and milliseconds, like `3:07.250`
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}.{:03}", seconds / 60, seconds % 60, time.subsec_millis())
+}
+
+/// Converts a time into the contents of the fastest time file, which is the number of milliseconds
+fn serialize(time: Duration) -> String {
+    time.as_millis().to_string()
+}
+
+/// Parses the contents of a fastest time file written by [`serialize`], or returns [`None`] if it is corrupt
+fn deserialize(contents: &str) -> Option<Duration> {
+    contents.trim().parse().ok().map(Duration::from_millis)
+}
+
+/// Loads the fastest time from [`BEST_TIME_PATH`][config::BEST_TIME_PATH], or returns [`None`] if no run has been finished yet
+pub fn load_best() -> Option<Duration> {
+    deserialize(&fs::read_to_string(config::BEST_TIME_PATH).ok()?)
+}
+
+/// Forgets the fastest time, so that the next finished run sets a new one
+pub fn reset_best() {
+    // There may not be a fastest time to forget, so errors are ignored
+    let _ = fs::remove_file(config::BEST_TIME_PATH);
+}
@@ -0,0 +1,39 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that times are shown as minutes, seconds and milliseconds
+#[test]
+fn test_format_time() {
+    assert_eq!(format_time(Duration::from_millis(0)), "0:00.000");
+    assert_eq!(format_time(Duration::from_millis(187_250)), "3:07.250");
+    assert_eq!(format_time(Duration::from_secs(3725)), "62:05.000");
+}
+
+/// Tests that a fastest time can be read back as it was written, and that corrupt files are ignored
+#[test]
+fn test_serialize() {
+    let time = Duration::from_millis(95_004);
+    assert_eq!(deserialize(&serialize(time)), Some(time));
+
+    assert_eq!(deserialize(""), None);
+    assert_eq!(deserialize("quick"), None);
+}
+
+/// Tests that resumed runs are marked as such instead of being compared to the fastest time
+#[test]
+fn test_resumed_run() {
+    let finish = Timer::start(true).finish();
+    assert_eq!(finish.record, Record::Resumed);
+    assert!(finish.describe().contains("resumed from a save"));
+}
+
+/// Tests that finishing a run doesn't write the fastest time by itself, so only saving it does
+#[test]
+fn test_finish_does_not_save() {
+    let before = fs::read_to_string(config::BEST_TIME_PATH).ok();
+
+    let finish = Timer::start(false).finish();
+    assert_ne!(finish.record, Record::Resumed);
+    assert_eq!(fs::read_to_string(config::BEST_TIME_PATH).ok(), before);
+}
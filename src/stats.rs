@@ -0,0 +1,65 @@
+//! Contains the [`RunStats`] type, which keeps running totals of what the [`Player`][crate::player::Player] has done during a loop
+
+mod tests;
+
+use serde::{Deserialize, Serialize};
+
+use crate::combat::{Damage, Health};
+use crate::menu::{Menu, Screen};
+
+/// Running totals of what the [`Player`][crate::player::Player] has done during the current loop, for comparing runs.
+/// They are shown on the win and death screens, and whenever the player asks to see them.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    /// How many turns have passed, in and out of battle
+    pub turns_taken: usize,
+    /// The total damage the [`Player`][crate::player::Player] has dealt to enemies
+    pub damage_dealt: usize,
+    /// The total damage the [`Player`][crate::player::Player] has taken from any source
+    pub damage_taken: usize,
+    /// How many battles the [`Player`][crate::player::Player] has won
+    pub enemies_defeated: usize,
+    /// How many items the [`Player`][crate::player::Player] has picked up, not counting gold
+    pub items_collected: usize,
+    /// How many different rooms the [`Player`][crate::player::Player] has been in, including the one they started in
+    pub rooms_visited: usize,
+    /// How many moves between rooms the [`Player`][crate::player::Player] has made. Fast travel counts every room on the way.
+    pub distance_traveled: usize,
+}
+
+impl RunStats {
+    /// Adds damage the [`Player`][crate::player::Player] has taken to the total
+    pub fn record_damage_taken(&mut self, damage: Damage) {
+        self.damage_taken += damage.as_usize();
+    }
+
+    /// Adds the damage an enemy has taken to the total dealt, given their health before and after.
+    /// Nothing is added if they healed instead.
+    pub fn record_damage_dealt(&mut self, before: Health, after: Health) {
+        self.damage_dealt += before.as_usize().saturating_sub(after.as_usize());
+    }
+
+    /// Gets a list of the stats, one per line
+    pub fn describe(&self) -> String {
+        format!(
+            "• Turns taken: {}\n• Damage dealt: {}\n• Damage taken: {}\n• Enemies defeated: {}\n• Items collected: {}\n• Rooms visited: {}\n• Distance travelled: {} rooms",
+            self.turns_taken,
+            self.damage_dealt,
+            self.damage_taken,
+            self.enemies_defeated,
+            self.items_collected,
+            self.rooms_visited,
+            self.distance_traveled,
+        )
+    }
+
+    /// Shows the stats on a [`Screen`]
+    pub fn show(&self, menu: &mut impl Menu) {
+        menu.show_screen(Screen {
+            title: "Your loop so far",
+            content: &self.describe(),
+            title_color: None,
+            art: None,
+        });
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that only health lost counts as damage dealt, not health healed
+#[test]
+fn test_record_damage_dealt() {
+    let mut stats = RunStats::default();
+
+    stats.record_damage_dealt(Health::new(10), Health::new(7));
+    stats.record_damage_dealt(Health::new(7), Health::new(9));
+    assert_eq!(stats.damage_dealt, 3);
+}
+
+/// Tests that stats can be written out and read back in unchanged
+#[test]
+fn test_serialize() {
+    let stats = RunStats {
+        turns_taken: 12,
+        damage_dealt: 20,
+        damage_taken: 8,
+        enemies_defeated: 2,
+        items_collected: 5,
+        rooms_visited: 6,
+        distance_traveled: 9,
+    };
+
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(serde_json::from_str::<RunStats>(&json).unwrap(), stats);
+}
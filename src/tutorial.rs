@@ -0,0 +1,88 @@
+//! Contains the [`Tutorial`] type, which explains the game to first-time players as they go
+
+use crate::menu::{Color, Menu, Screen};
+
+/// A moment in the game which the [`Tutorial`] explains the first time it happens
+#[derive(Debug, Clone, Copy)]
+pub enum TutorialPrompt {
+    /// The first time a battle starts
+    Combat,
+    /// The first time the [`Player`][crate::player::Player] picks up an item
+    PickUp,
+    /// The first time the [`Player`][crate::player::Player] tries to open a locked door
+    LockedDoor,
+}
+
+impl TutorialPrompt {
+    /// Gets the title of the [`Screen`] explaining this moment
+    const fn get_title(self) -> &'static str {
+        match self {
+            Self::Combat => "Tutorial: Fighting",
+            Self::PickUp => "Tutorial: Picking things up",
+            Self::LockedDoor => "Tutorial: Locked doors",
+        }
+    }
+
+    /// Gets the text of the [`Screen`] explaining this moment
+    const fn get_content(self) -> &'static str {
+        match self {
+            Self::Combat => "Each turn, you and your enemy both choose an action at the same time. \
+Attacking straight ahead hits unless they dodge, and aiming left or right catches them if they dodge that way. \
+Dodging and blocking keep you safe, but cost stamina.\n\
+Size up your opponent to see how healthy they are and what hurts them, or pick \"Remember how fighting works\" for the full rules. \
+If it's going badly, you can run for it through an open door.",
+            Self::PickUp => "Anything you pick up goes in your inventory, which you can open from the list of actions. \
+Everything has a weight, and you can only carry so much.\n\
+From your inventory you can equip weapons and armor to use in fights, eat food to heal, and drop what you don't need.",
+            Self::LockedDoor => "Some doors won't open until you find a way through. \
+Look around the ship for something that unlocks them - a key card, or the controls for the door.\n\
+If you find a lockpick, you can try to pick some locks yourself. Guess where each pin sits, and the feel of the pick tells you whether to go further or not as far.",
+        }
+    }
+
+    /// Gets the index of this moment's flag in [`Tutorial::seen`]
+    const fn get_index(self) -> usize {
+        match self {
+            Self::Combat => 0,
+            Self::PickUp => 1,
+            Self::LockedDoor => 2,
+        }
+    }
+}
+
+/// Explains the game to first-time players, by showing a [`Screen`] at each [`TutorialPrompt`] the first time it happens.
+/// Like [`Achievements`][crate::achievements::Achievements], this is kept when the time loop resets, so each prompt is only ever shown once.
+#[derive(Debug, Default, Clone)]
+pub struct Tutorial {
+    /// Whether the tutorial is turned on. It is chosen at the start of the game, and can be turned off at any time.
+    pub enabled: bool,
+    /// Whether each [`TutorialPrompt`] has been shown, by [index][TutorialPrompt::get_index]
+    seen: [bool; 3],
+}
+
+impl Tutorial {
+    /// Creates a [`Tutorial`] which hasn't shown anything yet, turned on or off
+    pub const fn new(enabled: bool) -> Self {
+        Self { enabled, seen: [false; 3] }
+    }
+
+    /// Shows the [`Screen`] for the given [`TutorialPrompt`], if the tutorial is turned on and it hasn't been shown before
+    pub fn show(&mut self, menu: &mut impl Menu, prompt: TutorialPrompt) {
+        if !self.enabled || self.has_seen(prompt) {
+            return;
+        }
+        self.seen[prompt.get_index()] = true;
+
+        menu.show_screen(Screen {
+            title: prompt.get_title(),
+            content: &format!("{}\n\nYou can turn the tutorial off from the list of actions at any time.", prompt.get_content()),
+            title_color: Some(Color::Cyan),
+            art: None,
+        });
+    }
+
+    /// Gets whether the [`Screen`] for the given [`TutorialPrompt`] has been shown
+    pub const fn has_seen(&self, prompt: TutorialPrompt) -> bool {
+        self.seen[prompt.get_index()]
+    }
+}